@@ -67,6 +67,13 @@ pub mod contract {
         pub paused: bool,
     }
 
+    #[ink(event)]
+    pub struct MarketFrozen {
+        #[ink(topic)]
+        pub pool: AccountId,
+        pub frozen: bool,
+    }
+
     #[ink(event)]
     pub struct ActionPaused {
         pub action: String,
@@ -97,12 +104,130 @@ pub mod contract {
         pub new: Balance,
     }
 
+    #[ink(event)]
+    pub struct NewSupplyCap {
+        pub pool: AccountId,
+        pub new: Balance,
+    }
+
     #[ink(event)]
     pub struct NewLiquidationIncentive {
         pub old: WrappedU256,
         pub new: WrappedU256,
     }
 
+    #[ink(event)]
+    pub struct NewMarketLiquidationIncentive {
+        #[ink(topic)]
+        pub pool: AccountId,
+        pub new: Option<WrappedU256>,
+    }
+
+    #[ink(event)]
+    pub struct NewProtocolSeizeShare {
+        pub old: WrappedU256,
+        pub new: WrappedU256,
+    }
+
+    #[ink(event)]
+    pub struct NewMarketProtocolSeizeShare {
+        #[ink(topic)]
+        pub pool: AccountId,
+        pub new: Option<WrappedU256>,
+    }
+
+    #[ink(event)]
+    pub struct NewCriticalHealthFactor {
+        pub old: WrappedU256,
+        pub new: WrappedU256,
+    }
+
+    #[ink(event)]
+    pub struct NewLiquidationDustThreshold {
+        pub old: Balance,
+        pub new: Balance,
+    }
+
+    #[ink(event)]
+    pub struct EModeCategoryUpdated {
+        #[ink(topic)]
+        pub id: u8,
+        pub category: EModeCategory,
+    }
+
+    #[ink(event)]
+    pub struct MarketEModeCategoryUpdated {
+        #[ink(topic)]
+        pub pool: AccountId,
+        pub id: u8,
+    }
+
+    #[ink(event)]
+    pub struct UserEModeSet {
+        #[ink(topic)]
+        pub account: AccountId,
+        pub id: u8,
+    }
+
+    #[ink(event)]
+    pub struct IsolationModeUpdated {
+        #[ink(topic)]
+        pub pool: AccountId,
+        pub isolated: bool,
+        pub debt_ceiling: Balance,
+    }
+
+    #[ink(event)]
+    pub struct BorrowableInIsolationUpdated {
+        #[ink(topic)]
+        pub pool: AccountId,
+        pub borrowable: bool,
+    }
+
+    #[ink(event)]
+    pub struct MarketDeprecated {
+        #[ink(topic)]
+        pub pool: AccountId,
+        pub ramp_duration: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct MarketRemoved {
+        #[ink(topic)]
+        pub pool: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Shutdown {
+        pub timestamp: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct SettlementPriceUpdated {
+        #[ink(topic)]
+        pub pool: AccountId,
+        pub price: u128,
+    }
+
+    #[ink(event)]
+    pub struct LiquidationAuctionUpdated {
+        pub duration: Timestamp,
+        pub start_incentive_mantissa: WrappedU256,
+    }
+
+    #[ink(event)]
+    pub struct LiquidationAuctionStarted {
+        #[ink(topic)]
+        pub account: AccountId,
+        pub started_at: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct LiquidationAuctionReset {
+        #[ink(topic)]
+        pub account: AccountId,
+    }
+
     impl Controller for ControllerContract {}
 
     impl ControllerContract {
@@ -142,6 +267,10 @@ pub mod contract {
             });
         }
 
+        fn _emit_market_frozen_event(&self, pool: AccountId, frozen: bool) {
+            self.env().emit_event(MarketFrozen { pool, frozen });
+        }
+
         fn _emit_action_paused_event(&self, action: String, paused: bool) {
             self.env().emit_event(ActionPaused { action, paused });
         }
@@ -166,8 +295,122 @@ pub mod contract {
             self.env().emit_event(NewLiquidationIncentive { old, new });
         }
 
+        fn _emit_new_market_liquidation_incentive_event(
+            &self,
+            pool: AccountId,
+            new: Option<WrappedU256>,
+        ) {
+            self.env()
+                .emit_event(NewMarketLiquidationIncentive { pool, new });
+        }
+
+        fn _emit_new_protocol_seize_share_event(&self, old: WrappedU256, new: WrappedU256) {
+            self.env().emit_event(NewProtocolSeizeShare { old, new });
+        }
+
+        fn _emit_new_market_protocol_seize_share_event(
+            &self,
+            pool: AccountId,
+            new: Option<WrappedU256>,
+        ) {
+            self.env()
+                .emit_event(NewMarketProtocolSeizeShare { pool, new });
+        }
+
+        fn _emit_new_critical_health_factor_event(&self, old: WrappedU256, new: WrappedU256) {
+            self.env().emit_event(NewCriticalHealthFactor { old, new });
+        }
+
+        fn _emit_new_liquidation_dust_threshold_event(&self, old: Balance, new: Balance) {
+            self.env()
+                .emit_event(NewLiquidationDustThreshold { old, new });
+        }
+
         fn _emit_new_borrow_cap_event(&self, pool: AccountId, new: Balance) {
             self.env().emit_event(NewBorrowCap { pool, new });
         }
+
+        fn _emit_new_supply_cap_event(&self, pool: AccountId, new: Balance) {
+            self.env().emit_event(NewSupplyCap { pool, new });
+        }
+
+        fn _emit_emode_category_updated_event(&self, id: u8, category: EModeCategory) {
+            self.env().emit_event(EModeCategoryUpdated { id, category });
+        }
+
+        fn _emit_market_emode_category_updated_event(&self, pool: AccountId, id: u8) {
+            self.env()
+                .emit_event(MarketEModeCategoryUpdated { pool, id });
+        }
+
+        fn _emit_user_emode_set_event(&self, account: AccountId, id: u8) {
+            self.env().emit_event(UserEModeSet { account, id });
+        }
+
+        fn _emit_isolation_mode_updated_event(
+            &self,
+            pool: AccountId,
+            isolated: bool,
+            debt_ceiling: Balance,
+        ) {
+            self.env().emit_event(IsolationModeUpdated {
+                pool,
+                isolated,
+                debt_ceiling,
+            });
+        }
+
+        fn _emit_borrowable_in_isolation_updated_event(&self, pool: AccountId, borrowable: bool) {
+            self.env()
+                .emit_event(BorrowableInIsolationUpdated { pool, borrowable });
+        }
+
+        fn _emit_market_deprecated_event(&self, pool: AccountId, ramp_duration: Timestamp) {
+            self.env().emit_event(MarketDeprecated {
+                pool,
+                ramp_duration,
+            });
+        }
+
+        fn _emit_market_removed_event(&self, pool: AccountId) {
+            self.env().emit_event(MarketRemoved { pool });
+        }
+
+        fn _emit_shutdown_event(&self) {
+            self.env().emit_event(Shutdown {
+                timestamp: self.env().block_timestamp(),
+            });
+        }
+
+        fn _emit_settlement_price_updated_event(&self, pool: AccountId, price: u128) {
+            self.env()
+                .emit_event(SettlementPriceUpdated { pool, price });
+        }
+
+        fn _emit_liquidation_auction_updated_event(
+            &self,
+            duration: Timestamp,
+            start_incentive_mantissa: WrappedU256,
+        ) {
+            self.env().emit_event(LiquidationAuctionUpdated {
+                duration,
+                start_incentive_mantissa,
+            });
+        }
+
+        fn _emit_liquidation_auction_started_event(
+            &self,
+            account: AccountId,
+            started_at: Timestamp,
+        ) {
+            self.env().emit_event(LiquidationAuctionStarted {
+                account,
+                started_at,
+            });
+        }
+
+        fn _emit_liquidation_auction_reset_event(&self, account: AccountId) {
+            self.env().emit_event(LiquidationAuctionReset { account });
+        }
     }
 }
@@ -3,6 +3,7 @@ use core::ops::{
     Add,
     Div,
     Mul,
+    Sub,
 };
 use ink::env::{
     test::{
@@ -16,11 +17,19 @@ use ink::env::{
 use logics::{
     impls::{
         controller::*,
-        exp_no_err::exp_scale,
+        exp_no_err::{
+            exp_scale,
+            Exp,
+        },
     },
     traits::types::WrappedU256,
 };
-use openbrush::traits::AccountId;
+use openbrush::traits::{
+    AccountId,
+    Balance,
+    Storage,
+    String,
+};
 use primitive_types::U256;
 use scale::Decode;
 
@@ -42,6 +51,21 @@ fn decode_market_listed_event(event: EmittedEvent) -> MarketListed {
     panic!("unexpected event kind: expected MarketListed event")
 }
 
+fn record_isolated_debt<T: Storage<Data>>(
+    contract: &mut T,
+    account: AccountId,
+    pool: AccountId,
+    collateral: AccountId,
+    debt: Balance,
+) {
+    let data = contract.data::<Data>();
+    let total_debt = data.isolated_debts.get(&collateral).unwrap_or(0);
+    data.isolated_debts
+        .insert(&collateral, &(total_debt + debt));
+    data.account_isolated_debts
+        .insert(&(&account, &pool), &(collateral, debt));
+}
+
 #[ink::test]
 fn new_works() {
     let accounts = default_accounts();
@@ -69,7 +93,7 @@ fn mint_allowed_works() {
     let pool = AccountId::from([0x01; 32]);
     let underlying = AccountId::from([0x01; 32]);
     assert!(contract.support_market(pool, underlying).is_ok());
-    assert!(contract.mint_allowed(pool, accounts.bob, 0).is_ok());
+    assert!(contract.mint_allowed(pool, accounts.bob, 0, None).is_ok());
 }
 
 #[ink::test]
@@ -80,7 +104,9 @@ fn mint_allowed_fail_when_not_supported() {
 
     let pool = AccountId::from([0x01; 32]);
     assert_eq!(
-        contract.mint_allowed(pool, accounts.bob, 0).unwrap_err(),
+        contract
+            .mint_allowed(pool, accounts.bob, 0, None)
+            .unwrap_err(),
         Error::MintIsPaused
     );
 }
@@ -96,11 +122,47 @@ fn mint_allowed_fail_when_paused() {
     assert!(contract.support_market(pool, underlying).is_ok());
     assert!(contract.set_mint_guardian_paused(pool, true).is_ok());
     assert_eq!(
-        contract.mint_allowed(pool, accounts.bob, 0).unwrap_err(),
+        contract
+            .mint_allowed(pool, accounts.bob, 0, None)
+            .unwrap_err(),
         Error::MintIsPaused
     );
 }
 
+#[ink::test]
+fn mint_allowed_fail_when_supply_cap_reached() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let pool = AccountId::from([0x01; 32]);
+    let underlying = AccountId::from([0x01; 32]);
+    assert!(contract.support_market(pool, underlying).is_ok());
+    assert!(contract.set_supply_cap(pool, 1_000).is_ok());
+
+    let pool_attribute = PoolAttributes {
+        pool: Some(pool),
+        exchange_rate: exp_scale(),
+        total_supply: 900,
+        ..Default::default()
+    };
+    assert!(contract
+        .mint_allowed(pool, accounts.bob, 100, Some(pool_attribute.clone()))
+        .is_ok());
+    assert_eq!(
+        contract
+            .mint_allowed(pool, accounts.bob, 101, Some(pool_attribute.clone()))
+            .unwrap_err(),
+        Error::SupplyCapReached
+    );
+    assert_eq!(
+        contract
+            .mint_allowed(pool, accounts.bob, 1_001, Some(pool_attribute))
+            .unwrap_err(),
+        Error::SupplyCapReached
+    );
+}
+
 #[ink::test]
 fn borrow_allowed_fail_when_paused() {
     let accounts = default_accounts();
@@ -119,6 +181,44 @@ fn borrow_allowed_fail_when_paused() {
     );
 }
 
+#[ink::test]
+fn set_market_frozen_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let pool = AccountId::from([0x01; 32]);
+    let underlying = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract.set_market_frozen(pool, true).unwrap_err(),
+        Error::MarketNotListed
+    );
+    assert!(contract.support_market(pool, underlying).is_ok());
+    assert!(!contract.market_frozen(pool));
+    assert!(contract.set_market_frozen(pool, true).is_ok());
+    assert!(contract.market_frozen(pool));
+
+    // frozen is distinct from paused
+    assert_eq!(contract.mint_guardian_paused(pool), Some(false));
+    assert_eq!(contract.borrow_guardian_paused(pool), Some(false));
+    assert_eq!(
+        contract
+            .mint_allowed(pool, accounts.bob, 0, None)
+            .unwrap_err(),
+        Error::MarketFrozen
+    );
+    assert_eq!(
+        contract
+            .borrow_allowed(pool, accounts.bob, 0, None)
+            .unwrap_err(),
+        Error::MarketFrozen
+    );
+
+    assert!(contract.set_market_frozen(pool, false).is_ok());
+    assert!(!contract.market_frozen(pool));
+    assert!(contract.mint_allowed(pool, accounts.bob, 0, None).is_ok());
+}
+
 #[ink::test]
 fn liquidate_borrow_allowed_fail() {
     let accounts = default_accounts();
@@ -185,6 +285,7 @@ fn support_market_works() {
     assert_eq!(contract.mint_guardian_paused(p1), Some(false));
     assert_eq!(contract.borrow_guardian_paused(p1), Some(false));
     assert_eq!(contract.borrow_cap(p1), Some(0));
+    assert_eq!(contract.supply_cap(p1), Some(0));
     let event = decode_market_listed_event(get_emitted_events()[0].clone());
     assert_eq!(event.pool, p1);
 
@@ -351,6 +452,87 @@ fn transfer_guardian_paused_works() {
     assert!(contract.transfer_guardian_paused());
 }
 
+#[ink::test]
+fn paused_actions_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let pool = AccountId::from([0x01; 32]);
+    let underlying = AccountId::from([0x01; 32]);
+    assert!(contract.support_market(pool, underlying).is_ok());
+    assert_eq!(contract.paused_actions(pool), 0);
+
+    assert!(contract
+        .set_actions_paused(pool, ACTION_REDEEM | ACTION_REPAY, true)
+        .is_ok());
+    assert_eq!(contract.paused_actions(pool), ACTION_REDEEM | ACTION_REPAY);
+    assert!(contract.action_paused(pool, ACTION_REDEEM));
+    assert!(!contract.action_paused(pool, ACTION_MINT));
+    assert_eq!(
+        contract
+            .redeem_allowed(pool, accounts.bob, 0, None)
+            .unwrap_err(),
+        Error::RedeemIsPaused
+    );
+    assert_eq!(
+        contract
+            .repay_borrow_allowed(pool, accounts.bob, accounts.bob, 0)
+            .unwrap_err(),
+        Error::RepayIsPaused
+    );
+    let events = get_emitted_events();
+    let actions: Vec<String> = events[events.len() - 2..]
+        .iter()
+        .map(|event| {
+            if let Ok(Event::PoolActionPaused(x)) = <Event as Decode>::decode(&mut &event.data[..])
+            {
+                assert_eq!(x.pool, pool);
+                assert!(x.paused);
+                return x.action
+            }
+            panic!("unexpected event kind: expected PoolActionPaused event")
+        })
+        .collect();
+    assert_eq!(actions, [String::from("Redeem"), String::from("Repay")]);
+
+    assert!(contract
+        .set_actions_paused(pool, ACTION_REDEEM, false)
+        .is_ok());
+    assert_eq!(contract.paused_actions(pool), ACTION_REPAY);
+}
+
+#[ink::test]
+fn global_paused_actions_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let pool = AccountId::from([0x01; 32]);
+    let underlying = AccountId::from([0x01; 32]);
+    assert!(contract.support_market(pool, underlying).is_ok());
+    assert_eq!(contract.global_paused_actions(), 0);
+
+    assert!(contract
+        .set_global_actions_paused(ACTION_TRANSFER, true)
+        .is_ok());
+    assert_eq!(contract.global_paused_actions(), ACTION_TRANSFER);
+    assert_eq!(contract.paused_actions(pool), 0);
+    assert!(contract.action_paused(pool, ACTION_TRANSFER));
+    assert!(contract.transfer_guardian_paused());
+    assert_eq!(
+        contract
+            .transfer_allowed(pool, accounts.bob, accounts.charlie, 0, None)
+            .unwrap_err(),
+        Error::TransferIsPaused
+    );
+
+    assert!(contract
+        .set_global_actions_paused(ACTION_TRANSFER, false)
+        .is_ok());
+    assert!(!contract.action_paused(pool, ACTION_TRANSFER));
+}
+
 #[ink::test]
 fn assert_manager_works() {
     let accounts = default_accounts();
@@ -373,11 +555,29 @@ fn assert_manager_works() {
         contract.set_collateral_factor_mantissa(dummy_id, WrappedU256::from(0)),
         contract.set_mint_guardian_paused(dummy_id, true),
         contract.set_borrow_guardian_paused(dummy_id, true),
+        contract.set_actions_paused(dummy_id, ACTION_REDEEM, true),
+        contract.set_global_actions_paused(ACTION_REDEEM, true),
+        contract.set_market_frozen(dummy_id, true),
         contract.set_seize_guardian_paused(true),
         contract.set_transfer_guardian_paused(true),
         contract.set_close_factor_mantissa(WrappedU256::from(0)),
         contract.set_liquidation_incentive_mantissa(WrappedU256::from(0)),
+        contract.set_market_liquidation_incentive_mantissa(dummy_id, None),
+        contract.set_protocol_seize_share_mantissa(WrappedU256::from(0)),
+        contract.set_market_protocol_seize_share_mantissa(dummy_id, None),
+        contract.set_liquidation_auction(0, WrappedU256::from(0)),
+        contract.set_critical_health_factor(WrappedU256::from(0)),
+        contract.set_liquidation_dust_threshold(0),
         contract.set_borrow_cap(dummy_id, 0),
+        contract.set_supply_cap(dummy_id, 0),
+        contract.set_emode_category(1, EModeCategory::default()),
+        contract.set_market_emode_category(dummy_id, 0),
+        contract.set_isolation_mode(dummy_id, true, 0),
+        contract.set_borrowable_in_isolation(dummy_id, true),
+        contract.deprecate_market(dummy_id, 0),
+        contract.remove_market(dummy_id),
+        contract.shutdown(),
+        contract.set_settlement_price(dummy_id, 1),
     ];
     for func in admin_funcs {
         assert_eq!(func.unwrap_err(), Error::CallerIsNotManager);
@@ -419,3 +619,702 @@ fn accept_manager_works() {
     assert_eq!(contract.pending_manager(), None);
     assert_eq!(contract.manager().unwrap(), accounts.alice);
 }
+
+fn stablecoin_emode_category() -> EModeCategory {
+    EModeCategory {
+        collateral_factor_mantissa: WrappedU256::from(exp_scale().mul(97).div(100)),
+        liquidation_threshold: 9800,
+        liquidation_incentive_mantissa: WrappedU256::from(exp_scale().mul(101).div(100)),
+        price_source: None,
+    }
+}
+
+#[ink::test]
+fn set_emode_category_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    assert_eq!(contract.emode_category(1), None);
+    let category = stablecoin_emode_category();
+    assert!(contract.set_emode_category(1, category.clone()).is_ok());
+    assert_eq!(contract.emode_category(1), Some(category));
+}
+
+#[ink::test]
+fn set_emode_category_fails_when_invalid() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    assert_eq!(
+        contract
+            .set_emode_category(0, stablecoin_emode_category())
+            .unwrap_err(),
+        Error::InvalidEModeCategory
+    );
+    let category = EModeCategory {
+        liquidation_threshold: 9600,
+        ..stablecoin_emode_category()
+    };
+    assert_eq!(
+        contract.set_emode_category(1, category).unwrap_err(),
+        Error::InvalidEModeCategory
+    );
+}
+
+#[ink::test]
+fn set_market_emode_category_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let pool = AccountId::from([0x01; 32]);
+    let underlying = AccountId::from([0x01; 32]);
+    assert!(contract.support_market(pool, underlying).is_ok());
+    assert!(contract
+        .set_emode_category(1, stablecoin_emode_category())
+        .is_ok());
+    assert_eq!(contract.market_emode_category(pool), 0);
+    assert!(contract.set_market_emode_category(pool, 1).is_ok());
+    assert_eq!(contract.market_emode_category(pool), 1);
+}
+
+#[ink::test]
+fn set_market_emode_category_fails() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let pool = AccountId::from([0x01; 32]);
+    let underlying = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract.set_market_emode_category(pool, 0).unwrap_err(),
+        Error::MarketNotListed
+    );
+    assert!(contract.support_market(pool, underlying).is_ok());
+    assert_eq!(
+        contract.set_market_emode_category(pool, 1).unwrap_err(),
+        Error::EModeCategoryNotFound
+    );
+}
+
+#[ink::test]
+fn set_user_emode_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    assert!(contract.set_price_oracle(accounts.django).is_ok());
+    assert!(contract
+        .set_emode_category(1, stablecoin_emode_category())
+        .is_ok());
+
+    set_caller(accounts.charlie);
+    assert_eq!(
+        contract.set_user_emode(2).unwrap_err(),
+        Error::EModeCategoryNotFound
+    );
+    assert!(contract.set_user_emode(1).is_ok());
+    assert_eq!(contract.user_emode(accounts.charlie), 1);
+    assert!(contract.set_user_emode(0).is_ok());
+    assert_eq!(contract.user_emode(accounts.charlie), 0);
+}
+
+#[ink::test]
+fn borrow_allowed_fail_when_emode_is_inconsistent() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    assert!(contract.set_price_oracle(accounts.django).is_ok());
+    assert!(contract
+        .set_emode_category(1, stablecoin_emode_category())
+        .is_ok());
+    assert!(contract.set_user_emode(1).is_ok());
+
+    let pool = AccountId::from([0x01; 32]);
+    let underlying = AccountId::from([0x01; 32]);
+    assert!(contract.support_market(pool, underlying).is_ok());
+    assert_eq!(
+        contract
+            .borrow_allowed(pool, accounts.bob, 0, None)
+            .unwrap_err(),
+        Error::InconsistentEModeCategory
+    );
+}
+
+#[ink::test]
+fn set_isolation_mode_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let pool = AccountId::from([0x01; 32]);
+    let underlying = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract.set_isolation_mode(pool, true, 1_000).unwrap_err(),
+        Error::MarketNotListed
+    );
+    assert!(contract.support_market(pool, underlying).is_ok());
+    assert!(!contract.isolated(pool));
+    assert_eq!(contract.debt_ceiling(pool), 0);
+    assert_eq!(
+        contract.set_isolation_mode(pool, true, 0).unwrap_err(),
+        Error::InvalidDebtCeiling
+    );
+
+    assert!(contract.set_isolation_mode(pool, true, 1_000).is_ok());
+    assert!(contract.isolated(pool));
+    assert_eq!(contract.debt_ceiling(pool), 1_000);
+}
+
+#[ink::test]
+fn set_borrowable_in_isolation_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let pool = AccountId::from([0x01; 32]);
+    let underlying = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract
+            .set_borrowable_in_isolation(pool, true)
+            .unwrap_err(),
+        Error::MarketNotListed
+    );
+    assert!(contract.support_market(pool, underlying).is_ok());
+    assert!(!contract.borrowable_in_isolation(pool));
+    assert!(contract.set_borrowable_in_isolation(pool, true).is_ok());
+    assert!(contract.borrowable_in_isolation(pool));
+}
+
+#[ink::test]
+fn deprecate_market_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let pool = AccountId::from([0x01; 32]);
+    let underlying = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract.deprecate_market(pool, 1000).unwrap_err(),
+        Error::MarketNotListed
+    );
+    assert!(contract.support_market(pool, underlying).is_ok());
+    assert_eq!(contract.market_deprecation(pool), None);
+
+    assert!(contract.deprecate_market(pool, 1000).is_ok());
+    assert_eq!(
+        contract.market_deprecation(pool),
+        Some(MarketDeprecation {
+            started_at: 0,
+            ramp_duration: 1000,
+        })
+    );
+    assert_eq!(contract.mint_guardian_paused(pool), Some(true));
+    assert_eq!(contract.borrow_guardian_paused(pool), Some(true));
+    assert_eq!(
+        contract.deprecate_market(pool, 1000).unwrap_err(),
+        Error::MarketAlreadyDeprecated
+    );
+    // the deprecated market stays frozen
+    assert_eq!(
+        contract.set_mint_guardian_paused(pool, false).unwrap_err(),
+        Error::MarketDeprecated
+    );
+    assert_eq!(
+        contract
+            .set_borrow_guardian_paused(pool, false)
+            .unwrap_err(),
+        Error::MarketDeprecated
+    );
+
+    let events = get_emitted_events();
+    if let Ok(Event::MarketDeprecated(event)) =
+        <Event as Decode>::decode(&mut &events[events.len() - 1].data[..])
+    {
+        assert_eq!(event.pool, pool);
+        assert_eq!(event.ramp_duration, 1000);
+    } else {
+        panic!("unexpected event kind: expected MarketDeprecated event")
+    }
+}
+
+#[ink::test]
+fn remove_market_fail_when_not_deprecated() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let pool = AccountId::from([0x01; 32]);
+    let underlying = AccountId::from([0x01; 32]);
+    assert!(contract.support_market(pool, underlying).is_ok());
+    assert_eq!(
+        contract.remove_market(pool).unwrap_err(),
+        Error::MarketNotDeprecated
+    );
+    assert_eq!(contract.markets(), [pool]);
+}
+
+#[ink::test]
+fn shutdown_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    assert_eq!(contract.shutdown().unwrap_err(), Error::OracleIsNotSet);
+    assert!(contract
+        .set_price_oracle(AccountId::from([0xff; 32]))
+        .is_ok());
+    assert!(contract.shutdown().is_ok());
+    assert!(contract.is_shutdown());
+    assert_eq!(contract.shutdown().unwrap_err(), Error::ProtocolIsShutdown);
+
+    let events = get_emitted_events();
+    assert!(matches!(
+        <Event as Decode>::decode(&mut &events[events.len() - 1].data[..]),
+        Ok(Event::Shutdown(_))
+    ));
+
+    let pool = AccountId::from([0x01; 32]);
+    let underlying = AccountId::from([0x01; 32]);
+    assert!(contract.support_market(pool, underlying).is_ok());
+    assert_eq!(
+        contract
+            .mint_allowed(pool, accounts.bob, 0, None)
+            .unwrap_err(),
+        Error::ProtocolIsShutdown
+    );
+    assert_eq!(
+        contract
+            .borrow_allowed(pool, accounts.bob, 0, None)
+            .unwrap_err(),
+        Error::ProtocolIsShutdown
+    );
+    assert_eq!(
+        contract
+            .liquidate_borrow_allowed(pool, pool, accounts.charlie, accounts.bob, 0, None)
+            .unwrap_err(),
+        Error::ProtocolIsShutdown
+    );
+    assert_eq!(
+        contract
+            .seize_allowed(pool, pool, accounts.charlie, accounts.bob, 0)
+            .unwrap_err(),
+        Error::ProtocolIsShutdown
+    );
+    assert_eq!(
+        contract.set_user_emode(0).unwrap_err(),
+        Error::ProtocolIsShutdown
+    );
+    assert!(contract
+        .repay_borrow_allowed(pool, accounts.bob, accounts.bob, 0)
+        .is_ok());
+}
+
+#[ink::test]
+fn set_settlement_price_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+    assert!(contract
+        .set_price_oracle(AccountId::from([0xff; 32]))
+        .is_ok());
+
+    let pool = AccountId::from([0x01; 32]);
+    let underlying = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract.set_settlement_price(pool, 100).unwrap_err(),
+        Error::ProtocolIsNotShutdown
+    );
+
+    // listed after the shutdown, as the oracle cannot be called off-chain
+    assert!(contract.shutdown().is_ok());
+    assert!(contract.support_market(pool, underlying).is_ok());
+    assert_eq!(contract.settlement_price(pool), None);
+    assert_eq!(
+        contract.set_settlement_price(pool, 0).unwrap_err(),
+        Error::PriceError
+    );
+    assert!(contract.set_settlement_price(pool, 100).is_ok());
+    assert_eq!(contract.settlement_price(pool), Some(100));
+}
+
+#[ink::test]
+fn use_reserve_as_collateral_allowed_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let pool1 = AccountId::from([0x01; 32]);
+    let pool2 = AccountId::from([0x02; 32]);
+    assert_eq!(
+        contract
+            .use_reserve_as_collateral_allowed(pool1, accounts.bob)
+            .unwrap_err(),
+        Error::MarketNotListed
+    );
+    assert!(contract.support_market(pool1, pool1).is_ok());
+    assert!(contract.support_market(pool2, pool2).is_ok());
+    // no snapshot of the other pools is needed as long as none of them is isolated
+    assert!(contract
+        .use_reserve_as_collateral_allowed(pool1, accounts.bob)
+        .is_ok());
+}
+
+#[ink::test]
+fn set_market_liquidation_incentive_mantissa_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let pool = AccountId::from([0x01; 32]);
+    let underlying = AccountId::from([0x01; 32]);
+    let incentive = WrappedU256::from(exp_scale().mul(110).div(100));
+    assert_eq!(
+        contract
+            .set_market_liquidation_incentive_mantissa(pool, Some(incentive))
+            .unwrap_err(),
+        Error::MarketNotListed
+    );
+    assert!(contract.support_market(pool, underlying).is_ok());
+    assert_eq!(contract.market_liquidation_incentive_mantissa(pool), None);
+
+    assert_eq!(
+        contract
+            .set_market_liquidation_incentive_mantissa(
+                pool,
+                Some(WrappedU256::from(exp_scale().sub(1)))
+            )
+            .unwrap_err(),
+        Error::InvalidLiquidationIncentive
+    );
+    assert!(contract
+        .set_market_liquidation_incentive_mantissa(pool, Some(incentive))
+        .is_ok());
+    assert_eq!(
+        contract.market_liquidation_incentive_mantissa(pool),
+        Some(incentive)
+    );
+    assert!(contract
+        .set_market_liquidation_incentive_mantissa(pool, None)
+        .is_ok());
+    assert_eq!(contract.market_liquidation_incentive_mantissa(pool), None);
+}
+
+#[ink::test]
+fn set_protocol_seize_share_mantissa_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    // 2.8% until the manager sets another one
+    assert_eq!(
+        contract.protocol_seize_share_mantissa(),
+        WrappedU256::from(exp_scale().mul(28).div(1000))
+    );
+    assert_eq!(
+        contract
+            .set_protocol_seize_share_mantissa(WrappedU256::from(exp_scale().add(1)))
+            .unwrap_err(),
+        Error::InvalidProtocolSeizeShare
+    );
+    let share = WrappedU256::from(exp_scale().mul(5).div(100));
+    assert!(contract.set_protocol_seize_share_mantissa(share).is_ok());
+    assert_eq!(contract.protocol_seize_share_mantissa(), share);
+}
+
+#[ink::test]
+fn set_market_protocol_seize_share_mantissa_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let pool = AccountId::from([0x01; 32]);
+    let underlying = AccountId::from([0x01; 32]);
+    let share = WrappedU256::from(exp_scale().mul(5).div(100));
+    assert_eq!(
+        contract
+            .set_market_protocol_seize_share_mantissa(pool, Some(share))
+            .unwrap_err(),
+        Error::MarketNotListed
+    );
+    assert!(contract.support_market(pool, underlying).is_ok());
+    assert_eq!(contract.market_protocol_seize_share_mantissa(pool), None);
+
+    assert_eq!(
+        contract
+            .set_market_protocol_seize_share_mantissa(
+                pool,
+                Some(WrappedU256::from(exp_scale().add(1)))
+            )
+            .unwrap_err(),
+        Error::InvalidProtocolSeizeShare
+    );
+    assert!(contract
+        .set_market_protocol_seize_share_mantissa(pool, Some(share))
+        .is_ok());
+    assert_eq!(
+        contract.market_protocol_seize_share_mantissa(pool),
+        Some(share)
+    );
+    assert!(contract
+        .set_market_protocol_seize_share_mantissa(pool, None)
+        .is_ok());
+    assert_eq!(contract.market_protocol_seize_share_mantissa(pool), None);
+}
+
+#[ink::test]
+fn set_critical_health_factor_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    assert_eq!(contract.critical_health_factor(), WrappedU256::from(0));
+    let critical_health_factor = WrappedU256::from(exp_scale().mul(95).div(100));
+    assert!(contract
+        .set_critical_health_factor(critical_health_factor)
+        .is_ok());
+    assert_eq!(contract.critical_health_factor(), critical_health_factor);
+}
+
+#[ink::test]
+fn set_critical_health_factor_fail_when_invalid_value() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    assert_eq!(
+        contract
+            .set_critical_health_factor(WrappedU256::from(exp_scale().add(1)))
+            .unwrap_err(),
+        Error::InvalidCriticalHealthFactor
+    );
+}
+
+#[ink::test]
+fn set_liquidation_auction_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    assert_eq!(contract.liquidation_auction_duration(), 0);
+    assert_eq!(
+        contract.liquidation_auction_start_incentive_mantissa(),
+        WrappedU256::from(0)
+    );
+    let start_incentive = WrappedU256::from(exp_scale().mul(102).div(100));
+    assert!(contract
+        .set_liquidation_auction(60 * 60 * 1000, start_incentive)
+        .is_ok());
+    assert_eq!(contract.liquidation_auction_duration(), 60 * 60 * 1000);
+    assert_eq!(
+        contract.liquidation_auction_start_incentive_mantissa(),
+        start_incentive
+    );
+
+    let events = get_emitted_events();
+    assert_eq!(events.len(), 1);
+    if let Ok(Event::LiquidationAuctionUpdated(event)) =
+        <Event as Decode>::decode(&mut &events[0].data[..])
+    {
+        assert_eq!(event.duration, 60 * 60 * 1000);
+        assert_eq!(event.start_incentive_mantissa, start_incentive);
+    } else {
+        panic!("unexpected event kind: expected LiquidationAuctionUpdated event")
+    }
+}
+
+#[ink::test]
+fn set_liquidation_auction_fail_when_invalid_start_incentive() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    assert_eq!(
+        contract
+            .set_liquidation_auction(1000, WrappedU256::from(exp_scale().sub(1)))
+            .unwrap_err(),
+        Error::InvalidLiquidationIncentive
+    );
+    // the start incentive is not used while the auction is disabled
+    assert!(contract
+        .set_liquidation_auction(0, WrappedU256::from(0))
+        .is_ok());
+}
+
+#[ink::test]
+fn update_liquidation_auction_fails_by_not_pool() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let pool_attributes = PoolAttributes {
+        pool: Some(accounts.charlie),
+        ..Default::default()
+    };
+    assert_eq!(
+        contract
+            .update_liquidation_auction(accounts.django, Some(pool_attributes))
+            .unwrap_err(),
+        Error::CallerIsNotPool
+    );
+    assert_eq!(contract.liquidation_auction_start(accounts.django), None);
+}
+
+#[ink::test]
+fn liquidate_borrow_allowed_fails_by_not_pool() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let pool = AccountId::from([0x01; 32]);
+    let underlying = AccountId::from([0x01; 32]);
+    assert!(contract.support_market(pool, underlying).is_ok());
+    let pool_attributes = PoolAttributes {
+        pool: Some(pool),
+        ..Default::default()
+    };
+    assert_eq!(
+        contract
+            .liquidate_borrow_allowed(
+                pool,
+                pool,
+                accounts.charlie,
+                accounts.django,
+                0,
+                Some(pool_attributes)
+            )
+            .unwrap_err(),
+        Error::CallerIsNotPool
+    );
+    assert_eq!(contract.liquidation_auction_start(accounts.django), None);
+}
+
+#[ink::test]
+fn update_account_market_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let pool1 = AccountId::from([0x01; 32]);
+    let pool2 = AccountId::from([0x02; 32]);
+    assert!(contract.support_market(pool1, pool1).is_ok());
+    assert!(contract.support_market(pool2, pool2).is_ok());
+    assert_eq!(
+        contract
+            .update_account_market(accounts.charlie, true)
+            .unwrap_err(),
+        Error::CallerIsNotPool
+    );
+
+    set_caller(pool1);
+    assert!(contract
+        .update_account_market(accounts.charlie, true)
+        .is_ok());
+    set_caller(pool2);
+    assert!(contract
+        .update_account_market(accounts.charlie, true)
+        .is_ok());
+    assert!(contract
+        .update_account_market(accounts.charlie, true)
+        .is_ok());
+    assert_eq!(contract.account_markets(accounts.charlie), [pool1, pool2]);
+    assert_eq!(contract.account_markets(accounts.django), []);
+
+    set_caller(pool1);
+    assert!(contract
+        .update_account_market(accounts.charlie, false)
+        .is_ok());
+    assert_eq!(contract.account_markets(accounts.charlie), [pool2]);
+}
+
+#[ink::test]
+fn isolated_debt_is_shared_by_accounts() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    let collateral = AccountId::from([0x01; 32]);
+    let borrowed = AccountId::from([0x02; 32]);
+    assert!(contract.support_market(collateral, collateral).is_ok());
+    assert!(contract.support_market(borrowed, borrowed).is_ok());
+    assert!(contract.set_isolation_mode(collateral, true, 1_000).is_ok());
+    assert!(contract.set_borrowable_in_isolation(borrowed, true).is_ok());
+    let to_exp = |mantissa: u128| {
+        Exp {
+            mantissa: WrappedU256::from(U256::from(mantissa)),
+        }
+    };
+    let asset_params = [
+        HypotheticalAccountLiquidityCalculationParam {
+            asset: collateral,
+            decimals: 18,
+            token_balance: 10_000,
+            borrow_balance: 0,
+            exchange_rate_mantissa: to_exp(exp_scale().as_u128()),
+            collateral_factor_mantissa: to_exp(exp_scale().as_u128() / 2),
+            oracle_price_mantissa: to_exp(exp_scale().as_u128()),
+        },
+        HypotheticalAccountLiquidityCalculationParam {
+            asset: borrowed,
+            decimals: 18,
+            token_balance: 0,
+            borrow_balance: 0,
+            exchange_rate_mantissa: to_exp(exp_scale().as_u128()),
+            collateral_factor_mantissa: to_exp(exp_scale().as_u128() / 2),
+            oracle_price_mantissa: to_exp(exp_scale().as_u128()),
+        },
+    ];
+
+    // the borrow of charlie counts towards the ceiling of django
+    record_isolated_debt(&mut contract, accounts.charlie, borrowed, collateral, 600);
+    assert_eq!(contract.isolated_debt(collateral), 600);
+    assert!(contract
+        ._isolation_mode_borrow_allowed(borrowed, 400, &asset_params)
+        .is_ok());
+    assert_eq!(
+        contract
+            ._isolation_mode_borrow_allowed(borrowed, 500, &asset_params)
+            .unwrap_err(),
+        Error::DebtCeilingReached
+    );
+
+    assert_eq!(
+        contract
+            .update_isolated_debt(accounts.charlie, 600, 300)
+            .unwrap_err(),
+        Error::CallerIsNotPool
+    );
+    set_caller(borrowed);
+    // the debt is released in proportion to the repaid borrow
+    assert!(contract
+        .update_isolated_debt(accounts.charlie, 600, 300)
+        .is_ok());
+    assert_eq!(contract.isolated_debt(collateral), 300);
+    assert!(contract
+        ._isolation_mode_borrow_allowed(borrowed, 500, &asset_params)
+        .is_ok());
+    assert!(contract
+        .update_isolated_debt(accounts.charlie, 300, 0)
+        .is_ok());
+    assert_eq!(contract.isolated_debt(collateral), 0);
+
+    // the debt of an account without isolated collateral is not recorded
+    assert!(contract
+        .update_isolated_debt(accounts.django, 0, 1_000)
+        .is_ok());
+    assert_eq!(contract.isolated_debt(collateral), 0);
+}
+
+#[ink::test]
+fn set_liquidation_dust_threshold_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let mut contract = ControllerContract::new(accounts.bob);
+
+    assert_eq!(contract.liquidation_dust_threshold(), 0);
+    assert!(contract.set_liquidation_dust_threshold(1_000).is_ok());
+    assert_eq!(contract.liquidation_dust_threshold(), 1_000);
+}
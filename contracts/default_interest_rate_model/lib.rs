@@ -48,5 +48,25 @@ pub mod contract {
                 ),
             }
         }
+
+        /// Generate this contract with a premium over the variable rate for stable borrows
+        #[ink(constructor)]
+        pub fn new_with_stable_rate_premium(
+            base_rate_per_year: WrappedU256,
+            multiplier_per_year_slope_1: WrappedU256,
+            multiplier_per_year_slope_2: WrappedU256,
+            kink: WrappedU256,
+            stable_rate_premium_per_year: WrappedU256,
+        ) -> Self {
+            Self {
+                model: Data::new_with_stable_rate_premium(
+                    base_rate_per_year,
+                    multiplier_per_year_slope_1,
+                    multiplier_per_year_slope_2,
+                    kink,
+                    stable_rate_premium_per_year,
+                ),
+            }
+        }
     }
 }
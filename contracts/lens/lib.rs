@@ -58,8 +58,11 @@ pub mod contract {
         collateral_factor_mantissa: WrappedU256,
         reserve_factor_mantissa: WrappedU256,
         borrow_cap: Option<u128>,
+        supply_cap: Option<u128>,
         mint_guardian_paused: bool,
         borrow_guardian_paused: bool,
+        market_frozen: bool,
+        paused_actions: u8,
     }
 
     /// Pool's Balance Information
@@ -98,10 +101,30 @@ pub mod contract {
         oracle: Option<AccountId>,
         seize_guardian_paused: bool,
         transfer_guardian_paused: bool,
+        global_paused_actions: u8,
+        shutdown: bool,
         liquidation_incentive_mantissa: WrappedU256,
         close_factor_mantissa: WrappedU256,
     }
 
+    /// Account with debt and its health factor
+    #[derive(Decode, Encode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct BorrowerHealth {
+        account: AccountId,
+        health_factor: WrappedU256,
+    }
+
+    /// Page of the accounts to liquidate in a pool
+    #[derive(Decode, Encode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct LiquidatableAccounts {
+        accounts: Vec<BorrowerHealth>,
+        /// Cursor of the next page, None if all the borrowers are scanned.
+        /// Only stable within the block of the query, see `Pool::borrowers`.
+        next_cursor: Option<u32>,
+    }
+
     #[ink(storage)]
     #[derive(Default, Storage)]
     pub struct LensContract {}
@@ -187,6 +210,21 @@ pub mod contract {
                 .collect()
         }
 
+        /// Get the borrowers of the pool whose health factor is below `health_factor`,
+        /// scanning `limit` borrowers from `cursor`.
+        /// A cursor is only stable within a block: the borrowers fully repaying their debt
+        /// reorder the list, so a scan over several blocks can skip or repeat accounts.
+        #[ink(message)]
+        pub fn liquidatable_accounts(
+            &self,
+            pool: AccountId,
+            health_factor: WrappedU256,
+            cursor: u32,
+            limit: u32,
+        ) -> LiquidatableAccounts {
+            self._liquidatable_accounts(pool, health_factor, cursor, limit)
+        }
+
         /// Get protocol's configuration
         #[ink(message)]
         pub fn configuration(&self, controller: AccountId) -> Configuration {
@@ -195,6 +233,8 @@ pub mod contract {
                 oracle: ControllerRef::oracle(&controller),
                 seize_guardian_paused: ControllerRef::seize_guardian_paused(&controller),
                 transfer_guardian_paused: ControllerRef::transfer_guardian_paused(&controller),
+                global_paused_actions: ControllerRef::global_paused_actions(&controller),
+                shutdown: ControllerRef::is_shutdown(&controller),
                 liquidation_incentive_mantissa: ControllerRef::liquidation_incentive_mantissa(
                     &controller,
                 ),
@@ -224,19 +264,34 @@ pub mod contract {
                 is_listed,
                 collateral_factor_mantissa,
                 borrow_cap,
+                supply_cap,
                 mint_guardian_paused,
                 borrow_guardian_paused,
+                market_frozen,
+                paused_actions,
             ) = if let Some(_controller) = controller {
                 (
                     ControllerRef::is_listed(&_controller, pool),
                     ControllerRef::collateral_factor_mantissa(&_controller, pool)
                         .unwrap_or_default(),
                     ControllerRef::borrow_cap(&_controller, pool),
+                    ControllerRef::supply_cap(&_controller, pool),
                     ControllerRef::mint_guardian_paused(&_controller, pool).unwrap_or_default(),
                     ControllerRef::borrow_guardian_paused(&_controller, pool).unwrap_or_default(),
+                    ControllerRef::market_frozen(&_controller, pool),
+                    ControllerRef::paused_actions(&_controller, pool),
                 )
             } else {
-                (false, Default::default(), Some(0), true, true)
+                (
+                    false,
+                    Default::default(),
+                    Some(0),
+                    Some(0),
+                    true,
+                    true,
+                    false,
+                    u8::MAX,
+                )
             };
 
             PoolMetadata {
@@ -248,16 +303,19 @@ pub mod contract {
                 is_listed,
                 total_cash: PoolRef::get_cash_prior(&pool),
                 total_supply: PSP22Ref::total_supply(&pool),
-                total_borrows: PoolRef::total_borrows(&pool),
-                total_reserves: PoolRef::total_reserves(&pool),
-                exchange_rate_current: PoolRef::exchange_rate_current(&pool).unwrap_or_default(),
+                total_borrows: PoolRef::total_borrows_at(&pool, None).unwrap_or_default(),
+                total_reserves: PoolRef::total_reserves_at(&pool, None).unwrap_or_default(),
+                exchange_rate_current: PoolRef::exchange_rate_at(&pool, None).unwrap_or_default(),
                 supply_rate_per_msec: PoolRef::supply_rate_per_msec(&pool),
                 borrow_rate_per_msec: PoolRef::borrow_rate_per_msec(&pool),
                 collateral_factor_mantissa,
                 reserve_factor_mantissa: PoolRef::reserve_factor_mantissa(&pool),
                 borrow_cap,
+                supply_cap,
                 mint_guardian_paused,
                 borrow_guardian_paused,
+                market_frozen,
+                paused_actions,
             }
         }
 
@@ -274,7 +332,7 @@ pub mod contract {
             PoolBalances {
                 pool,
                 balance_of: PSP22Ref::balance_of(&pool, account),
-                borrow_balance_current: PoolRef::borrow_balance_current(&pool, account)
+                borrow_balance_current: PoolRef::borrow_balance_at(&pool, account, None)
                     .unwrap_or_default(),
                 balance_of_underlying: PoolRef::balance_of_underlying(&pool, account),
                 token_balance,
@@ -293,6 +351,14 @@ pub mod contract {
                 }
             }
 
+            // the prices are frozen once the protocol is shut down
+            if let Some(price) = ControllerRef::settlement_price(&controller.unwrap(), pool) {
+                return PoolUnderlyingPrice {
+                    pool,
+                    underlying_price: price,
+                }
+            }
+
             let oracle = ControllerRef::oracle(&controller.unwrap());
             if oracle.is_none() {
                 return PoolUnderlyingPrice {
@@ -308,6 +374,47 @@ pub mod contract {
             }
         }
 
+        fn _liquidatable_accounts(
+            &self,
+            pool: AccountId,
+            health_factor: WrappedU256,
+            cursor: u32,
+            limit: u32,
+        ) -> LiquidatableAccounts {
+            let controller = match PoolRef::controller(&pool) {
+                Some(controller) => controller,
+                None => {
+                    return LiquidatableAccounts {
+                        accounts: Vec::new(),
+                        next_cursor: None,
+                    }
+                }
+            };
+
+            let borrowers = PoolRef::borrowers(&pool, cursor, limit);
+            let next_cursor = cursor.saturating_add(borrowers.len() as u32);
+            let accounts = borrowers
+                .into_iter()
+                .filter_map(|account| {
+                    let account_data =
+                        ControllerRef::calculate_user_account_data(&controller, account, None)
+                            .ok()?;
+                    if account_data.health_factor >= health_factor.into() {
+                        return None
+                    }
+                    Some(BorrowerHealth {
+                        account,
+                        health_factor: account_data.health_factor.into(),
+                    })
+                })
+                .collect();
+
+            LiquidatableAccounts {
+                accounts,
+                next_cursor: (next_cursor < PoolRef::borrowers_count(&pool)).then_some(next_cursor),
+            }
+        }
+
         fn _underlying_balance(&self, pool: &AccountId, account: AccountId) -> Balance {
             let underlying = PoolRef::underlying(pool);
             if let Some(_underlying) = underlying {
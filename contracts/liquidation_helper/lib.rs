@@ -0,0 +1,40 @@
+// Copyright 2023 Asynmatrix Pte. Ltd.
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+#![feature(min_specialization)]
+
+#[cfg(test)]
+mod tests;
+
+/// Definition of Liquidation Helper Contract
+#[openbrush::contract]
+pub mod contract {
+    use logics::impls::liquidation_helper::*;
+    use openbrush::traits::Storage;
+
+    /// Contract's Storage
+    #[ink(storage)]
+    #[derive(Storage)]
+    pub struct LiquidationHelperContract {
+        #[storage_field]
+        helper: Data,
+    }
+
+    impl LiquidationHelper for LiquidationHelperContract {}
+    impl Internal for LiquidationHelperContract {}
+
+    impl LiquidationHelperContract {
+        /// Generate this contract
+        #[ink(constructor)]
+        pub fn new(controller: AccountId) -> Self {
+            Self {
+                helper: Data { controller },
+            }
+        }
+    }
+}
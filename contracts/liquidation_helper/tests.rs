@@ -0,0 +1,52 @@
+use crate::contract::*;
+use ink::env::{
+    test::{
+        self,
+        DefaultAccounts,
+    },
+    DefaultEnvironment,
+};
+use logics::impls::liquidation_helper::*;
+use openbrush::traits::AccountId;
+
+fn default_accounts() -> DefaultAccounts<DefaultEnvironment> {
+    test::default_accounts::<DefaultEnvironment>()
+}
+fn set_caller(id: AccountId) {
+    test::set_caller::<DefaultEnvironment>(id);
+}
+
+#[ink::test]
+fn new_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let controller = AccountId::from([0x01; 32]);
+    let contract = LiquidationHelperContract::new(controller);
+    assert_eq!(contract.controller(), controller);
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn liquidate_borrows_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = LiquidationHelperContract::new(controller);
+    let pool = AccountId::from([0x02; 32]);
+    contract
+        .liquidate_borrows(
+            pool,
+            vec![LiquidationParams {
+                borrower: accounts.charlie,
+                repay_amount: 100,
+                collateral: pool,
+            }],
+            false,
+            true,
+        )
+        .unwrap();
+}
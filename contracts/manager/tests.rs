@@ -11,10 +11,15 @@ use logics::{
         Manager,
         BORROW_CAP_GUARDIAN,
         CONTROLLER_ADMIN,
+        EMERGENCY_GUARDIAN,
         PAUSE_GUARDIAN,
         TOKEN_ADMIN,
     },
     traits::{
+        controller::{
+            EModeCategory,
+            ACTION_REDEEM,
+        },
         manager::Error,
         types::WrappedU256,
     },
@@ -308,6 +313,104 @@ fn set_borrow_guardian_paused_fails_by_no_authority() {
     );
 }
 
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_actions_paused_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x01; 32]);
+    contract
+        .set_actions_paused(pool, ACTION_REDEEM, true)
+        .unwrap();
+}
+#[ink::test]
+fn set_actions_paused_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    let pool = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract
+            .set_actions_paused(pool, ACTION_REDEEM, true)
+            .unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_market_frozen_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x01; 32]);
+    contract.set_market_frozen(pool, true).unwrap();
+}
+#[ink::test]
+fn set_market_frozen_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    let pool = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract.set_market_frozen(pool, true).unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn shutdown_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract
+        .grant_role(EMERGENCY_GUARDIAN, accounts.bob)
+        .is_ok());
+    contract.shutdown().unwrap();
+}
+#[ink::test]
+fn shutdown_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    assert_eq!(
+        contract.shutdown().unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+
 #[ink::test]
 #[should_panic(
     expected = "not implemented: off-chain environment does not support contract invocation"
@@ -319,11 +422,271 @@ fn set_close_factor_mantissa_works() {
     let mut contract = ManagerContract::new(controller);
     assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
     contract
-        .set_close_factor_mantissa(WrappedU256::from(0))
+        .set_close_factor_mantissa(WrappedU256::from(0))
+        .unwrap();
+}
+#[ink::test]
+fn set_close_factor_mantissa_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    assert_eq!(
+        contract
+            .set_close_factor_mantissa(WrappedU256::from(0))
+            .unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_liquidation_incentive_mantissa_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    contract
+        .set_liquidation_incentive_mantissa(WrappedU256::from(0))
+        .unwrap();
+}
+#[ink::test]
+fn set_liquidation_incentive_mantissa_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    assert_eq!(
+        contract
+            .set_liquidation_incentive_mantissa(WrappedU256::from(0))
+            .unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_market_liquidation_incentive_mantissa_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    contract
+        .set_market_liquidation_incentive_mantissa(AccountId::from([0x02; 32]), None)
+        .unwrap();
+}
+#[ink::test]
+fn set_market_liquidation_incentive_mantissa_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    assert_eq!(
+        contract
+            .set_market_liquidation_incentive_mantissa(AccountId::from([0x02; 32]), None)
+            .unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_protocol_seize_share_mantissa_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    contract
+        .set_protocol_seize_share_mantissa(WrappedU256::from(0))
+        .unwrap();
+}
+#[ink::test]
+fn set_protocol_seize_share_mantissa_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    assert_eq!(
+        contract
+            .set_protocol_seize_share_mantissa(WrappedU256::from(0))
+            .unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_market_protocol_seize_share_mantissa_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    contract
+        .set_market_protocol_seize_share_mantissa(AccountId::from([0x02; 32]), None)
+        .unwrap();
+}
+#[ink::test]
+fn set_market_protocol_seize_share_mantissa_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    assert_eq!(
+        contract
+            .set_market_protocol_seize_share_mantissa(AccountId::from([0x02; 32]), None)
+            .unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn deprecate_market_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x02; 32]);
+    contract.deprecate_market(pool, 0).unwrap();
+}
+#[ink::test]
+fn deprecate_market_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x02; 32]);
+    assert_eq!(
+        contract.deprecate_market(pool, 0).unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn remove_market_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x02; 32]);
+    contract.remove_market(pool).unwrap();
+}
+#[ink::test]
+fn remove_market_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x02; 32]);
+    assert_eq!(
+        contract.remove_market(pool).unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_liquidation_auction_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    contract
+        .set_liquidation_auction(0, WrappedU256::from(0))
+        .unwrap();
+}
+#[ink::test]
+fn set_liquidation_auction_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    assert_eq!(
+        contract
+            .set_liquidation_auction(0, WrappedU256::from(0))
+            .unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_critical_health_factor_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    contract
+        .set_critical_health_factor(WrappedU256::from(0))
         .unwrap();
 }
 #[ink::test]
-fn set_close_factor_mantissa_fails_by_no_authority() {
+fn set_critical_health_factor_fails_by_no_authority() {
     let accounts = default_accounts();
     set_caller(accounts.bob);
     let controller = AccountId::from([0x01; 32]);
@@ -335,7 +698,7 @@ fn set_close_factor_mantissa_fails_by_no_authority() {
     assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
     assert_eq!(
         contract
-            .set_close_factor_mantissa(WrappedU256::from(0))
+            .set_critical_health_factor(WrappedU256::from(0))
             .unwrap_err(),
         Error::AccessControl(AccessControlError::MissingRole)
     );
@@ -345,18 +708,47 @@ fn set_close_factor_mantissa_fails_by_no_authority() {
 #[should_panic(
     expected = "not implemented: off-chain environment does not support contract invocation"
 )]
-fn set_liquidation_incentive_mantissa_works() {
+fn set_liquidation_dust_threshold_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    contract.set_liquidation_dust_threshold(0).unwrap();
+}
+#[ink::test]
+fn set_liquidation_dust_threshold_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    assert_eq!(
+        contract.set_liquidation_dust_threshold(0).unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_emode_category_works() {
     let accounts = default_accounts();
     set_caller(accounts.bob);
     let controller = AccountId::from([0x01; 32]);
     let mut contract = ManagerContract::new(controller);
     assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
     contract
-        .set_liquidation_incentive_mantissa(WrappedU256::from(0))
+        .set_emode_category(1, EModeCategory::default())
         .unwrap();
 }
 #[ink::test]
-fn set_liquidation_incentive_mantissa_fails_by_no_authority() {
+fn set_emode_category_fails_by_no_authority() {
     let accounts = default_accounts();
     set_caller(accounts.bob);
     let controller = AccountId::from([0x01; 32]);
@@ -368,7 +760,102 @@ fn set_liquidation_incentive_mantissa_fails_by_no_authority() {
     assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
     assert_eq!(
         contract
-            .set_liquidation_incentive_mantissa(WrappedU256::from(0))
+            .set_emode_category(1, EModeCategory::default())
+            .unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_market_emode_category_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x01; 32]);
+    contract.set_market_emode_category(pool, 1).unwrap();
+}
+#[ink::test]
+fn set_market_emode_category_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract.set_market_emode_category(pool, 1).unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_isolation_mode_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x01; 32]);
+    contract.set_isolation_mode(pool, true, 1_000).unwrap();
+}
+#[ink::test]
+fn set_isolation_mode_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract.set_isolation_mode(pool, true, 1_000).unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_borrowable_in_isolation_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x01; 32]);
+    contract.set_borrowable_in_isolation(pool, true).unwrap();
+}
+#[ink::test]
+fn set_borrowable_in_isolation_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract
+            .set_borrowable_in_isolation(pool, true)
             .unwrap_err(),
         Error::AccessControl(AccessControlError::MissingRole)
     );
@@ -405,6 +892,37 @@ fn set_borrow_cap_fails_by_no_authority() {
     );
 }
 
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_supply_cap_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    let pool = AccountId::from([0x01; 32]);
+    contract.set_supply_cap(pool, 0).unwrap();
+}
+#[ink::test]
+fn set_supply_cap_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract.set_supply_cap(pool, 0).unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+
 #[ink::test]
 #[should_panic(
     expected = "not implemented: off-chain environment does not support contract invocation"
@@ -421,6 +939,92 @@ fn set_reserve_factor_mantissa_works() {
         .unwrap();
 }
 #[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_minimum_initial_deposit_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x01; 32]);
+    contract.set_minimum_initial_deposit(pool, 1_000).unwrap();
+}
+#[ink::test]
+fn set_minimum_initial_deposit_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    let pool = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract
+            .set_minimum_initial_deposit(pool, 1_000)
+            .unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_socialize_bad_debt_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x01; 32]);
+    contract.set_socialize_bad_debt(pool, true).unwrap();
+}
+#[ink::test]
+fn set_socialize_bad_debt_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(CONTROLLER_ADMIN, accounts.bob).is_ok());
+    assert!(contract
+        .grant_role(BORROW_CAP_GUARDIAN, accounts.bob)
+        .is_ok());
+    assert!(contract.grant_role(PAUSE_GUARDIAN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract.set_socialize_bad_debt(pool, true).unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_max_stable_borrow_size_mantissa_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x01; 32]);
+    contract
+        .set_max_stable_borrow_size_mantissa(pool, WrappedU256::from(0))
+        .unwrap();
+}
+#[ink::test]
+fn set_max_stable_borrow_size_mantissa_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    let pool = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract
+            .set_max_stable_borrow_size_mantissa(pool, WrappedU256::from(0))
+            .unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+#[ink::test]
 fn set_reserve_factor_mantissa_fails_by_no_authority() {
     let accounts = default_accounts();
     set_caller(accounts.bob);
@@ -486,6 +1090,31 @@ fn sweep_token_works() {
     contract.sweep_token(token1, token2).unwrap();
 }
 #[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn skim_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    assert!(contract.grant_role(TOKEN_ADMIN, accounts.bob).is_ok());
+    let pool = AccountId::from([0x02; 32]);
+    contract.skim(pool).unwrap();
+}
+#[ink::test]
+fn skim_fails_by_no_authority() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let controller = AccountId::from([0x01; 32]);
+    let mut contract = ManagerContract::new(controller);
+    let pool = AccountId::from([0x02; 32]);
+    assert_eq!(
+        contract.skim(pool).unwrap_err(),
+        Error::AccessControl(AccessControlError::MissingRole)
+    );
+}
+#[ink::test]
 fn sweep_token_fails_by_no_authority() {
     let accounts = default_accounts();
     set_caller(accounts.bob);
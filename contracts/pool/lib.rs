@@ -22,9 +22,12 @@ pub mod contract {
         prelude::vec::Vec,
     };
     use logics::{
-        impls::pool::{
-            Internal,
-            *,
+        impls::{
+            pool::{
+                Internal,
+                *,
+            },
+            vault::Vault,
         },
         traits::types::WrappedU256,
     };
@@ -161,6 +164,22 @@ pub mod contract {
         pub new: AccountId,
     }
 
+    /// Event: User has swapped the rate mode of their borrow
+    #[ink(event)]
+    pub struct SwapBorrowRateMode {
+        #[ink(topic)]
+        pub borrower: AccountId,
+        pub rate_mode: BorrowRateMode,
+    }
+
+    /// Event: Stable rate of the user has been reset to the current stable rate
+    #[ink(event)]
+    pub struct RebalanceStableBorrowRate {
+        #[ink(topic)]
+        pub borrower: AccountId,
+        pub stable_rate: WrappedU256,
+    }
+
     #[ink(event)]
     pub struct AccrueInterest {
         pub interest_accumulated: Balance,
@@ -196,6 +215,55 @@ pub mod contract {
         pub new: WrappedU256,
     }
 
+    #[ink(event)]
+    pub struct NewMinimumInitialDeposit {
+        pub old: Balance,
+        pub new: Balance,
+    }
+
+    #[ink(event)]
+    pub struct NewMaxStableBorrowSize {
+        pub old: WrappedU256,
+        pub new: WrappedU256,
+    }
+
+    #[ink(event)]
+    pub struct BadDebtDetected {
+        #[ink(topic)]
+        pub borrower: AccountId,
+        pub amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct BadDebtCoveredByReserves {
+        #[ink(topic)]
+        pub borrower: AccountId,
+        pub amount: Balance,
+        pub total_reserves_new: Balance,
+    }
+
+    #[ink(event)]
+    pub struct BadDebtSocialized {
+        #[ink(topic)]
+        pub borrower: AccountId,
+        pub amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct DeficitRecorded {
+        #[ink(topic)]
+        pub borrower: AccountId,
+        pub amount: Balance,
+        pub deficit: Balance,
+    }
+
+    #[ink(event)]
+    pub struct DeficitCovered {
+        pub benefactor: AccountId,
+        pub amount: Balance,
+        pub deficit: Balance,
+    }
+
     impl Pool for PoolContract {}
     impl Internal for PoolContract {
         fn _emit_mint_event(&self, minter: AccountId, mint_amount: Balance, mint_tokens: Balance) {
@@ -297,6 +365,28 @@ pub mod contract {
             self.env().emit_event(ManagerAddressUpdated { old, new })
         }
 
+        fn _emit_swap_borrow_rate_mode_event(
+            &self,
+            borrower: AccountId,
+            rate_mode: BorrowRateMode,
+        ) {
+            self.env().emit_event(SwapBorrowRateMode {
+                borrower,
+                rate_mode,
+            })
+        }
+
+        fn _emit_rebalance_stable_borrow_rate_event(
+            &self,
+            borrower: AccountId,
+            stable_rate: WrappedU256,
+        ) {
+            self.env().emit_event(RebalanceStableBorrowRate {
+                borrower,
+                stable_rate,
+            })
+        }
+
         fn _emit_accrue_interest_event(
             &self,
             interest_accumulated: Balance,
@@ -336,6 +426,62 @@ pub mod contract {
         fn _emit_new_reserve_factor_event(&self, old: WrappedU256, new: WrappedU256) {
             self.env().emit_event(NewReserveFactor { old, new })
         }
+
+        fn _emit_new_minimum_initial_deposit_event(&self, old: Balance, new: Balance) {
+            self.env().emit_event(NewMinimumInitialDeposit { old, new })
+        }
+
+        fn _emit_new_max_stable_borrow_size_event(&self, old: WrappedU256, new: WrappedU256) {
+            self.env().emit_event(NewMaxStableBorrowSize { old, new })
+        }
+
+        fn _emit_bad_debt_detected_event(&self, borrower: AccountId, amount: Balance) {
+            self.env().emit_event(BadDebtDetected { borrower, amount })
+        }
+
+        fn _emit_bad_debt_covered_by_reserves_event(
+            &self,
+            borrower: AccountId,
+            amount: Balance,
+            total_reserves_new: Balance,
+        ) {
+            self.env().emit_event(BadDebtCoveredByReserves {
+                borrower,
+                amount,
+                total_reserves_new,
+            })
+        }
+
+        fn _emit_bad_debt_socialized_event(&self, borrower: AccountId, amount: Balance) {
+            self.env()
+                .emit_event(BadDebtSocialized { borrower, amount })
+        }
+
+        fn _emit_deficit_recorded_event(
+            &self,
+            borrower: AccountId,
+            amount: Balance,
+            deficit: Balance,
+        ) {
+            self.env().emit_event(DeficitRecorded {
+                borrower,
+                amount,
+                deficit,
+            })
+        }
+
+        fn _emit_deficit_covered_event(
+            &self,
+            benefactor: AccountId,
+            amount: Balance,
+            deficit: Balance,
+        ) {
+            self.env().emit_event(DeficitCovered {
+                benefactor,
+                amount,
+                deficit,
+            })
+        }
     }
 
     impl psp22::PSP22 for PoolContract {
@@ -382,6 +528,8 @@ pub mod contract {
 
     impl metadata::PSP22Metadata for PoolContract {}
 
+    impl Vault for PoolContract {}
+
     #[allow(clippy::too_many_arguments)]
     impl PoolContract {
         /// Generate this contract
@@ -3,7 +3,9 @@ use ink::{
     env::{
         test::{
             self,
+            recorded_events,
             DefaultAccounts,
+            EmittedEvent,
         },
         DefaultEnvironment,
     },
@@ -17,21 +19,70 @@ use logics::{
     traits::types::WrappedU256,
 };
 use openbrush::{
-    contracts::psp22::PSP22,
-    traits::AccountId,
+    contracts::psp22::{
+        self,
+        PSP22,
+    },
+    traits::{
+        AccountId,
+        Balance,
+        Storage,
+    },
 };
 use primitive_types::U256;
+use scale::Decode;
 use std::ops::{
     Add,
     Div,
+    Mul,
 };
 
+type Event = <PoolContract as ink::reflect::ContractEventBase>::Type;
+
 fn default_accounts() -> DefaultAccounts<DefaultEnvironment> {
     test::default_accounts::<DefaultEnvironment>()
 }
 fn set_caller(id: AccountId) {
     test::set_caller::<DefaultEnvironment>(id);
 }
+fn get_emitted_events() -> Vec<EmittedEvent> {
+    recorded_events().collect::<Vec<_>>()
+}
+fn decode_event(event: &EmittedEvent) -> Event {
+    <Event as Decode>::decode(&mut &event.data[..]).unwrap()
+}
+
+/// 1,000 shares backed by 1,000 cash and 500 borrows, minus the reserves
+fn setup_bad_debt<T: Storage<Data> + Storage<psp22::Data>>(
+    contract: &mut T,
+    total_reserves: Balance,
+) {
+    contract.data::<psp22::Data>().supply = 1_000;
+    contract.data::<Data>().total_cash = 1_000;
+    contract.data::<Data>().total_borrows = 500;
+    contract.data::<Data>().total_reserves = total_reserves;
+}
+fn new_pool_with_bad_debt(total_reserves: Balance, socialize_bad_debt: bool) -> PoolContract {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let dummy_id = AccountId::from([0x01; 32]);
+    let mut contract = PoolContract::new(
+        Some(dummy_id),
+        dummy_id,
+        dummy_id,
+        dummy_id,
+        accounts.bob,
+        WrappedU256::from(exp_scale()),
+        10000,
+        String::from("Token Name"),
+        String::from("symbol"),
+        8,
+    );
+    assert!(contract.set_socialize_bad_debt(socialize_bad_debt).is_ok());
+    setup_bad_debt(&mut contract, total_reserves);
+    contract._add_borrower(accounts.charlie);
+    contract
+}
 
 #[ink::test]
 fn new_works() {
@@ -240,6 +291,10 @@ fn assert_manager_works() {
     let admin_funcs: Vec<Result<()>> = vec![
         contract.reduce_reserves(100),
         contract.sweep_token(dummy_id),
+        contract.skim(),
+        contract.set_minimum_initial_deposit(100),
+        contract.set_socialize_bad_debt(true),
+        contract.set_max_stable_borrow_size_mantissa(WrappedU256::from(0)),
         contract.set_reserve_factor_mantissa(WrappedU256::from(0)),
     ];
     for func in admin_funcs {
@@ -349,3 +404,285 @@ fn accept_manager_works() {
     assert_eq!(contract.pending_manager(), None);
     assert_eq!(contract.manager().unwrap(), accounts.alice);
 }
+
+#[ink::test]
+fn projected_views_fail_before_last_accrual() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    test::set_block_timestamp::<DefaultEnvironment>(100);
+    let dummy_id = AccountId::from([0x01; 32]);
+    let contract = PoolContract::new(
+        Some(dummy_id),
+        dummy_id,
+        dummy_id,
+        dummy_id,
+        accounts.bob,
+        WrappedU256::from(exp_scale()),
+        10000,
+        String::from("Token Name"),
+        String::from("symbol"),
+        8,
+    );
+    assert_eq!(contract.get_accrual_block_timestamp(), 100);
+
+    let past = Some(99);
+    assert_eq!(
+        contract.exchange_rate_at(past).unwrap_err(),
+        Error::InvalidParameter
+    );
+    assert_eq!(
+        contract.borrow_balance_at(accounts.bob, past).unwrap_err(),
+        Error::InvalidParameter
+    );
+    assert_eq!(
+        contract.total_borrows_at(past).unwrap_err(),
+        Error::InvalidParameter
+    );
+    assert_eq!(
+        contract.total_reserves_at(past).unwrap_err(),
+        Error::InvalidParameter
+    );
+    assert_eq!(
+        contract.borrow_index_at(past).unwrap_err(),
+        Error::InvalidParameter
+    );
+}
+
+#[ink::test]
+fn set_minimum_initial_deposit_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let dummy_id = AccountId::from([0x01; 32]);
+    let mut contract = PoolContract::new(
+        Some(dummy_id),
+        dummy_id,
+        dummy_id,
+        dummy_id,
+        accounts.bob,
+        WrappedU256::from(exp_scale()),
+        10000,
+        String::from("Token Name"),
+        String::from("symbol"),
+        8,
+    );
+    assert_eq!(contract.minimum_initial_deposit(), 0);
+    assert!(contract.set_minimum_initial_deposit(1_000_000).is_ok());
+    assert_eq!(contract.minimum_initial_deposit(), 1_000_000);
+}
+
+#[ink::test]
+fn set_socialize_bad_debt_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let dummy_id = AccountId::from([0x01; 32]);
+    let mut contract = PoolContract::new(
+        Some(dummy_id),
+        dummy_id,
+        dummy_id,
+        dummy_id,
+        accounts.bob,
+        WrappedU256::from(exp_scale()),
+        10000,
+        String::from("Token Name"),
+        String::from("symbol"),
+        8,
+    );
+    assert!(!contract.socialize_bad_debt());
+    assert_eq!(contract.deficit(), 0);
+    assert!(contract.set_socialize_bad_debt(true).is_ok());
+    assert!(contract.socialize_bad_debt());
+}
+
+#[ink::test]
+fn set_max_stable_borrow_size_mantissa_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let dummy_id = AccountId::from([0x01; 32]);
+    let mut contract = PoolContract::new(
+        Some(dummy_id),
+        dummy_id,
+        dummy_id,
+        dummy_id,
+        accounts.bob,
+        WrappedU256::from(exp_scale()),
+        10000,
+        String::from("Token Name"),
+        String::from("symbol"),
+        8,
+    );
+    assert_eq!(
+        contract.max_stable_borrow_size_mantissa(),
+        WrappedU256::from(exp_scale().div(4))
+    );
+    assert!(contract
+        .set_max_stable_borrow_size_mantissa(WrappedU256::from(exp_scale()))
+        .is_ok());
+    assert_eq!(
+        contract.max_stable_borrow_size_mantissa(),
+        WrappedU256::from(exp_scale())
+    );
+    assert_eq!(
+        contract
+            .set_max_stable_borrow_size_mantissa(WrappedU256::from(exp_scale().add(U256::one())))
+            .unwrap_err(),
+        Error::SetMaxStableBorrowSizeBoundsCheck
+    );
+}
+
+#[ink::test]
+fn write_off_bad_debt_covered_by_reserves() {
+    let accounts = default_accounts();
+    let mut contract = new_pool_with_bad_debt(500, false);
+    let exchange_rate = contract.exchange_rate_stored();
+    assert_eq!(U256::from(exchange_rate), exp_scale());
+
+    let emitted = get_emitted_events().len();
+    assert!(contract._write_off_bad_debt(accounts.charlie, 500).is_ok());
+    assert_eq!(contract.total_borrows(), 0);
+    assert_eq!(contract.total_reserves(), 0);
+    assert_eq!(contract.deficit(), 0);
+    assert_eq!(contract.borrowers_count(), 0);
+    // the suppliers do not bear any loss
+    assert_eq!(contract.exchange_rate_stored(), exchange_rate);
+
+    let events = get_emitted_events().split_off(emitted);
+    assert_eq!(events.len(), 2);
+    if let Event::BadDebtDetected(event) = decode_event(&events[0]) {
+        assert_eq!(event.borrower, accounts.charlie);
+        assert_eq!(event.amount, 500);
+    } else {
+        panic!("unexpected event kind: expected BadDebtDetected event")
+    }
+    if let Event::BadDebtCoveredByReserves(event) = decode_event(&events[1]) {
+        assert_eq!(event.amount, 500);
+        assert_eq!(event.total_reserves_new, 0);
+    } else {
+        panic!("unexpected event kind: expected BadDebtCoveredByReserves event")
+    }
+}
+
+#[ink::test]
+fn write_off_bad_debt_socialized() {
+    let accounts = default_accounts();
+    let mut contract = new_pool_with_bad_debt(200, true);
+    assert_eq!(
+        U256::from(contract.exchange_rate_stored()),
+        exp_scale().mul(13).div(10)
+    );
+
+    let emitted = get_emitted_events().len();
+    assert!(contract._write_off_bad_debt(accounts.charlie, 500).is_ok());
+    assert_eq!(contract.total_borrows(), 0);
+    assert_eq!(contract.total_reserves(), 0);
+    assert_eq!(contract.deficit(), 0);
+    // the uncovered 300 is taken from the suppliers
+    assert_eq!(U256::from(contract.exchange_rate_stored()), exp_scale());
+
+    let events = get_emitted_events().split_off(emitted);
+    assert_eq!(events.len(), 3);
+    if let Event::BadDebtCoveredByReserves(event) = decode_event(&events[1]) {
+        assert_eq!(event.amount, 200);
+        assert_eq!(event.total_reserves_new, 0);
+    } else {
+        panic!("unexpected event kind: expected BadDebtCoveredByReserves event")
+    }
+    if let Event::BadDebtSocialized(event) = decode_event(&events[2]) {
+        assert_eq!(event.borrower, accounts.charlie);
+        assert_eq!(event.amount, 300);
+    } else {
+        panic!("unexpected event kind: expected BadDebtSocialized event")
+    }
+}
+
+#[ink::test]
+fn write_off_bad_debt_recorded_as_deficit() {
+    let accounts = default_accounts();
+    let mut contract = new_pool_with_bad_debt(200, false);
+    let exchange_rate = contract.exchange_rate_stored();
+    assert_eq!(U256::from(exchange_rate), exp_scale().mul(13).div(10));
+
+    let emitted = get_emitted_events().len();
+    assert!(contract._write_off_bad_debt(accounts.charlie, 500).is_ok());
+    assert_eq!(contract.total_borrows(), 0);
+    assert_eq!(contract.total_reserves(), 0);
+    assert_eq!(contract.deficit(), 300);
+    // the deficit is still counted as an asset until it is covered
+    assert_eq!(contract.exchange_rate_stored(), exchange_rate);
+
+    let events = get_emitted_events().split_off(emitted);
+    assert_eq!(events.len(), 3);
+    if let Event::DeficitRecorded(event) = decode_event(&events[2]) {
+        assert_eq!(event.borrower, accounts.charlie);
+        assert_eq!(event.amount, 300);
+        assert_eq!(event.deficit, 300);
+    } else {
+        panic!("unexpected event kind: expected DeficitRecorded event")
+    }
+}
+
+#[ink::test]
+fn cover_deficit_does_nothing_without_deficit() {
+    let mut contract = new_pool_with_bad_debt(0, false);
+    let emitted = get_emitted_events().len();
+    assert!(contract.cover_deficit(100).is_ok());
+    assert_eq!(contract.deficit(), 0);
+    assert_eq!(get_emitted_events().len(), emitted);
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn cover_deficit_works() {
+    let accounts = default_accounts();
+    let mut contract = new_pool_with_bad_debt(200, false);
+    assert!(contract._write_off_bad_debt(accounts.charlie, 500).is_ok());
+    // transfers the underlying from the caller to cover the deficit
+    contract.cover_deficit(100).unwrap();
+}
+
+#[ink::test]
+fn borrower_registry_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+    let dummy_id = AccountId::from([0x01; 32]);
+    let mut contract = PoolContract::new(
+        Some(dummy_id),
+        dummy_id,
+        dummy_id,
+        dummy_id,
+        accounts.bob,
+        WrappedU256::from(exp_scale()),
+        10000,
+        String::from("Token Name"),
+        String::from("symbol"),
+        8,
+    );
+    assert_eq!(contract.borrowers_count(), 0);
+    assert_eq!(contract.borrowers(0, 10), []);
+
+    contract._add_borrower(accounts.alice);
+    contract._add_borrower(accounts.charlie);
+    contract._add_borrower(accounts.django);
+    contract._add_borrower(accounts.alice);
+    assert_eq!(contract.borrowers_count(), 3);
+    assert_eq!(
+        contract.borrowers(0, 10),
+        [accounts.alice, accounts.charlie, accounts.django]
+    );
+    assert_eq!(contract.borrowers(1, 1), [accounts.charlie]);
+    assert_eq!(contract.borrowers(3, 1), []);
+
+    // the last account takes the position of the removed one
+    contract._remove_borrower(accounts.alice);
+    contract._remove_borrower(accounts.eve);
+    assert_eq!(contract.borrowers_count(), 2);
+    assert_eq!(
+        contract.borrowers(0, 10),
+        [accounts.django, accounts.charlie]
+    );
+    contract._remove_borrower(accounts.charlie);
+    contract._remove_borrower(accounts.django);
+    assert_eq!(contract.borrowers_count(), 0);
+    assert_eq!(contract.borrowers(0, 10), []);
+}
@@ -0,0 +1,145 @@
+// Copyright 2023 Asynmatrix Pte. Ltd.
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+#![feature(min_specialization)]
+
+#[cfg(test)]
+mod tests;
+
+/// Definition of PriceAggregator Contract
+#[openbrush::contract]
+pub mod contract {
+    use ink::codegen::{
+        EmitEvent,
+        Env,
+    };
+    use logics::{
+        impls::price_aggregator::{
+            Data,
+            Internal,
+            *,
+        },
+        traits::{
+            pool::PoolRef,
+            price_oracle::{
+                self,
+                PriceOracle,
+            },
+        },
+    };
+    use openbrush::{
+        contracts::ownable::*,
+        modifiers,
+        traits::Storage,
+    };
+
+    /// Contract's Storage
+    #[ink(storage)]
+    #[derive(Default, Storage)]
+    pub struct PriceAggregatorContract {
+        #[storage_field]
+        price_aggregator: Data,
+        #[storage_field]
+        ownable: ownable::Data,
+    }
+
+    /// Event: Sources of the price of the asset are updated
+    #[ink(event)]
+    pub struct SourceConfigUpdated {
+        #[ink(topic)]
+        pub asset: AccountId,
+        pub config: SourceConfig,
+    }
+
+    /// Event: Sources of the price of the asset are removed
+    #[ink(event)]
+    pub struct SourceConfigRemoved {
+        #[ink(topic)]
+        pub asset: AccountId,
+    }
+
+    impl Ownable for PriceAggregatorContract {}
+    impl PriceAggregator for PriceAggregatorContract {}
+    impl Internal for PriceAggregatorContract {
+        fn _emit_source_config_updated_event(&self, asset: AccountId, config: SourceConfig) {
+            self.env().emit_event(SourceConfigUpdated { asset, config });
+        }
+
+        fn _emit_source_config_removed_event(&self, asset: AccountId) {
+            self.env().emit_event(SourceConfigRemoved { asset });
+        }
+    }
+
+    /// Serves the median of the sources so that the aggregator can replace a PriceOracle.
+    /// Prices can only be fed to the sources, so the sybil functions are disabled.
+    impl PriceOracle for PriceAggregatorContract {
+        #[ink(message)]
+        fn get_price(&self, asset: AccountId) -> Option<u128> {
+            self._get_aggregated_price(asset).ok().map(|p| p.price)
+        }
+
+        #[ink(message)]
+        fn get_underlying_price(&self, pool: AccountId) -> Option<u128> {
+            self.get_price(PoolRef::underlying(&pool)?)
+        }
+
+        #[ink(message)]
+        fn get_price_data(&self, asset: AccountId) -> Option<(u128, Timestamp)> {
+            self._get_aggregated_price(asset)
+                .ok()
+                .map(|p| (p.price, p.updated_at))
+        }
+
+        #[ink(message)]
+        fn get_heartbeat(&self, asset: AccountId) -> Timestamp {
+            self._get_heartbeat(asset)
+        }
+
+        #[ink(message)]
+        fn set_fixed_price(&mut self, _asset: AccountId, _value: u128) -> price_oracle::Result<()> {
+            Err(price_oracle::Error::CallerIsNotAuthorized)
+        }
+
+        #[ink(message)]
+        #[modifiers(only_owner)]
+        fn set_heartbeat(
+            &mut self,
+            asset: AccountId,
+            heartbeat: Timestamp,
+        ) -> price_oracle::Result<()> {
+            self._set_heartbeat(asset, heartbeat);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn authorize_sybil(&mut self, _sybil: AccountId) -> price_oracle::Result<()> {
+            Err(price_oracle::Error::CallerIsNotAuthorized)
+        }
+
+        #[ink(message)]
+        fn unauthorize_sybil(&mut self, _sybil: AccountId) -> price_oracle::Result<()> {
+            Err(price_oracle::Error::CallerIsNotAuthorized)
+        }
+
+        #[ink(message)]
+        fn is_sybil_authorized(&self, _sybil: AccountId) -> bool {
+            false
+        }
+    }
+
+    impl PriceAggregatorContract {
+        /// Generate this contract
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            let mut instance = Self::default();
+            let caller = Self::env().caller();
+            instance._init_with_owner(caller);
+            instance
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::contract::*;
+use ink::env::{
+    test::{
+        self,
+        DefaultAccounts,
+    },
+    DefaultEnvironment,
+};
+use logics::{
+    impls::price_aggregator::*,
+    traits::price_oracle::{
+        self,
+        PriceOracle,
+    },
+};
+use openbrush::{
+    contracts::ownable::OwnableError,
+    traits::AccountId,
+};
+
+fn default_accounts() -> DefaultAccounts<DefaultEnvironment> {
+    test::default_accounts::<DefaultEnvironment>()
+}
+fn set_caller(id: AccountId) {
+    test::set_caller::<DefaultEnvironment>(id);
+}
+
+#[ink::test]
+fn new_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let contract = PriceAggregatorContract::new();
+
+    let asset_addr = AccountId::from([0x01; 32]);
+    assert_eq!(contract.source_config(asset_addr), None);
+    assert_eq!(
+        contract.get_aggregated_price(asset_addr).unwrap_err(),
+        Error::SourceConfigIsNotSet
+    );
+    assert_eq!(contract.get_price(asset_addr), None);
+}
+
+#[ink::test]
+fn set_source_config_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let mut contract = PriceAggregatorContract::new();
+
+    let asset_addr = AccountId::from([0x01; 32]);
+    let config = SourceConfig {
+        sources: [
+            AccountId::from([0x11; 32]),
+            AccountId::from([0x12; 32]),
+            AccountId::from([0x13; 32]),
+        ]
+        .to_vec(),
+        min_sources: 2,
+        max_deviation: 500,
+    };
+    assert!(contract
+        .set_source_config(asset_addr, config.clone())
+        .is_ok());
+    assert_eq!(contract.source_config(asset_addr), Some(config.clone()));
+
+    assert_eq!(
+        contract
+            .set_source_config(
+                asset_addr,
+                SourceConfig {
+                    min_sources: 4,
+                    ..config.clone()
+                }
+            )
+            .unwrap_err(),
+        Error::InvalidSourceConfig
+    );
+
+    set_caller(accounts.charlie);
+    assert_eq!(
+        contract.set_source_config(asset_addr, config).unwrap_err(),
+        Error::Ownable(OwnableError::CallerIsNotOwner)
+    );
+    assert_eq!(
+        contract.remove_source_config(asset_addr).unwrap_err(),
+        Error::Ownable(OwnableError::CallerIsNotOwner)
+    );
+
+    set_caller(accounts.bob);
+    assert!(contract.remove_source_config(asset_addr).is_ok());
+    assert_eq!(contract.source_config(asset_addr), None);
+    assert_eq!(
+        contract.remove_source_config(asset_addr).unwrap_err(),
+        Error::SourceConfigIsNotSet
+    );
+}
+
+#[ink::test]
+fn set_heartbeat_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let mut contract = PriceAggregatorContract::new();
+
+    let asset_addr = AccountId::from([0x01; 32]);
+    assert_eq!(contract.get_heartbeat(asset_addr), 0);
+    assert!(contract.set_heartbeat(asset_addr, 60 * 60 * 1000).is_ok());
+    assert_eq!(contract.get_heartbeat(asset_addr), 60 * 60 * 1000);
+
+    set_caller(accounts.charlie);
+    assert_eq!(
+        contract.set_heartbeat(asset_addr, 0).unwrap_err(),
+        price_oracle::Error::Ownable(OwnableError::CallerIsNotOwner)
+    );
+}
+
+#[ink::test]
+fn sybil_functions_are_disabled() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let mut contract = PriceAggregatorContract::new();
+
+    let asset_addr = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract.set_fixed_price(asset_addr, 1).unwrap_err(),
+        price_oracle::Error::CallerIsNotAuthorized
+    );
+    assert_eq!(
+        contract.authorize_sybil(accounts.charlie).unwrap_err(),
+        price_oracle::Error::CallerIsNotAuthorized
+    );
+    assert!(!contract.is_sybil_authorized(accounts.bob));
+}
@@ -14,6 +14,10 @@ mod tests;
 /// Definition of PriceOracle Contract
 #[openbrush::contract]
 pub mod contract {
+    use ink::codegen::{
+        EmitEvent,
+        Env,
+    };
     use logics::impls::price_oracle::{
         Data,
         Internal,
@@ -34,9 +38,77 @@ pub mod contract {
         ownable: ownable::Data,
     }
 
+    /// Event: Deviation bounds of the price updates of the asset are changed
+    #[ink(event)]
+    pub struct DeviationBoundsUpdated {
+        #[ink(topic)]
+        pub asset: AccountId,
+        pub bounds: Option<DeviationBounds>,
+    }
+
+    /// Event: Price update out of the deviation bounds is rejected
+    #[ink(event)]
+    pub struct PriceUpdateRejected {
+        #[ink(topic)]
+        pub asset: AccountId,
+        pub price: u128,
+    }
+
+    /// Event: Price update out of the deviation bounds waits for confirmation
+    #[ink(event)]
+    pub struct PriceUpdatePending {
+        #[ink(topic)]
+        pub asset: AccountId,
+        pub price: u128,
+    }
+
+    /// Event: Pending price is confirmed by the owner
+    #[ink(event)]
+    pub struct PendingPriceConfirmed {
+        #[ink(topic)]
+        pub asset: AccountId,
+        pub price: u128,
+    }
+
+    /// Event: Pending price is discarded by the owner
+    #[ink(event)]
+    pub struct PendingPriceDiscarded {
+        #[ink(topic)]
+        pub asset: AccountId,
+        pub price: u128,
+    }
+
     impl Ownable for PriceOracleContract {}
     impl PriceOracle for PriceOracleContract {}
-    impl Internal for PriceOracleContract {}
+    impl PriceCircuitBreaker for PriceOracleContract {}
+    impl Internal for PriceOracleContract {
+        fn _emit_deviation_bounds_updated_event(
+            &self,
+            asset: AccountId,
+            bounds: Option<DeviationBounds>,
+        ) {
+            self.env()
+                .emit_event(DeviationBoundsUpdated { asset, bounds });
+        }
+
+        fn _emit_price_update_rejected_event(&self, asset: AccountId, price: u128) {
+            self.env().emit_event(PriceUpdateRejected { asset, price });
+        }
+
+        fn _emit_price_update_pending_event(&self, asset: AccountId, price: u128) {
+            self.env().emit_event(PriceUpdatePending { asset, price });
+        }
+
+        fn _emit_pending_price_confirmed_event(&self, asset: AccountId, price: u128) {
+            self.env()
+                .emit_event(PendingPriceConfirmed { asset, price });
+        }
+
+        fn _emit_pending_price_discarded_event(&self, asset: AccountId, price: u128) {
+            self.env()
+                .emit_event(PendingPriceDiscarded { asset, price });
+        }
+    }
 
     impl PriceOracleContract {
         /// Generate this contract
@@ -2,12 +2,20 @@ use crate::contract::*;
 use ink::env::{
     test::{
         self,
+        recorded_events,
         DefaultAccounts,
+        EmittedEvent,
     },
     DefaultEnvironment,
 };
 use logics::impls::price_oracle::*;
-use openbrush::traits::AccountId;
+use openbrush::{
+    contracts::ownable::OwnableError,
+    traits::AccountId,
+};
+use scale::Decode;
+
+type Event = <PriceOracleContract as ink::reflect::ContractEventBase>::Type;
 
 fn default_accounts() -> DefaultAccounts<DefaultEnvironment> {
     test::default_accounts::<DefaultEnvironment>()
@@ -15,6 +23,9 @@ fn default_accounts() -> DefaultAccounts<DefaultEnvironment> {
 fn set_caller(id: AccountId) {
     test::set_caller::<DefaultEnvironment>(id);
 }
+fn get_emitted_events() -> Vec<EmittedEvent> {
+    recorded_events().collect::<Vec<_>>()
+}
 
 #[ink::test]
 fn new_works() {
@@ -40,3 +51,195 @@ fn set_fixed_price_works() {
         Some(PRICE_PRECISION * 101 / 100)
     )
 }
+
+#[ink::test]
+fn get_price_data_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let mut contract = PriceOracleContract::new();
+
+    let asset_addr = AccountId::from([0x01; 32]);
+    assert_eq!(contract.get_price_data(asset_addr), None);
+
+    test::set_block_timestamp::<DefaultEnvironment>(100);
+    assert!(contract
+        .set_fixed_price(asset_addr, PRICE_PRECISION)
+        .is_ok());
+    assert_eq!(
+        contract.get_price_data(asset_addr),
+        Some((PRICE_PRECISION, 100))
+    );
+}
+
+#[ink::test]
+fn set_heartbeat_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let mut contract = PriceOracleContract::new();
+
+    let asset_addr = AccountId::from([0x01; 32]);
+    assert_eq!(contract.get_heartbeat(asset_addr), 0);
+    assert!(contract.set_heartbeat(asset_addr, 60 * 60 * 1000).is_ok());
+    assert_eq!(contract.get_heartbeat(asset_addr), 60 * 60 * 1000);
+
+    set_caller(accounts.charlie);
+    assert_eq!(
+        contract.set_heartbeat(asset_addr, 0).unwrap_err(),
+        Error::Ownable(OwnableError::CallerIsNotOwner)
+    );
+}
+
+#[ink::test]
+fn set_deviation_bounds_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let mut contract = PriceOracleContract::new();
+
+    let asset_addr = AccountId::from([0x01; 32]);
+    let bounds = DeviationBounds {
+        max_update_deviation: 1000,
+        max_window_deviation: 2000,
+        window: 1000,
+        pending_on_breach: false,
+    };
+    assert_eq!(contract.deviation_bounds(asset_addr), None);
+    assert_eq!(
+        contract
+            .set_deviation_bounds(
+                asset_addr,
+                Some(DeviationBounds {
+                    max_update_deviation: 0,
+                    ..bounds.clone()
+                })
+            )
+            .unwrap_err(),
+        Error::InvalidDeviationBounds
+    );
+    assert!(contract
+        .set_deviation_bounds(asset_addr, Some(bounds.clone()))
+        .is_ok());
+    assert_eq!(contract.deviation_bounds(asset_addr), Some(bounds));
+
+    set_caller(accounts.charlie);
+    assert_eq!(
+        contract.set_deviation_bounds(asset_addr, None).unwrap_err(),
+        Error::Ownable(OwnableError::CallerIsNotOwner)
+    );
+
+    set_caller(accounts.bob);
+    assert!(contract.set_deviation_bounds(asset_addr, None).is_ok());
+    assert_eq!(contract.deviation_bounds(asset_addr), None);
+}
+
+#[ink::test]
+fn set_fixed_price_rejects_update_out_of_bounds() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let mut contract = PriceOracleContract::new();
+
+    let asset_addr = AccountId::from([0x01; 32]);
+    assert!(contract
+        .set_deviation_bounds(
+            asset_addr,
+            Some(DeviationBounds {
+                max_update_deviation: 1000,
+                max_window_deviation: 2000,
+                window: 1000,
+                pending_on_breach: false,
+            })
+        )
+        .is_ok());
+    assert!(contract.set_fixed_price(asset_addr, 100).is_ok());
+
+    // out of the bounds for each update
+    for price in [0, 150] {
+        assert!(contract.set_fixed_price(asset_addr, price).is_ok());
+        assert_eq!(contract.get_price(asset_addr), Some(100));
+        let events = get_emitted_events();
+        if let Ok(Event::PriceUpdateRejected(event)) =
+            <Event as Decode>::decode(&mut &events[events.len() - 1].data[..])
+        {
+            assert_eq!(event.asset, asset_addr);
+            assert_eq!(event.price, price);
+        } else {
+            panic!("unexpected event kind: expected PriceUpdateRejected event")
+        }
+    }
+
+    // out of the bounds over the window
+    assert!(contract.set_fixed_price(asset_addr, 109).is_ok());
+    assert!(contract.set_fixed_price(asset_addr, 119).is_ok());
+    assert!(contract.set_fixed_price(asset_addr, 125).is_ok());
+    assert_eq!(contract.get_price(asset_addr), Some(119));
+
+    // a new window starts from the current price
+    test::set_block_timestamp::<DefaultEnvironment>(1001);
+    assert!(contract.set_fixed_price(asset_addr, 125).is_ok());
+    assert_eq!(contract.get_price(asset_addr), Some(125));
+    assert_eq!(contract.pending_price(asset_addr), None);
+}
+
+#[ink::test]
+fn confirm_pending_price_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let mut contract = PriceOracleContract::new();
+
+    let asset_addr = AccountId::from([0x01; 32]);
+    assert!(contract
+        .set_deviation_bounds(
+            asset_addr,
+            Some(DeviationBounds {
+                max_update_deviation: 1000,
+                max_window_deviation: 2000,
+                window: 1000,
+                pending_on_breach: true,
+            })
+        )
+        .is_ok());
+    assert!(contract.set_fixed_price(asset_addr, 100).is_ok());
+    assert!(contract.set_fixed_price(asset_addr, 200).is_ok());
+    assert_eq!(contract.pending_price(asset_addr), Some(200));
+    assert_eq!(contract.get_price(asset_addr), None);
+    assert_eq!(contract.get_price_data(asset_addr), None);
+
+    // updates keep pending until the owner acts
+    assert!(contract.set_fixed_price(asset_addr, 180).is_ok());
+    assert_eq!(contract.pending_price(asset_addr), Some(180));
+
+    set_caller(accounts.charlie);
+    assert_eq!(
+        contract.confirm_pending_price(asset_addr, 180).unwrap_err(),
+        Error::Ownable(OwnableError::CallerIsNotOwner)
+    );
+
+    // the price reviewed before the later update is not confirmed
+    set_caller(accounts.bob);
+    assert_eq!(
+        contract.confirm_pending_price(asset_addr, 200).unwrap_err(),
+        Error::PendingPriceMismatch
+    );
+    assert_eq!(contract.pending_price(asset_addr), Some(180));
+    assert_eq!(contract.get_price(asset_addr), None);
+
+    assert!(contract.confirm_pending_price(asset_addr, 180).is_ok());
+    assert_eq!(contract.pending_price(asset_addr), None);
+    assert_eq!(contract.get_price(asset_addr), Some(180));
+
+    // bounds are measured from the confirmed price
+    assert!(contract.set_fixed_price(asset_addr, 195).is_ok());
+    assert_eq!(contract.get_price(asset_addr), Some(195));
+
+    assert!(contract.set_fixed_price(asset_addr, 1).is_ok());
+    assert!(contract.discard_pending_price(asset_addr).is_ok());
+    assert_eq!(contract.get_price(asset_addr), Some(195));
+    assert_eq!(
+        contract.discard_pending_price(asset_addr).unwrap_err(),
+        Error::PendingPriceIsNotSet
+    );
+}
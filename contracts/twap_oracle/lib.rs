@@ -0,0 +1,150 @@
+// Copyright 2023 Asynmatrix Pte. Ltd.
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+#![feature(min_specialization)]
+
+#[cfg(test)]
+mod tests;
+
+/// Definition of TwapOracle Contract
+#[openbrush::contract]
+pub mod contract {
+    use ink::codegen::{
+        EmitEvent,
+        Env,
+    };
+    use logics::{
+        impls::twap_oracle::{
+            Data,
+            Internal,
+            *,
+        },
+        traits::{
+            pool::PoolRef,
+            price_oracle::{
+                self,
+                PriceOracle,
+            },
+            types::WrappedU256,
+        },
+    };
+    use openbrush::{
+        contracts::ownable::*,
+        modifiers,
+        traits::Storage,
+    };
+
+    /// Contract's Storage
+    #[ink(storage)]
+    #[derive(Default, Storage)]
+    pub struct TwapOracleContract {
+        #[storage_field]
+        twap_oracle: Data,
+        #[storage_field]
+        ownable: ownable::Data,
+    }
+
+    /// Event: Source of the price of the asset is updated
+    #[ink(event)]
+    pub struct TwapConfigUpdated {
+        #[ink(topic)]
+        pub asset: AccountId,
+        pub config: TwapConfig,
+    }
+
+    /// Event: Price accumulator of the pair of the asset is recorded
+    #[ink(event)]
+    pub struct ObservationRecorded {
+        #[ink(topic)]
+        pub asset: AccountId,
+        pub timestamp: Timestamp,
+        pub price_cumulative: WrappedU256,
+    }
+
+    impl Ownable for TwapOracleContract {}
+    impl TwapOracle for TwapOracleContract {}
+    impl Internal for TwapOracleContract {
+        fn _emit_twap_config_updated_event(&self, asset: AccountId, config: TwapConfig) {
+            self.env().emit_event(TwapConfigUpdated { asset, config });
+        }
+
+        fn _emit_observation_recorded_event(&self, asset: AccountId, observation: TwapObservation) {
+            self.env().emit_event(ObservationRecorded {
+                asset,
+                timestamp: observation.timestamp,
+                price_cumulative: observation.price_cumulative,
+            });
+        }
+    }
+
+    /// Serves the time-weighted average price so that the TwapOracle can replace a PriceOracle.
+    /// Prices come from the pairs only, so the sybil functions are disabled.
+    impl PriceOracle for TwapOracleContract {
+        #[ink(message)]
+        fn get_price(&self, asset: AccountId) -> Option<u128> {
+            self._consult(asset).ok()
+        }
+
+        #[ink(message)]
+        fn get_underlying_price(&self, pool: AccountId) -> Option<u128> {
+            self.get_price(PoolRef::underlying(&pool)?)
+        }
+
+        #[ink(message)]
+        fn get_price_data(&self, asset: AccountId) -> Option<(u128, Timestamp)> {
+            self._get_price_data(asset)
+        }
+
+        #[ink(message)]
+        fn get_heartbeat(&self, asset: AccountId) -> Timestamp {
+            self._get_heartbeat(asset)
+        }
+
+        #[ink(message)]
+        fn set_fixed_price(&mut self, _asset: AccountId, _value: u128) -> price_oracle::Result<()> {
+            Err(price_oracle::Error::CallerIsNotAuthorized)
+        }
+
+        #[ink(message)]
+        #[modifiers(only_owner)]
+        fn set_heartbeat(
+            &mut self,
+            asset: AccountId,
+            heartbeat: Timestamp,
+        ) -> price_oracle::Result<()> {
+            self._set_heartbeat(asset, heartbeat);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn authorize_sybil(&mut self, _sybil: AccountId) -> price_oracle::Result<()> {
+            Err(price_oracle::Error::CallerIsNotAuthorized)
+        }
+
+        #[ink(message)]
+        fn unauthorize_sybil(&mut self, _sybil: AccountId) -> price_oracle::Result<()> {
+            Err(price_oracle::Error::CallerIsNotAuthorized)
+        }
+
+        #[ink(message)]
+        fn is_sybil_authorized(&self, _sybil: AccountId) -> bool {
+            false
+        }
+    }
+
+    impl TwapOracleContract {
+        /// Generate this contract
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            let mut instance = Self::default();
+            let caller = Self::env().caller();
+            instance._init_with_owner(caller);
+            instance
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use crate::contract::*;
+use ink::env::{
+    test::{
+        self,
+        DefaultAccounts,
+    },
+    DefaultEnvironment,
+};
+use logics::{
+    impls::twap_oracle::*,
+    traits::{
+        price_oracle::{
+            self,
+            PriceOracle,
+        },
+        types::WrappedU256,
+    },
+};
+use openbrush::{
+    contracts::ownable::OwnableError,
+    traits::AccountId,
+};
+use primitive_types::U256;
+
+fn default_accounts() -> DefaultAccounts<DefaultEnvironment> {
+    test::default_accounts::<DefaultEnvironment>()
+}
+fn set_caller(id: AccountId) {
+    test::set_caller::<DefaultEnvironment>(id);
+}
+fn twap_config() -> TwapConfig {
+    TwapConfig {
+        pair: AccountId::from([0x10; 32]),
+        window: 60 * 60 * 1000,
+        period: 5 * 60 * 1000,
+        min_observations: 3,
+        asset_decimals: 6,
+        quote_decimals: 18,
+        quote_oracle: None,
+    }
+}
+
+#[ink::test]
+fn new_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let contract = TwapOracleContract::new();
+
+    let asset_addr = AccountId::from([0x01; 32]);
+    assert_eq!(contract.twap_config(asset_addr), None);
+    assert_eq!(contract.observations(asset_addr), []);
+    assert_eq!(
+        contract.consult(asset_addr).unwrap_err(),
+        Error::TwapConfigIsNotSet
+    );
+    assert_eq!(contract.get_price(asset_addr), None);
+}
+
+#[ink::test]
+#[should_panic(
+    expected = "not implemented: off-chain environment does not support contract invocation"
+)]
+fn set_twap_config_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let mut contract = TwapOracleContract::new();
+
+    let asset_addr = AccountId::from([0x01; 32]);
+    contract.set_twap_config(asset_addr, twap_config()).unwrap();
+}
+
+#[ink::test]
+fn set_twap_config_fails() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let mut contract = TwapOracleContract::new();
+
+    let asset_addr = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract
+            .set_twap_config(
+                asset_addr,
+                TwapConfig {
+                    period: 0,
+                    ..twap_config()
+                }
+            )
+            .unwrap_err(),
+        Error::InvalidTwapConfig
+    );
+
+    set_caller(accounts.charlie);
+    assert_eq!(
+        contract
+            .set_twap_config(asset_addr, twap_config())
+            .unwrap_err(),
+        Error::Ownable(OwnableError::CallerIsNotOwner)
+    );
+}
+
+#[ink::test]
+fn update_fails_without_twap_config() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let mut contract = TwapOracleContract::new();
+
+    let asset_addr = AccountId::from([0x01; 32]);
+    assert_eq!(
+        contract.update(asset_addr).unwrap_err(),
+        Error::TwapConfigIsNotSet
+    );
+}
+
+#[ink::test]
+fn consult_fails_with_insufficient_observations() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let mut contract = TwapOracleContract::new();
+
+    let asset_addr = AccountId::from([0x01; 32]);
+    let config = twap_config();
+    assert!(contract
+        ._set_twap_config(asset_addr, config.clone(), true)
+        .is_ok());
+    assert_eq!(
+        contract.consult(asset_addr).unwrap_err(),
+        Error::InsufficientObservations
+    );
+
+    // observations out of the window are not counted
+    for i in 0..config.min_observations as u64 {
+        contract._push_observation(
+            asset_addr,
+            TwapObservation {
+                timestamp: i * config.period,
+                price_cumulative: WrappedU256::from(U256::zero()),
+            },
+        );
+    }
+    test::set_block_timestamp::<DefaultEnvironment>(
+        (config.min_observations as u64 - 1) * config.period + config.window + 1,
+    );
+    assert_eq!(
+        contract.consult(asset_addr).unwrap_err(),
+        Error::InsufficientObservations
+    );
+    assert_eq!(contract.get_price_data(asset_addr), None);
+}
+
+#[ink::test]
+fn observations_are_kept_in_ring_buffer() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let mut contract = TwapOracleContract::new();
+
+    let asset_addr = AccountId::from([0x01; 32]);
+    let count = OBSERVATION_CAPACITY as u64 + 2;
+    for i in 0..count {
+        contract._push_observation(
+            asset_addr,
+            TwapObservation {
+                timestamp: i,
+                price_cumulative: WrappedU256::from(U256::from(i)),
+            },
+        );
+    }
+
+    let observations = contract.observations(asset_addr);
+    assert_eq!(observations.len(), OBSERVATION_CAPACITY as usize);
+    assert_eq!(observations[0].timestamp, 2);
+    assert_eq!(observations[observations.len() - 1].timestamp, count - 1);
+    assert!(observations
+        .windows(2)
+        .all(|pair| pair[0].timestamp < pair[1].timestamp));
+
+    // a new configuration discards the observations
+    assert!(contract
+        ._set_twap_config(asset_addr, twap_config(), true)
+        .is_ok());
+    assert_eq!(contract.observations(asset_addr), []);
+}
+
+#[ink::test]
+fn set_heartbeat_works() {
+    let accounts = default_accounts();
+    set_caller(accounts.bob);
+
+    let mut contract = TwapOracleContract::new();
+
+    let asset_addr = AccountId::from([0x01; 32]);
+    assert!(contract.set_heartbeat(asset_addr, 60 * 60 * 1000).is_ok());
+    assert_eq!(contract.get_heartbeat(asset_addr), 60 * 60 * 1000);
+    assert_eq!(
+        contract.set_fixed_price(asset_addr, 1).unwrap_err(),
+        price_oracle::Error::CallerIsNotAuthorized
+    );
+
+    set_caller(accounts.charlie);
+    assert_eq!(
+        contract.set_heartbeat(asset_addr, 0).unwrap_err(),
+        price_oracle::Error::Ownable(OwnableError::CallerIsNotOwner)
+    );
+}
@@ -1,8 +1,15 @@
 use super::{
-    exp_no_err::Exp,
+    exp_no_err::{
+        exp_scale,
+        Exp,
+    },
     pool::{
-        utils::underlying_balance,
+        utils::{
+            protocol_seize_share_mantissa,
+            underlying_balance,
+        },
         COLLATERAL_FACTOR_MANTISSA_DECIMALS,
+        DEAD_SHARES_HOLDER,
         LIQUIDATION_THRESHOLD_DECIMALS,
     },
 };
@@ -28,12 +35,16 @@ use core::ops::{
 };
 use ink::prelude::vec::Vec;
 use openbrush::{
-    storage::Mapping,
+    storage::{
+        Mapping,
+        TypeGuard,
+    },
     traits::{
         AccountId,
         Balance,
         Storage,
         String,
+        Timestamp,
     },
 };
 use primitive_types::U256;
@@ -43,78 +54,159 @@ pub use self::utils::{
     calculate_available_borrow_in_base_currency,
     calculate_health_factor_from_balances,
     collateral_factor_max_mantissa,
+    deprecated_collateral_factor_mantissa,
+    emode_category_is_valid,
     get_hypothetical_account_liquidity,
+    get_hypothetical_account_liquidity_per_asset,
     liquidate_calculate_seize_tokens,
+    liquidation_auction_incentive_mantissa,
+    liquidation_close_factor_mantissa,
     BalanceDecreaseAllowedParam,
+    DeprecatedCollateralFactorInput,
     GetHypotheticalAccountLiquidityInput,
     HypotheticalAccountLiquidityCalculationParam,
     LiquidateCalculateSeizeTokensInput,
+    LiquidationAuctionIncentiveInput,
+    LiquidationCloseFactorInput,
+    HEALTH_FACTOR_LIQUIDATION_THRESHOLD,
 };
 
 pub const STORAGE_KEY: u32 = openbrush::storage_unique_key!(Data);
 
-pub const MAXIMUM_MARKETS: usize = 8;
+pub const MAXIMUM_MARKETS: usize = 64;
 
 #[derive(Debug)]
 #[openbrush::upgradeable_storage(STORAGE_KEY)]
 pub struct Data {
     /// AccountId of managed Pools
     pub markets: Vec<AccountId>,
+    /// Mapping of account and the Pools where it has a balance or debt
+    pub account_markets: Mapping<AccountId, Vec<AccountId>>,
     /// Pair of underlying and pool
     pub underlying_market_pair: Mapping<AccountId, AccountId>,
     /// Pair of pool and underlying
     pub market_underlying_pair: Mapping<AccountId, AccountId>,
     /// Mapping of Pool and Collateral Factors (Decimals: 18)
     pub collateral_factor_mantissa: Mapping<AccountId, WrappedU256>,
-    /// Whether Pool has paused `Mint` Action
-    pub mint_guardian_paused: Mapping<AccountId, bool>,
-    /// Whether Pool has paused `Borrow` Action
-    pub borrow_guardian_paused: Mapping<AccountId, bool>,
-    /// Whether Pool has paused `Seize` Action
-    pub seize_guardian_paused: bool,
-    /// Whether Pool has paused `Transfer` Action
-    pub transfer_guardian_paused: bool,
+    /// Mapping of Pool and the mask of its paused actions (`ACTION_*` bits)
+    pub paused_actions: Mapping<AccountId, u8>,
+    /// Mask of the actions paused in all Pools (`ACTION_*` bits)
+    pub global_paused_actions: u8,
+    /// Whether Pool is frozen, blocking new supply and borrows only
+    pub market_frozen: Mapping<AccountId, bool>,
     /// Oracle's AccountId associated with this contract
     pub oracle: Option<AccountId>,
     /// Close Factor
     pub close_factor_mantissa: WrappedU256,
     /// Liquidation Incentive
     pub liquidation_incentive_mantissa: WrappedU256,
+    /// Mapping of Pool and Liquidation Incentive applied when seizing its collateral (Decimals: 18)
+    pub market_liquidation_incentive_mantissa: Mapping<AccountId, WrappedU256>,
+    /// Share of the seized collateral added to the reserves of the Pools without their own (Decimals: 18)
+    pub protocol_seize_share_mantissa: WrappedU256,
+    /// Mapping of Pool and share of the seized collateral added to its reserves (Decimals: 18)
+    pub market_protocol_seize_share_mantissa: Mapping<AccountId, WrappedU256>,
+    /// Duration in which the Liquidation Incentive rises to the one of the collateral market, 0 disables the auction
+    pub liquidation_auction_duration: Timestamp,
+    /// Liquidation Incentive at the start of the liquidation auction (Decimals: 18)
+    pub liquidation_auction_start_incentive_mantissa: WrappedU256,
+    /// Mapping of account and when it became liquidatable
+    pub liquidation_auction_start: Mapping<AccountId, Timestamp>,
+    /// Health factor below which the debt can be fully liquidated (Decimals: 18)
+    pub critical_health_factor: WrappedU256,
+    /// Debt or collateral position below which it can be fully liquidated (in base currency, Decimals: 18)
+    pub liquidation_dust_threshold: Balance,
     /// Maximum that can be borrowed per Pool
     pub borrow_caps: Mapping<AccountId, Balance>,
+    /// Maximum that can be supplied per Pool (in underlying)
+    pub supply_caps: Mapping<AccountId, Balance>,
     /// Manager's AccountId associated with this contract
     pub manager: Option<AccountId>,
     /// AccountId of Pending Manager use for transfer manager role
     pub pending_manager: Option<AccountId>,
     /// Flashloan Gateway's AccountId associated with this contract
     pub flashloan_gateway: Option<AccountId>,
+    /// E-mode categories by id (0 is reserved for no category)
+    pub emode_categories: Mapping<u8, EModeCategory>,
+    /// Mapping of Pool and E-mode category
+    pub market_emode_category: Mapping<AccountId, u8>,
+    /// Mapping of account and the E-mode category it opted in to
+    pub user_emode: Mapping<AccountId, u8>,
+    /// Whether Pool is isolated
+    pub isolated: Mapping<AccountId, bool>,
+    /// Maximum total debt (in base currency) of the accounts using isolated Pool as collateral
+    pub debt_ceilings: Mapping<AccountId, Balance>,
+    /// Total debt (in base currency) of the accounts using isolated Pool as collateral
+    pub isolated_debts: Mapping<AccountId, Balance>,
+    /// Mapping of account and borrowed Pool, and the isolated Pool its debt counts towards with the amount
+    pub account_isolated_debts:
+        Mapping<(AccountId, AccountId), (AccountId, Balance), AccountIsolatedDebtsKey>,
+    /// Whether Pool can be borrowed by the accounts using isolated Pool as collateral
+    pub borrowable_in_isolation: Mapping<AccountId, bool>,
+    /// Mapping of deprecated Pool and its deprecation
+    pub market_deprecation: Mapping<AccountId, MarketDeprecation>,
+    /// Whether the protocol is shut down for settlement
+    pub shutdown: bool,
+    /// Mapping of underlying and its price frozen at the shutdown
+    pub settlement_prices: Mapping<AccountId, u128>,
 }
 
 impl Default for Data {
     fn default() -> Self {
         Self {
             markets: Default::default(),
+            account_markets: Default::default(),
             underlying_market_pair: Default::default(),
             market_underlying_pair: Default::default(),
             collateral_factor_mantissa: Default::default(),
-            mint_guardian_paused: Default::default(),
-            borrow_guardian_paused: Default::default(),
-            seize_guardian_paused: Default::default(),
-            transfer_guardian_paused: Default::default(),
+            paused_actions: Default::default(),
+            global_paused_actions: Default::default(),
+            market_frozen: Default::default(),
             oracle: None,
             close_factor_mantissa: WrappedU256::from(U256::zero()),
             liquidation_incentive_mantissa: WrappedU256::from(U256::zero()),
+            market_liquidation_incentive_mantissa: Default::default(),
+            protocol_seize_share_mantissa: WrappedU256::from(protocol_seize_share_mantissa()),
+            market_protocol_seize_share_mantissa: Default::default(),
+            liquidation_auction_duration: Default::default(),
+            liquidation_auction_start_incentive_mantissa: WrappedU256::from(U256::zero()),
+            liquidation_auction_start: Default::default(),
+            critical_health_factor: WrappedU256::from(U256::zero()),
+            liquidation_dust_threshold: Default::default(),
             borrow_caps: Default::default(),
+            supply_caps: Default::default(),
             manager: None,
             pending_manager: None,
             flashloan_gateway: None,
+            emode_categories: Default::default(),
+            market_emode_category: Default::default(),
+            user_emode: Default::default(),
+            isolated: Default::default(),
+            debt_ceilings: Default::default(),
+            isolated_debts: Default::default(),
+            account_isolated_debts: Default::default(),
+            borrowable_in_isolation: Default::default(),
+            market_deprecation: Default::default(),
+            shutdown: false,
+            settlement_prices: Default::default(),
         }
     }
 }
 
+pub struct AccountIsolatedDebtsKey;
+
+impl<'a> TypeGuard<'a> for AccountIsolatedDebtsKey {
+    type Type = &'a (&'a AccountId, &'a AccountId);
+}
+
 pub trait Internal {
-    fn _mint_allowed(&self, pool: AccountId, minter: AccountId, mint_amount: Balance)
-        -> Result<()>;
+    fn _mint_allowed(
+        &self,
+        pool: AccountId,
+        minter: AccountId,
+        mint_amount: Balance,
+        pool_attribute: Option<PoolAttributes>,
+    ) -> Result<()>;
 
     fn _redeem_allowed(
         &self,
@@ -124,6 +216,15 @@ pub trait Internal {
         pool_attribute: Option<PoolAttributes>,
     ) -> Result<()>;
 
+    /// Checks that the account stays solvent after its balance in the pool decreases by `amount`
+    fn _redeem_liquidity_allowed(
+        &self,
+        pool: AccountId,
+        redeemer: AccountId,
+        amount: Balance,
+        pool_attribute: Option<PoolAttributes>,
+    ) -> Result<()>;
+
     fn _borrow_allowed(
         &self,
         pool: AccountId,
@@ -132,8 +233,16 @@ pub trait Internal {
         pool_attribute: Option<PoolAttributes>,
     ) -> Result<()>;
 
-    fn _liquidate_borrow_allowed(
+    fn _repay_borrow_allowed(
         &self,
+        pool: AccountId,
+        payer: AccountId,
+        borrower: AccountId,
+        repay_amount: Balance,
+    ) -> Result<()>;
+
+    fn _liquidate_borrow_allowed(
+        &mut self,
         pool_borrowed: AccountId,
         pool_collateral: AccountId,
         liquidator: AccountId,
@@ -160,10 +269,22 @@ pub trait Internal {
         pool_attribute: Option<PoolAttributes>,
     ) -> Result<()>;
 
+    fn _use_reserve_as_collateral_allowed(&self, pool: AccountId, account: AccountId)
+        -> Result<()>;
+
+    /// Checks the borrowing against the restrictions of the isolated collateral of the account, if any
+    fn _isolation_mode_borrow_allowed(
+        &self,
+        pool: AccountId,
+        borrow_amount: Balance,
+        asset_params: &[HypotheticalAccountLiquidityCalculationParam],
+    ) -> Result<()>;
+
     fn _liquidate_calculate_seize_tokens(
         &self,
         pool_borrowed: AccountId,
         pool_collateral: AccountId,
+        borrower: AccountId,
         exchange_rate_mantissa: WrappedU256,
         repay_amount: Balance,
         pool_borrowed_attributes: Option<PoolAttributesForSeizeCalculation>,
@@ -186,34 +307,133 @@ pub trait Internal {
         pool: &AccountId,
         new_collateral_factor_mantissa: WrappedU256,
     ) -> Result<()>;
-    fn _set_mint_guardian_paused(&mut self, pool: &AccountId, paused: bool) -> Result<()>;
-    fn _set_borrow_guardian_paused(&mut self, pool: &AccountId, paused: bool) -> Result<()>;
-    fn _set_seize_guardian_paused(&mut self, paused: bool) -> Result<()>;
-    fn _set_transfer_guardian_paused(&mut self, paused: bool) -> Result<()>;
+    fn _set_actions_paused(&mut self, pool: &AccountId, actions: u8, paused: bool) -> Result<()>;
+    fn _set_global_actions_paused(&mut self, actions: u8, paused: bool) -> Result<()>;
+    fn _set_market_frozen(&mut self, pool: &AccountId, frozen: bool) -> Result<()>;
     fn _set_close_factor_mantissa(&mut self, new_close_factor_mantissa: WrappedU256) -> Result<()>;
     fn _set_liquidation_incentive_mantissa(
         &mut self,
         new_liquidation_incentive_mantissa: WrappedU256,
     ) -> Result<()>;
+    fn _set_market_liquidation_incentive_mantissa(
+        &mut self,
+        pool: &AccountId,
+        new_liquidation_incentive_mantissa: Option<WrappedU256>,
+    ) -> Result<()>;
+    fn _set_protocol_seize_share_mantissa(
+        &mut self,
+        new_protocol_seize_share_mantissa: WrappedU256,
+    ) -> Result<()>;
+    fn _set_market_protocol_seize_share_mantissa(
+        &mut self,
+        pool: &AccountId,
+        new_protocol_seize_share_mantissa: Option<WrappedU256>,
+    ) -> Result<()>;
+    fn _set_liquidation_auction(
+        &mut self,
+        duration: Timestamp,
+        start_incentive_mantissa: WrappedU256,
+    ) -> Result<()>;
+    fn _set_critical_health_factor(
+        &mut self,
+        new_critical_health_factor: WrappedU256,
+    ) -> Result<()>;
+    fn _set_liquidation_dust_threshold(&mut self, new_dust_threshold: Balance) -> Result<()>;
     fn _set_borrow_cap(&mut self, pool: &AccountId, new_cap: Balance) -> Result<()>;
+    fn _set_supply_cap(&mut self, pool: &AccountId, new_cap: Balance) -> Result<()>;
+    fn _set_emode_category(&mut self, id: u8, category: EModeCategory) -> Result<()>;
+    fn _set_market_emode_category(&mut self, pool: &AccountId, id: u8) -> Result<()>;
+    fn _set_isolation_mode(
+        &mut self,
+        pool: &AccountId,
+        isolated: bool,
+        debt_ceiling: Balance,
+    ) -> Result<()>;
+    fn _set_borrowable_in_isolation(&mut self, pool: &AccountId, borrowable: bool) -> Result<()>;
+    fn _deprecate_market(&mut self, pool: &AccountId, ramp_duration: Timestamp) -> Result<()>;
+    fn _remove_market(&mut self, pool: &AccountId) -> Result<()>;
+    fn _shutdown(&mut self) -> Result<()>;
+    fn _set_settlement_price(&mut self, pool: &AccountId, price: u128) -> Result<()>;
     fn _set_manager(&mut self, manager: AccountId) -> Result<()>;
     fn _accept_manager(&mut self) -> Result<()>;
+    fn _set_user_emode(&mut self, account: AccountId, id: u8) -> Result<()>;
+    fn _update_liquidation_auction(
+        &mut self,
+        account: AccountId,
+        pool_attributes: Option<PoolAttributes>,
+    ) -> Result<()>;
+    fn _set_liquidation_auction_started(&mut self, account: AccountId, in_shortfall: bool);
+    fn _update_account_market(
+        &mut self,
+        account: AccountId,
+        pool: AccountId,
+        entered: bool,
+    ) -> Result<()>;
+    fn _update_isolated_debt(
+        &mut self,
+        account: AccountId,
+        pool: AccountId,
+        borrow_balance_prev: Balance,
+        borrow_balance_new: Balance,
+    ) -> Result<()>;
 
     // view function
     fn _markets(&self) -> Vec<AccountId>;
+    fn _account_markets(&self, account: AccountId) -> Vec<AccountId>;
     fn _market_of_underlying(&self, underlying: AccountId) -> Option<AccountId>;
     fn _underlying_of_market(&self, pool: AccountId) -> Option<AccountId>;
     fn _flashloan_gateway(&self) -> Option<AccountId>;
     fn _collateral_factor_mantissa(&self, pool: AccountId) -> Option<WrappedU256>;
     fn _is_listed(&self, pool: AccountId) -> bool;
-    fn _mint_guardian_paused(&self, pool: AccountId) -> Option<bool>;
-    fn _borrow_guardian_paused(&self, pool: AccountId) -> Option<bool>;
-    fn _seize_guardian_paused(&self) -> bool;
-    fn _transfer_guardian_paused(&self) -> bool;
+    fn _paused_actions(&self, pool: AccountId) -> u8;
+    fn _global_paused_actions(&self) -> u8;
+    fn _action_paused(&self, pool: AccountId, action: u8) -> bool;
+    fn _market_frozen(&self, pool: AccountId) -> bool;
     fn _oracle(&self) -> Option<AccountId>;
     fn _close_factor_mantissa(&self) -> WrappedU256;
     fn _liquidation_incentive_mantissa(&self) -> WrappedU256;
+    fn _market_liquidation_incentive_mantissa(&self, pool: AccountId) -> Option<WrappedU256>;
+    fn _protocol_seize_share_mantissa(&self) -> WrappedU256;
+    fn _market_protocol_seize_share_mantissa(&self, pool: AccountId) -> Option<WrappedU256>;
+    fn _liquidation_auction_duration(&self) -> Timestamp;
+    fn _liquidation_auction_start_incentive_mantissa(&self) -> WrappedU256;
+    fn _liquidation_auction_start(&self, account: AccountId) -> Option<Timestamp>;
+    fn _critical_health_factor(&self) -> WrappedU256;
+    fn _liquidation_dust_threshold(&self) -> Balance;
     fn _borrow_cap(&self, pool: AccountId) -> Option<Balance>;
+    fn _supply_cap(&self, pool: AccountId) -> Option<Balance>;
+    fn _emode_category(&self, id: u8) -> Option<EModeCategory>;
+    fn _market_emode_category(&self, pool: AccountId) -> u8;
+    fn _user_emode(&self, account: AccountId) -> u8;
+    fn _isolated(&self, pool: AccountId) -> bool;
+    fn _debt_ceiling(&self, pool: AccountId) -> Balance;
+    fn _isolated_debt(&self, pool: AccountId) -> Balance;
+    /// Isolated pool other than the given one the account supplies to, if any
+    fn _isolated_collateral(
+        &self,
+        account: AccountId,
+        pool: AccountId,
+    ) -> Result<Option<AccountId>>;
+    fn _borrowable_in_isolation(&self, pool: AccountId) -> bool;
+    fn _market_deprecation(&self, pool: AccountId) -> Option<MarketDeprecation>;
+    fn _is_shutdown(&self) -> bool;
+    fn _settlement_price(&self, pool: AccountId) -> Option<u128>;
+    /// Price of the underlying from the oracle, or the frozen one once the protocol is shut down
+    ///
+    /// Fails with `StalePrice` if it is older than the heartbeat of the underlying in the oracle
+    fn _price(&self, oracle: AccountId, underlying: AccountId) -> Result<u128>;
+    /// Price of the underlying of the pool from the oracle, or the frozen one once the protocol is shut down
+    fn _underlying_price(&self, oracle: AccountId, pool: AccountId) -> Result<u128>;
+    /// E-mode category applied to the position of the account in the pool, if any
+    fn _applied_emode_category(&self, account: AccountId, pool: AccountId)
+        -> Option<EModeCategory>;
+    /// Collateral factor, liquidation threshold and price oracle applied to the position of the account in the pool
+    fn _risk_parameters(
+        &self,
+        account: AccountId,
+        pool: AccountId,
+        pool_liquidation_threshold: u128,
+    ) -> Result<(WrappedU256, u128, AccountId)>;
     fn _manager(&self) -> Option<AccountId>;
     fn _pending_manager(&self) -> Option<AccountId>;
     fn _account_assets(
@@ -221,6 +441,11 @@ pub trait Internal {
         account: AccountId,
         token_modify: Option<AccountId>,
     ) -> Result<Vec<AccountId>>;
+    fn _account_snapshot(
+        &self,
+        pool: AccountId,
+        account: AccountId,
+    ) -> Result<(Balance, Balance, U256)>;
     fn _get_account_liquidity(&self, account: AccountId) -> Result<(U256, U256)>;
     fn _get_hypothetical_account_liquidity(
         &self,
@@ -255,12 +480,48 @@ pub trait Internal {
         new: WrappedU256,
     );
     fn _emit_pool_action_paused_event(&self, pool: AccountId, action: String, paused: bool);
+    fn _emit_market_frozen_event(&self, pool: AccountId, frozen: bool);
     fn _emit_action_paused_event(&self, action: String, paused: bool);
     fn _emit_new_price_oracle_event(&self, old: Option<AccountId>, new: Option<AccountId>);
     fn _emit_new_flashloan_gateway_event(&self, _old: Option<AccountId>, _new: Option<AccountId>);
     fn _emit_new_close_factor_event(&self, old: WrappedU256, new: WrappedU256);
     fn _emit_new_liquidation_incentive_event(&self, old: WrappedU256, new: WrappedU256);
+    fn _emit_new_market_liquidation_incentive_event(
+        &self,
+        pool: AccountId,
+        new: Option<WrappedU256>,
+    );
+    fn _emit_new_protocol_seize_share_event(&self, old: WrappedU256, new: WrappedU256);
+    fn _emit_new_market_protocol_seize_share_event(
+        &self,
+        pool: AccountId,
+        new: Option<WrappedU256>,
+    );
+    fn _emit_new_critical_health_factor_event(&self, old: WrappedU256, new: WrappedU256);
+    fn _emit_new_liquidation_dust_threshold_event(&self, old: Balance, new: Balance);
     fn _emit_new_borrow_cap_event(&self, pool: AccountId, new: Balance);
+    fn _emit_new_supply_cap_event(&self, pool: AccountId, new: Balance);
+    fn _emit_emode_category_updated_event(&self, id: u8, category: EModeCategory);
+    fn _emit_market_emode_category_updated_event(&self, pool: AccountId, id: u8);
+    fn _emit_user_emode_set_event(&self, account: AccountId, id: u8);
+    fn _emit_liquidation_auction_updated_event(
+        &self,
+        duration: Timestamp,
+        start_incentive_mantissa: WrappedU256,
+    );
+    fn _emit_liquidation_auction_started_event(&self, account: AccountId, started_at: Timestamp);
+    fn _emit_liquidation_auction_reset_event(&self, account: AccountId);
+    fn _emit_isolation_mode_updated_event(
+        &self,
+        pool: AccountId,
+        isolated: bool,
+        debt_ceiling: Balance,
+    );
+    fn _emit_borrowable_in_isolation_updated_event(&self, pool: AccountId, borrowable: bool);
+    fn _emit_market_deprecated_event(&self, pool: AccountId, ramp_duration: Timestamp);
+    fn _emit_market_removed_event(&self, pool: AccountId);
+    fn _emit_shutdown_event(&self);
+    fn _emit_settlement_price_updated_event(&self, pool: AccountId, price: u128);
     fn _emit_manager_updated_event(&self, old: AccountId, new: AccountId);
 }
 
@@ -270,8 +531,9 @@ impl<T: Storage<Data>> Controller for T {
         pool: AccountId,
         minter: AccountId,
         mint_amount: Balance,
+        pool_attribute: Option<PoolAttributes>,
     ) -> Result<()> {
-        self._mint_allowed(pool, minter, mint_amount)
+        self._mint_allowed(pool, minter, mint_amount, pool_attribute)
     }
 
     default fn redeem_allowed(
@@ -294,8 +556,18 @@ impl<T: Storage<Data>> Controller for T {
         self._borrow_allowed(pool, borrower, borrow_amount, pool_attribute)
     }
 
-    default fn liquidate_borrow_allowed(
+    default fn repay_borrow_allowed(
         &self,
+        pool: AccountId,
+        payer: AccountId,
+        borrower: AccountId,
+        repay_amount: Balance,
+    ) -> Result<()> {
+        self._repay_borrow_allowed(pool, payer, borrower, repay_amount)
+    }
+
+    default fn liquidate_borrow_allowed(
+        &mut self,
         pool_borrowed: AccountId,
         pool_collateral: AccountId,
         liquidator: AccountId,
@@ -341,10 +613,19 @@ impl<T: Storage<Data>> Controller for T {
         self._transfer_allowed(pool, src, dst, transfer_tokens, pool_attribute)
     }
 
+    default fn use_reserve_as_collateral_allowed(
+        &self,
+        pool: AccountId,
+        account: AccountId,
+    ) -> Result<()> {
+        self._use_reserve_as_collateral_allowed(pool, account)
+    }
+
     default fn liquidate_calculate_seize_tokens(
         &self,
         pool_borrowed: AccountId,
         pool_collateral: AccountId,
+        borrower: AccountId,
         exchange_rate_mantissa: WrappedU256,
         repay_amount: Balance,
         pool_borrowed_attributes: Option<PoolAttributesForSeizeCalculation>,
@@ -353,6 +634,7 @@ impl<T: Storage<Data>> Controller for T {
         self._liquidate_calculate_seize_tokens(
             pool_borrowed,
             pool_collateral,
+            borrower,
             exchange_rate_mantissa,
             repay_amount,
             pool_borrowed_attributes,
@@ -407,32 +689,61 @@ impl<T: Storage<Data>> Controller for T {
         Ok(())
     }
 
-    default fn set_mint_guardian_paused(&mut self, pool: AccountId, paused: bool) -> Result<()> {
+    default fn set_actions_paused(
+        &mut self,
+        pool: AccountId,
+        actions: u8,
+        paused: bool,
+    ) -> Result<()> {
         self._assert_manager()?;
-        self._set_mint_guardian_paused(&pool, paused)?;
-        self._emit_pool_action_paused_event(pool, String::from("Mint"), paused);
+        // a deprecated market never accepts new supply or borrows again
+        if !paused
+            && actions & (ACTION_MINT | ACTION_BORROW) != 0
+            && self._market_deprecation(pool).is_some()
+        {
+            return Err(Error::MarketDeprecated)
+        }
+        self._set_actions_paused(&pool, actions, paused)?;
+        for (action, name) in ACTION_NAMES {
+            if actions & action != 0 {
+                self._emit_pool_action_paused_event(pool, String::from(name), paused);
+            }
+        }
         Ok(())
     }
 
-    default fn set_borrow_guardian_paused(&mut self, pool: AccountId, paused: bool) -> Result<()> {
+    default fn set_global_actions_paused(&mut self, actions: u8, paused: bool) -> Result<()> {
         self._assert_manager()?;
-        self._set_borrow_guardian_paused(&pool, paused)?;
-        self._emit_pool_action_paused_event(pool, String::from("Borrow"), paused);
+        self._set_global_actions_paused(actions, paused)?;
+        for (action, name) in ACTION_NAMES {
+            if actions & action != 0 {
+                self._emit_action_paused_event(String::from(name), paused);
+            }
+        }
         Ok(())
     }
 
-    default fn set_seize_guardian_paused(&mut self, paused: bool) -> Result<()> {
+    default fn set_mint_guardian_paused(&mut self, pool: AccountId, paused: bool) -> Result<()> {
+        self.set_actions_paused(pool, ACTION_MINT, paused)
+    }
+
+    default fn set_borrow_guardian_paused(&mut self, pool: AccountId, paused: bool) -> Result<()> {
+        self.set_actions_paused(pool, ACTION_BORROW, paused)
+    }
+
+    default fn set_market_frozen(&mut self, pool: AccountId, frozen: bool) -> Result<()> {
         self._assert_manager()?;
-        self._set_seize_guardian_paused(paused)?;
-        self._emit_action_paused_event(String::from("Seize"), paused);
+        self._set_market_frozen(&pool, frozen)?;
+        self._emit_market_frozen_event(pool, frozen);
         Ok(())
     }
 
+    default fn set_seize_guardian_paused(&mut self, paused: bool) -> Result<()> {
+        self.set_global_actions_paused(ACTION_SEIZE, paused)
+    }
+
     default fn set_transfer_guardian_paused(&mut self, paused: bool) -> Result<()> {
-        self._assert_manager()?;
-        self._set_transfer_guardian_paused(paused)?;
-        self._emit_action_paused_event(String::from("Transfer"), paused);
-        Ok(())
+        self.set_global_actions_paused(ACTION_TRANSFER, paused)
     }
 
     default fn set_close_factor_mantissa(
@@ -457,6 +768,72 @@ impl<T: Storage<Data>> Controller for T {
         Ok(())
     }
 
+    default fn set_market_liquidation_incentive_mantissa(
+        &mut self,
+        pool: AccountId,
+        new_liquidation_incentive_mantissa: Option<WrappedU256>,
+    ) -> Result<()> {
+        self._assert_manager()?;
+        self._set_market_liquidation_incentive_mantissa(&pool, new_liquidation_incentive_mantissa)?;
+        self._emit_new_market_liquidation_incentive_event(pool, new_liquidation_incentive_mantissa);
+        Ok(())
+    }
+
+    default fn set_protocol_seize_share_mantissa(
+        &mut self,
+        new_protocol_seize_share_mantissa: WrappedU256,
+    ) -> Result<()> {
+        self._assert_manager()?;
+        let old = self._protocol_seize_share_mantissa();
+        self._set_protocol_seize_share_mantissa(new_protocol_seize_share_mantissa)?;
+        self._emit_new_protocol_seize_share_event(old, new_protocol_seize_share_mantissa);
+        Ok(())
+    }
+
+    default fn set_market_protocol_seize_share_mantissa(
+        &mut self,
+        pool: AccountId,
+        new_protocol_seize_share_mantissa: Option<WrappedU256>,
+    ) -> Result<()> {
+        self._assert_manager()?;
+        self._set_market_protocol_seize_share_mantissa(&pool, new_protocol_seize_share_mantissa)?;
+        self._emit_new_market_protocol_seize_share_event(pool, new_protocol_seize_share_mantissa);
+        Ok(())
+    }
+
+    default fn set_liquidation_auction(
+        &mut self,
+        duration: Timestamp,
+        start_incentive_mantissa: WrappedU256,
+    ) -> Result<()> {
+        self._assert_manager()?;
+        self._set_liquidation_auction(duration, start_incentive_mantissa)?;
+        self._emit_liquidation_auction_updated_event(duration, start_incentive_mantissa);
+        Ok(())
+    }
+
+    default fn set_critical_health_factor(
+        &mut self,
+        new_critical_health_factor: WrappedU256,
+    ) -> Result<()> {
+        self._assert_manager()?;
+        let old = self._critical_health_factor();
+        self._set_critical_health_factor(new_critical_health_factor)?;
+        self._emit_new_critical_health_factor_event(old, new_critical_health_factor);
+        Ok(())
+    }
+
+    default fn set_liquidation_dust_threshold(
+        &mut self,
+        new_dust_threshold: Balance,
+    ) -> Result<()> {
+        self._assert_manager()?;
+        let old = self._liquidation_dust_threshold();
+        self._set_liquidation_dust_threshold(new_dust_threshold)?;
+        self._emit_new_liquidation_dust_threshold_event(old, new_dust_threshold);
+        Ok(())
+    }
+
     default fn set_borrow_cap(&mut self, pool: AccountId, new_cap: Balance) -> Result<()> {
         self._assert_manager()?;
         self._set_borrow_cap(&pool, new_cap)?;
@@ -464,6 +841,84 @@ impl<T: Storage<Data>> Controller for T {
         Ok(())
     }
 
+    default fn set_supply_cap(&mut self, pool: AccountId, new_cap: Balance) -> Result<()> {
+        self._assert_manager()?;
+        self._set_supply_cap(&pool, new_cap)?;
+        self._emit_new_supply_cap_event(pool, new_cap);
+        Ok(())
+    }
+
+    default fn set_emode_category(&mut self, id: u8, category: EModeCategory) -> Result<()> {
+        self._assert_manager()?;
+        self._set_emode_category(id, category.clone())?;
+        self._emit_emode_category_updated_event(id, category);
+        Ok(())
+    }
+
+    default fn set_market_emode_category(&mut self, pool: AccountId, id: u8) -> Result<()> {
+        self._assert_manager()?;
+        self._set_market_emode_category(&pool, id)?;
+        self._emit_market_emode_category_updated_event(pool, id);
+        Ok(())
+    }
+
+    default fn set_isolation_mode(
+        &mut self,
+        pool: AccountId,
+        isolated: bool,
+        debt_ceiling: Balance,
+    ) -> Result<()> {
+        self._assert_manager()?;
+        self._set_isolation_mode(&pool, isolated, debt_ceiling)?;
+        self._emit_isolation_mode_updated_event(pool, isolated, debt_ceiling);
+        Ok(())
+    }
+
+    default fn set_borrowable_in_isolation(
+        &mut self,
+        pool: AccountId,
+        borrowable: bool,
+    ) -> Result<()> {
+        self._assert_manager()?;
+        self._set_borrowable_in_isolation(&pool, borrowable)?;
+        self._emit_borrowable_in_isolation_updated_event(pool, borrowable);
+        Ok(())
+    }
+
+    default fn deprecate_market(
+        &mut self,
+        pool: AccountId,
+        ramp_duration: Timestamp,
+    ) -> Result<()> {
+        self._assert_manager()?;
+        self._deprecate_market(&pool, ramp_duration)?;
+        self._emit_pool_action_paused_event(pool, String::from("Mint"), true);
+        self._emit_pool_action_paused_event(pool, String::from("Borrow"), true);
+        self._emit_market_deprecated_event(pool, ramp_duration);
+        Ok(())
+    }
+
+    default fn remove_market(&mut self, pool: AccountId) -> Result<()> {
+        self._assert_manager()?;
+        self._remove_market(&pool)?;
+        self._emit_market_removed_event(pool);
+        Ok(())
+    }
+
+    default fn shutdown(&mut self) -> Result<()> {
+        self._assert_manager()?;
+        self._shutdown()?;
+        self._emit_shutdown_event();
+        Ok(())
+    }
+
+    default fn set_settlement_price(&mut self, pool: AccountId, price: u128) -> Result<()> {
+        self._assert_manager()?;
+        self._set_settlement_price(&pool, price)?;
+        self._emit_settlement_price_updated_event(pool, price);
+        Ok(())
+    }
+
     default fn set_manager(&mut self, manager: AccountId) -> Result<()> {
         self._assert_manager()?;
         self._set_manager(manager)?;
@@ -476,10 +931,53 @@ impl<T: Storage<Data>> Controller for T {
         Ok(())
     }
 
+    default fn set_user_emode(&mut self, id: u8) -> Result<()> {
+        if self._is_shutdown() {
+            return Err(Error::ProtocolIsShutdown)
+        }
+        let caller = Self::env().caller();
+        self._set_user_emode(caller, id)?;
+        self._emit_user_emode_set_event(caller, id);
+        Ok(())
+    }
+
+    default fn update_liquidation_auction(
+        &mut self,
+        account: AccountId,
+        pool_attributes: Option<PoolAttributes>,
+    ) -> Result<()> {
+        self._update_liquidation_auction(account, pool_attributes)
+    }
+
+    default fn update_account_market(&mut self, account: AccountId, entered: bool) -> Result<()> {
+        let caller = Self::env().caller();
+        if !self._is_listed(caller) {
+            return Err(Error::CallerIsNotPool)
+        }
+        self._update_account_market(account, caller, entered)
+    }
+
+    default fn update_isolated_debt(
+        &mut self,
+        account: AccountId,
+        borrow_balance_prev: Balance,
+        borrow_balance_new: Balance,
+    ) -> Result<()> {
+        let caller = Self::env().caller();
+        if !self._is_listed(caller) {
+            return Err(Error::CallerIsNotPool)
+        }
+        self._update_isolated_debt(account, caller, borrow_balance_prev, borrow_balance_new)
+    }
+
     default fn markets(&self) -> Vec<AccountId> {
         self._markets()
     }
 
+    default fn account_markets(&self, account: AccountId) -> Vec<AccountId> {
+        self._account_markets(account)
+    }
+
     default fn market_of_underlying(&self, underlying: AccountId) -> Option<AccountId> {
         self._market_of_underlying(underlying)
     }
@@ -496,20 +994,42 @@ impl<T: Storage<Data>> Controller for T {
         self._collateral_factor_mantissa(pool)
     }
 
+    default fn paused_actions(&self, pool: AccountId) -> u8 {
+        self._paused_actions(pool)
+    }
+
+    default fn global_paused_actions(&self) -> u8 {
+        self._global_paused_actions()
+    }
+
+    default fn action_paused(&self, pool: AccountId, action: u8) -> bool {
+        self._action_paused(pool, action)
+    }
+
     default fn mint_guardian_paused(&self, pool: AccountId) -> Option<bool> {
-        self._mint_guardian_paused(pool)
+        if !self._is_listed(pool) {
+            return None
+        }
+        Some(self._action_paused(pool, ACTION_MINT))
     }
 
     default fn borrow_guardian_paused(&self, pool: AccountId) -> Option<bool> {
-        self._borrow_guardian_paused(pool)
+        if !self._is_listed(pool) {
+            return None
+        }
+        Some(self._action_paused(pool, ACTION_BORROW))
+    }
+
+    default fn market_frozen(&self, pool: AccountId) -> bool {
+        self._market_frozen(pool)
     }
 
     default fn seize_guardian_paused(&self) -> bool {
-        self._seize_guardian_paused()
+        self._global_paused_actions() & ACTION_SEIZE != 0
     }
 
     default fn transfer_guardian_paused(&self) -> bool {
-        self._transfer_guardian_paused()
+        self._global_paused_actions() & ACTION_TRANSFER != 0
     }
 
     default fn oracle(&self) -> Option<AccountId> {
@@ -524,27 +1044,106 @@ impl<T: Storage<Data>> Controller for T {
         self._liquidation_incentive_mantissa()
     }
 
-    default fn borrow_cap(&self, pool: AccountId) -> Option<Balance> {
-        self._borrow_cap(pool)
+    default fn market_liquidation_incentive_mantissa(
+        &self,
+        pool: AccountId,
+    ) -> Option<WrappedU256> {
+        self._market_liquidation_incentive_mantissa(pool)
     }
 
-    default fn manager(&self) -> Option<AccountId> {
-        self._manager()
+    default fn protocol_seize_share_mantissa(&self) -> WrappedU256 {
+        self._protocol_seize_share_mantissa()
     }
 
-    default fn pending_manager(&self) -> Option<AccountId> {
-        self._pending_manager()
+    default fn market_protocol_seize_share_mantissa(&self, pool: AccountId) -> Option<WrappedU256> {
+        self._market_protocol_seize_share_mantissa(pool)
     }
 
-    default fn is_listed(&self, pool: AccountId) -> bool {
-        self._is_listed(pool)
+    default fn liquidation_auction_duration(&self) -> Timestamp {
+        self._liquidation_auction_duration()
     }
 
-    default fn account_assets(&self, account: AccountId) -> Result<Vec<AccountId>> {
-        self._account_assets(account, None)
+    default fn liquidation_auction_start_incentive_mantissa(&self) -> WrappedU256 {
+        self._liquidation_auction_start_incentive_mantissa()
     }
 
-    default fn get_account_liquidity(&self, account: AccountId) -> Result<(U256, U256)> {
+    default fn liquidation_auction_start(&self, account: AccountId) -> Option<Timestamp> {
+        self._liquidation_auction_start(account)
+    }
+
+    default fn critical_health_factor(&self) -> WrappedU256 {
+        self._critical_health_factor()
+    }
+
+    default fn liquidation_dust_threshold(&self) -> Balance {
+        self._liquidation_dust_threshold()
+    }
+
+    default fn borrow_cap(&self, pool: AccountId) -> Option<Balance> {
+        self._borrow_cap(pool)
+    }
+
+    default fn supply_cap(&self, pool: AccountId) -> Option<Balance> {
+        self._supply_cap(pool)
+    }
+
+    default fn emode_category(&self, id: u8) -> Option<EModeCategory> {
+        self._emode_category(id)
+    }
+
+    default fn market_emode_category(&self, pool: AccountId) -> u8 {
+        self._market_emode_category(pool)
+    }
+
+    default fn user_emode(&self, account: AccountId) -> u8 {
+        self._user_emode(account)
+    }
+
+    default fn isolated(&self, pool: AccountId) -> bool {
+        self._isolated(pool)
+    }
+
+    default fn debt_ceiling(&self, pool: AccountId) -> Balance {
+        self._debt_ceiling(pool)
+    }
+
+    default fn isolated_debt(&self, pool: AccountId) -> Balance {
+        self._isolated_debt(pool)
+    }
+
+    default fn borrowable_in_isolation(&self, pool: AccountId) -> bool {
+        self._borrowable_in_isolation(pool)
+    }
+
+    default fn market_deprecation(&self, pool: AccountId) -> Option<MarketDeprecation> {
+        self._market_deprecation(pool)
+    }
+
+    default fn is_shutdown(&self) -> bool {
+        self._is_shutdown()
+    }
+
+    default fn settlement_price(&self, pool: AccountId) -> Option<u128> {
+        self._settlement_price(pool)
+    }
+
+    default fn manager(&self) -> Option<AccountId> {
+        self._manager()
+    }
+
+    default fn pending_manager(&self) -> Option<AccountId> {
+        self._pending_manager()
+    }
+
+    default fn is_listed(&self, pool: AccountId) -> bool {
+        self._is_listed(pool)
+    }
+
+    default fn account_assets(&self, account: AccountId) -> Result<Vec<AccountId>> {
+        self._account_assets(account, None)
+    }
+
+    default fn get_account_liquidity(&self, account: AccountId) -> Result<(U256, U256)> {
         self._get_account_liquidity(account)
     }
 
@@ -596,11 +1195,40 @@ impl<T: Storage<Data>> Internal for T {
         &self,
         pool: AccountId,
         _minter: AccountId,
-        _mint_amount: Balance,
+        mint_amount: Balance,
+        pool_attribute: Option<PoolAttributes>,
     ) -> Result<()> {
-        if let Some(true) | None = self._mint_guardian_paused(pool) {
+        if self._is_shutdown() {
+            return Err(Error::ProtocolIsShutdown)
+        }
+        if !self._is_listed(pool) || self._action_paused(pool, ACTION_MINT) {
             return Err(Error::MintIsPaused)
         }
+        if self._market_frozen(pool) {
+            return Err(Error::MarketFrozen)
+        }
+
+        let supply_cap = self._supply_cap(pool).unwrap_or_default();
+        if supply_cap != 0 {
+            let (total_supply, exchange_rate) = if let Some(attrs) = pool_attribute {
+                (attrs.total_supply, WrappedU256::from(attrs.exchange_rate))
+            } else {
+                (
+                    PoolRef::total_supply(&pool),
+                    PoolRef::exchange_rate_stored(&pool),
+                )
+            };
+            let total_supply_in_underlying = underlying_balance(
+                Exp {
+                    mantissa: exchange_rate,
+                },
+                total_supply,
+            );
+            if supply_cap < mint_amount || total_supply_in_underlying > supply_cap - mint_amount {
+                return Err(Error::SupplyCapReached)
+            }
+        }
+
         // FEATURE: update governance token supply index & distribute
 
         Ok(())
@@ -616,6 +1244,23 @@ impl<T: Storage<Data>> Internal for T {
         if !self._is_listed(pool) {
             return Err(Error::MarketNotListed)
         }
+        if self._action_paused(pool, ACTION_REDEEM) {
+            return Err(Error::RedeemIsPaused)
+        }
+
+        self._redeem_liquidity_allowed(pool, redeemer, redeem_amount, pool_attributes)
+    }
+
+    default fn _redeem_liquidity_allowed(
+        &self,
+        pool: AccountId,
+        redeemer: AccountId,
+        redeem_amount: Balance,
+        pool_attributes: Option<PoolAttributes>,
+    ) -> Result<()> {
+        if !self._is_listed(pool) {
+            return Err(Error::MarketNotListed)
+        }
 
         let (
             AccountCollateralData {
@@ -630,6 +1275,11 @@ impl<T: Storage<Data>> Internal for T {
             asset_params,
         ) = self._calculate_user_account_data(redeemer, pool_attributes, Some(pool))?;
 
+        // once shut down, debts are settled first: collateral is only released to accounts without borrows
+        if self._is_shutdown() && !total_debt_in_base_currency.is_zero() {
+            return Err(Error::ShutdownDebtOutstanding)
+        }
+
         // Prepare parameters for calculation
         let (sum_collateral, sum_borrow_plus_effect) =
             get_hypothetical_account_liquidity(GetHypotheticalAccountLiquidityInput {
@@ -677,29 +1327,30 @@ impl<T: Storage<Data>> Internal for T {
             return Err(Error::MarketNotListed)
         }
 
-        if let Some(true) | None = self._borrow_guardian_paused(pool) {
+        if self._is_shutdown() {
+            return Err(Error::ProtocolIsShutdown)
+        }
+        if self._action_paused(pool, ACTION_BORROW) {
             return Err(Error::BorrowIsPaused)
         }
+        if self._market_frozen(pool) {
+            return Err(Error::MarketFrozen)
+        }
+
+        let user_emode = self._user_emode(borrower);
+        if user_emode != 0 && self._market_emode_category(pool) != user_emode {
+            return Err(Error::InconsistentEModeCategory)
+        }
 
         let oracle = self._oracle().ok_or(Error::OracleIsNotSet)?;
-        let (price, total_borrow, pool_attributes) = if let Some(attrs) = pool_attribute {
+        let (total_borrow, pool_attributes) = if let Some(attrs) = pool_attribute {
             let underlying = attrs.underlying.ok_or(Error::UnderlyingIsNotSet)?;
-            (
-                PriceOracleRef::get_price(&oracle, underlying),
-                attrs.total_borrows,
-                Some(attrs),
-            )
+            self._price(oracle, underlying)?;
+            (attrs.total_borrows, Some(attrs))
         } else {
-            (
-                PriceOracleRef::get_underlying_price(&oracle, pool),
-                PoolRef::total_borrows(&pool),
-                None,
-            )
+            self._underlying_price(oracle, pool)?;
+            (PoolRef::total_borrows(&pool), None)
         };
-
-        if let None | Some(0) = price {
-            return Err(Error::PriceError)
-        }
         let borrow_cap = self._borrow_cap(pool).unwrap_or_default();
         if borrow_cap != 0 {
             if borrow_cap < borrow_amount || total_borrow > borrow_cap - borrow_amount {
@@ -707,14 +1358,18 @@ impl<T: Storage<Data>> Internal for T {
             }
         }
 
-        let (_, shortfall) = self._get_hypothetical_account_liquidity(
-            borrower,
-            Some(pool),
-            0,
-            borrow_amount,
-            pool_attributes,
-        )?;
-        if !shortfall.is_zero() {
+        let (_, asset_params) =
+            self._calculate_user_account_data(borrower, pool_attributes, Some(pool))?;
+        self._isolation_mode_borrow_allowed(pool, borrow_amount, &asset_params)?;
+
+        let (sum_collateral, sum_borrow_plus_effect) =
+            get_hypothetical_account_liquidity(GetHypotheticalAccountLiquidityInput {
+                asset_params,
+                token_modify: Some(pool),
+                redeem_tokens: 0,
+                borrow_amount,
+            });
+        if sum_borrow_plus_effect > sum_collateral {
             return Err(Error::InsufficientLiquidity)
         }
 
@@ -723,8 +1378,27 @@ impl<T: Storage<Data>> Internal for T {
         Ok(())
     }
 
-    default fn _liquidate_borrow_allowed(
+    default fn _repay_borrow_allowed(
         &self,
+        pool: AccountId,
+        _payer: AccountId,
+        _borrower: AccountId,
+        _repay_amount: Balance,
+    ) -> Result<()> {
+        if !self._is_listed(pool) {
+            return Err(Error::MarketNotListed)
+        }
+        if self._action_paused(pool, ACTION_REPAY) {
+            return Err(Error::RepayIsPaused)
+        }
+
+        // FEATURE: update governance token borrow index & distribute
+
+        Ok(())
+    }
+
+    default fn _liquidate_borrow_allowed(
+        &mut self,
         pool_borrowed: AccountId,
         pool_collateral: AccountId,
         _liquidator: AccountId,
@@ -735,26 +1409,98 @@ impl<T: Storage<Data>> Internal for T {
         if !self._is_listed(pool_borrowed) || !self._is_listed(pool_collateral) {
             return Err(Error::MarketNotListed)
         }
+        if self._is_shutdown() {
+            return Err(Error::ProtocolIsShutdown)
+        }
+        if self._action_paused(pool_borrowed, ACTION_LIQUIDATE) {
+            return Err(Error::LiquidateIsPaused)
+        }
+        // the auction start is only recorded from the balances given by the pool itself
+        if let Some(attrs) = pool_attribute.as_ref() {
+            if attrs.pool != Some(Self::env().caller()) {
+                return Err(Error::CallerIsNotPool)
+            }
+        }
 
         let (borrow_balance, pool_attributes) = if let Some(attrs) = pool_attribute.clone() {
             (attrs.account_borrow_balance, Some(attrs))
         } else {
             (
-                PoolRef::borrow_balance_stored(&pool_borrowed, borrower),
+                PoolRef::borrow_balance_at(&pool_borrowed, borrower, None)
+                    .map_err(|_| Error::PoolError)?,
                 None,
             )
         };
 
-        // The borrower must have shortfall in order to be liquidatable
-        let (_, shortfall) =
-            self._get_hypothetical_account_liquidity(borrower, None, 0, 0, pool_attributes)?;
-        if shortfall.is_zero() {
+        let (account_data, asset_params) =
+            self._calculate_user_account_data(borrower, pool_attributes, None)?;
+
+        // values of the borrowed and the collateral positions, not weighted by collateral factor
+        let (mut debt_in_base_currency, mut collateral_in_base_currency) =
+            (U256::from(0), U256::from(0));
+        for param in asset_params.iter() {
+            if param.asset != pool_borrowed && param.asset != pool_collateral {
+                continue
+            }
+            let (_, collateral, borrow) = get_hypothetical_account_liquidity_per_asset(
+                param.token_balance,
+                param.borrow_balance,
+                param.decimals,
+                param.exchange_rate_mantissa.clone(),
+                Exp {
+                    mantissa: WrappedU256::from(exp_scale()),
+                },
+                param.oracle_price_mantissa.clone(),
+            );
+            if param.asset == pool_borrowed {
+                debt_in_base_currency = borrow;
+            }
+            if param.asset == pool_collateral {
+                collateral_in_base_currency = collateral;
+            }
+        }
+
+        // The borrower must have shortfall in order to be liquidatable,
+        // unless it borrows from a deprecated market whose collateral factor has ramped down to zero
+        let borrowed_deprecation = self._market_deprecation(pool_borrowed);
+        let deprecation_ended = borrowed_deprecation.as_ref().map_or(false, |deprecation| {
+            Self::env()
+                .block_timestamp()
+                .saturating_sub(deprecation.started_at)
+                >= deprecation.ramp_duration
+        });
+        let (sum_collateral, sum_borrow_plus_effect) =
+            get_hypothetical_account_liquidity(GetHypotheticalAccountLiquidityInput {
+                asset_params,
+                token_modify: None,
+                redeem_tokens: 0,
+                borrow_amount: 0,
+            });
+        let in_shortfall = sum_borrow_plus_effect > sum_collateral;
+        // the auction is reset by `update_liquidation_auction` and the pool hooks
+        if in_shortfall {
+            self._set_liquidation_auction_started(borrower, true);
+        }
+        if !in_shortfall && !deprecation_ended {
             return Err(Error::InsufficientShortfall)
         }
 
-        // The liquidator may not repay more than what is allowed by the closeFactor
+        // The liquidator may not repay more than what is allowed by the closeFactor,
+        // the borrow of a deprecated market can be repaid entirely
+        let close_factor_mantissa = if borrowed_deprecation.is_some() {
+            exp_scale()
+        } else {
+            liquidation_close_factor_mantissa(&LiquidationCloseFactorInput {
+                close_factor_mantissa: self._close_factor_mantissa().into(),
+                health_factor: account_data.health_factor,
+                critical_health_factor: self._critical_health_factor().into(),
+                debt_in_base_currency,
+                collateral_in_base_currency,
+                dust_threshold: U256::from(self._liquidation_dust_threshold()),
+            })
+        };
         let max_close = Exp {
-            mantissa: self._close_factor_mantissa(),
+            mantissa: WrappedU256::from(close_factor_mantissa),
         }
         .mul_scalar_truncate(U256::from(borrow_balance));
         if U256::from(repay_amount).gt(&max_close) {
@@ -772,13 +1518,15 @@ impl<T: Storage<Data>> Internal for T {
         _borrower: AccountId,
         _seize_tokens: Balance,
     ) -> Result<()> {
-        if self._seize_guardian_paused() {
-            return Err(Error::SeizeIsPaused)
-        }
-
         if !self._is_listed(pool_collateral) || !self._is_listed(pool_borrowed) {
             return Err(Error::MarketNotListed)
         }
+        if self._is_shutdown() {
+            return Err(Error::ProtocolIsShutdown)
+        }
+        if self._action_paused(pool_collateral, ACTION_SEIZE) {
+            return Err(Error::SeizeIsPaused)
+        }
 
         // NOTE: cannot perform controller check on the pool here, as a cross-contract call to the caller occurs when the pool is the caller.
         //   To avoid this, the pool itself needs to perform this check.
@@ -801,7 +1549,7 @@ impl<T: Storage<Data>> Internal for T {
         transfer_tokens: Balance,
         pool_attribute: Option<PoolAttributes>,
     ) -> Result<()> {
-        if self._transfer_guardian_paused() {
+        if self._action_paused(pool, ACTION_TRANSFER) {
             return Err(Error::TransferIsPaused)
         }
 
@@ -818,8 +1566,68 @@ impl<T: Storage<Data>> Internal for T {
             transfer_tokens,
         );
 
-        self._redeem_allowed(pool, src, transfer_tokens_in_underlying, pool_attribute)?;
+        // a transfer releases collateral like a redeem, so the same rules apply once shut down
+        self._redeem_liquidity_allowed(pool, src, transfer_tokens_in_underlying, pool_attribute)?;
+
+        Ok(())
+    }
+
+    default fn _use_reserve_as_collateral_allowed(
+        &self,
+        pool: AccountId,
+        account: AccountId,
+    ) -> Result<()> {
+        if !self._is_listed(pool) {
+            return Err(Error::MarketNotListed)
+        }
+
+        // an isolated asset can only be the sole collateral of the account
+        let isolated = self._isolated(pool);
+        for asset in self._account_markets(account) {
+            if asset == pool || (!isolated && !self._isolated(asset)) {
+                continue
+            }
+            let (balance, _, _) = self._account_snapshot(asset, account)?;
+            if balance > 0 {
+                return Err(Error::InconsistentIsolationMode)
+            }
+        }
+        Ok(())
+    }
+
+    default fn _isolation_mode_borrow_allowed(
+        &self,
+        pool: AccountId,
+        borrow_amount: Balance,
+        asset_params: &[HypotheticalAccountLiquidityCalculationParam],
+    ) -> Result<()> {
+        let isolated_collateral = asset_params
+            .iter()
+            .find(|param| param.token_balance > 0 && self._isolated(param.asset));
+        let collateral = match isolated_collateral {
+            Some(param) => param.asset,
+            None => return Ok(()),
+        };
+
+        if !self._borrowable_in_isolation(pool) {
+            return Err(Error::NotBorrowableInIsolation)
+        }
 
+        let debt_ceiling = self._debt_ceiling(collateral);
+        let borrowed_price = asset_params
+            .iter()
+            .find(|param| param.asset == pool)
+            .map(|param| U256::from(param.oracle_price_mantissa.mantissa))
+            .ok_or(Error::PriceError)?;
+        let borrow_amount_in_base_currency = borrowed_price
+            .mul(U256::from(borrow_amount))
+            .div(U256::from(PRICE_PRECISION));
+        // the ceiling applies to the debt of all the accounts using the collateral
+        if U256::from(self._isolated_debt(collateral)).add(borrow_amount_in_base_currency)
+            > U256::from(debt_ceiling)
+        {
+            return Err(Error::DebtCeilingReached)
+        }
         Ok(())
     }
 
@@ -827,47 +1635,73 @@ impl<T: Storage<Data>> Internal for T {
         &self,
         pool_borrowed: AccountId,
         pool_collateral: AccountId,
+        borrower: AccountId,
         exchange_rate_mantissa: WrappedU256,
         repay_amount: Balance,
         pool_borrowed_attributes: Option<PoolAttributesForSeizeCalculation>,
         pool_collateral_attributes: Option<PoolAttributesForSeizeCalculation>,
     ) -> Result<Balance> {
         let oracle = self._oracle().ok_or(Error::OracleIsNotSet)?;
+        let emode_borrowed = self._applied_emode_category(borrower, pool_borrowed);
+        let emode_collateral = self._applied_emode_category(borrower, pool_collateral);
+
+        let oracle_borrowed = emode_borrowed
+            .and_then(|category| category.price_source)
+            .unwrap_or(oracle);
         let (price_borrowed_mantissa, pool_decimals_borrowed) =
             if let Some(attrs) = pool_borrowed_attributes {
                 let underlying = attrs.underlying.ok_or(Error::UnderlyingIsNotSet)?;
-                (
-                    PriceOracleRef::get_price(&oracle, underlying).ok_or(Error::PriceError)?,
-                    attrs.decimals,
-                )
+                (self._price(oracle_borrowed, underlying)?, attrs.decimals)
             } else {
                 (
-                    PriceOracleRef::get_underlying_price(&oracle, pool_borrowed)
-                        .ok_or(Error::PriceError)?,
+                    self._underlying_price(oracle_borrowed, pool_borrowed)?,
                     PoolRef::token_decimals(&pool_borrowed),
                 )
             };
-        if price_borrowed_mantissa == 0 {
-            return Err(Error::PriceError)
-        }
 
+        let (oracle_collateral, liquidation_incentive_mantissa) = match emode_collateral {
+            Some(category) => {
+                (
+                    category.price_source.unwrap_or(oracle),
+                    category.liquidation_incentive_mantissa,
+                )
+            }
+            None => {
+                (
+                    oracle,
+                    self._market_liquidation_incentive_mantissa(pool_collateral)
+                        .unwrap_or(self._liquidation_incentive_mantissa()),
+                )
+            }
+        };
+        // the incentive rises from the start of the auction, which is now if nobody has started it
+        let duration = self._liquidation_auction_duration();
+        let liquidation_incentive_mantissa = if duration == 0 {
+            liquidation_incentive_mantissa
+        } else {
+            let now = Self::env().block_timestamp();
+            let started_at = self._liquidation_auction_start(borrower).unwrap_or(now);
+            WrappedU256::from(liquidation_auction_incentive_mantissa(
+                &LiquidationAuctionIncentiveInput {
+                    start_incentive_mantissa: self
+                        ._liquidation_auction_start_incentive_mantissa()
+                        .into(),
+                    max_incentive_mantissa: liquidation_incentive_mantissa.into(),
+                    elapsed: now.saturating_sub(started_at),
+                    duration,
+                },
+            ))
+        };
         let (price_collateral_mantissa, pool_decimals_collateral) =
             if let Some(attrs) = pool_collateral_attributes {
                 let underlying = attrs.underlying.ok_or(Error::UnderlyingIsNotSet)?;
-                (
-                    PriceOracleRef::get_price(&oracle, underlying).ok_or(Error::PriceError)?,
-                    attrs.decimals,
-                )
+                (self._price(oracle_collateral, underlying)?, attrs.decimals)
             } else {
                 (
-                    PriceOracleRef::get_underlying_price(&oracle, pool_collateral)
-                        .ok_or(Error::PriceError)?,
+                    self._underlying_price(oracle_collateral, pool_collateral)?,
                     PoolRef::token_decimals(&pool_collateral),
                 )
             };
-        if price_collateral_mantissa == 0 {
-            return Err(Error::PriceError)
-        }
 
         let result = liquidate_calculate_seize_tokens(&LiquidateCalculateSeizeTokensInput {
             price_borrowed_mantissa: U256::from(price_borrowed_mantissa),
@@ -875,7 +1709,7 @@ impl<T: Storage<Data>> Internal for T {
             price_collateral_mantissa: U256::from(price_collateral_mantissa),
             decimals_collateral: pool_decimals_collateral,
             exchange_rate_mantissa: exchange_rate_mantissa.into(),
-            liquidation_incentive_mantissa: self._liquidation_incentive_mantissa().into(),
+            liquidation_incentive_mantissa: liquidation_incentive_mantissa.into(),
             actual_repay_amount: repay_amount,
         });
 
@@ -936,12 +1770,12 @@ impl<T: Storage<Data>> Internal for T {
         self.data().market_underlying_pair.insert(pool, underlying);
 
         // set default states
-        self._set_mint_guardian_paused(pool, false)?;
-        self._set_borrow_guardian_paused(pool, false)?;
+        self._set_actions_paused(pool, ACTION_MINT | ACTION_BORROW, false)?;
         if let Some(value) = collateral_factor_mantissa {
             self._set_collateral_factor_mantissa(pool, value)?;
         }
         self._set_borrow_cap(pool, 0)?;
+        self._set_supply_cap(pool, 0)?;
 
         Ok(())
     }
@@ -972,9 +1806,7 @@ impl<T: Storage<Data>> Internal for T {
         }
 
         let oracle = self._oracle().ok_or(Error::OracleIsNotSet)?;
-        if let None | Some(0) = PriceOracleRef::get_underlying_price(&oracle, *pool) {
-            return Err(Error::PriceError)
-        }
+        self._underlying_price(oracle, *pool)?;
 
         self.data()
             .collateral_factor_mantissa
@@ -983,33 +1815,40 @@ impl<T: Storage<Data>> Internal for T {
         Ok(())
     }
 
-    default fn _set_mint_guardian_paused(&mut self, pool: &AccountId, paused: bool) -> Result<()> {
-        if !self._is_listed(*pool) {
-            return Err(Error::MarketNotListed)
-        }
-        self.data().mint_guardian_paused.insert(pool, &paused);
-        Ok(())
-    }
-
-    default fn _set_borrow_guardian_paused(
+    default fn _set_actions_paused(
         &mut self,
         pool: &AccountId,
+        actions: u8,
         paused: bool,
     ) -> Result<()> {
         if !self._is_listed(*pool) {
             return Err(Error::MarketNotListed)
         }
-        self.data().borrow_guardian_paused.insert(pool, &paused);
+        let current = self._paused_actions(*pool);
+        let new = if paused {
+            current | actions
+        } else {
+            current & !actions
+        };
+        self.data().paused_actions.insert(pool, &new);
         Ok(())
     }
 
-    default fn _set_seize_guardian_paused(&mut self, paused: bool) -> Result<()> {
-        self.data().seize_guardian_paused = paused;
+    default fn _set_global_actions_paused(&mut self, actions: u8, paused: bool) -> Result<()> {
+        let current = self._global_paused_actions();
+        self.data().global_paused_actions = if paused {
+            current | actions
+        } else {
+            current & !actions
+        };
         Ok(())
     }
 
-    default fn _set_transfer_guardian_paused(&mut self, paused: bool) -> Result<()> {
-        self.data().transfer_guardian_paused = paused;
+    default fn _set_market_frozen(&mut self, pool: &AccountId, frozen: bool) -> Result<()> {
+        if !self._is_listed(*pool) {
+            return Err(Error::MarketNotListed)
+        }
+        self.data().market_frozen.insert(pool, &frozen);
         Ok(())
     }
 
@@ -1029,37 +1868,449 @@ impl<T: Storage<Data>> Internal for T {
         Ok(())
     }
 
-    default fn _set_borrow_cap(&mut self, pool: &AccountId, new_cap: Balance) -> Result<()> {
+    default fn _set_market_liquidation_incentive_mantissa(
+        &mut self,
+        pool: &AccountId,
+        new_liquidation_incentive_mantissa: Option<WrappedU256>,
+    ) -> Result<()> {
         if !self._is_listed(*pool) {
             return Err(Error::MarketNotListed)
         }
-        self.data().borrow_caps.insert(pool, &new_cap);
+        match new_liquidation_incentive_mantissa {
+            Some(value) => {
+                // the liquidator must not receive less collateral than repaid
+                if U256::from(value) < exp_scale() {
+                    return Err(Error::InvalidLiquidationIncentive)
+                }
+                self.data()
+                    .market_liquidation_incentive_mantissa
+                    .insert(pool, &value);
+            }
+            None => {
+                self.data()
+                    .market_liquidation_incentive_mantissa
+                    .remove(pool)
+            }
+        }
         Ok(())
     }
 
-    default fn _set_manager(&mut self, manager: AccountId) -> Result<()> {
-        self.data().pending_manager = Some(manager);
+    default fn _set_protocol_seize_share_mantissa(
+        &mut self,
+        new_protocol_seize_share_mantissa: WrappedU256,
+    ) -> Result<()> {
+        if U256::from(new_protocol_seize_share_mantissa) > exp_scale() {
+            return Err(Error::InvalidProtocolSeizeShare)
+        }
+        self.data().protocol_seize_share_mantissa = new_protocol_seize_share_mantissa;
         Ok(())
     }
 
-    default fn _accept_manager(&mut self) -> Result<()> {
-        let manager = self._manager().ok_or(Error::ManagerIsNotSet)?;
-        let pending_manager = self
-            ._pending_manager()
-            .ok_or(Error::PendingManagerIsNotSet)?;
-        self.data().manager = Some(pending_manager);
-        self.data().pending_manager = None;
+    default fn _set_market_protocol_seize_share_mantissa(
+        &mut self,
+        pool: &AccountId,
+        new_protocol_seize_share_mantissa: Option<WrappedU256>,
+    ) -> Result<()> {
+        if !self._is_listed(*pool) {
+            return Err(Error::MarketNotListed)
+        }
+        match new_protocol_seize_share_mantissa {
+            Some(value) => {
+                if U256::from(value) > exp_scale() {
+                    return Err(Error::InvalidProtocolSeizeShare)
+                }
+                self.data()
+                    .market_protocol_seize_share_mantissa
+                    .insert(pool, &value);
+            }
+            None => {
+                self.data()
+                    .market_protocol_seize_share_mantissa
+                    .remove(pool)
+            }
+        }
+        Ok(())
+    }
 
-        self._emit_manager_updated_event(manager, pending_manager);
+    default fn _set_liquidation_auction(
+        &mut self,
+        duration: Timestamp,
+        start_incentive_mantissa: WrappedU256,
+    ) -> Result<()> {
+        if duration != 0 && U256::from(start_incentive_mantissa) < exp_scale() {
+            return Err(Error::InvalidLiquidationIncentive)
+        }
+        let mut data = self.data::<Data>();
+        data.liquidation_auction_duration = duration;
+        data.liquidation_auction_start_incentive_mantissa = start_incentive_mantissa;
         Ok(())
     }
 
-    default fn _markets(&self) -> Vec<AccountId> {
-        self.data().markets.clone()
+    default fn _set_critical_health_factor(
+        &mut self,
+        new_critical_health_factor: WrappedU256,
+    ) -> Result<()> {
+        // accounts at or above health factor 1 are not liquidatable
+        if U256::from(new_critical_health_factor) > U256::from(HEALTH_FACTOR_LIQUIDATION_THRESHOLD)
+        {
+            return Err(Error::InvalidCriticalHealthFactor)
+        }
+        self.data().critical_health_factor = new_critical_health_factor;
+        Ok(())
     }
 
-    default fn _market_of_underlying(&self, underlying: AccountId) -> Option<AccountId> {
-        self.data().underlying_market_pair.get(&underlying)
+    default fn _set_liquidation_dust_threshold(
+        &mut self,
+        new_dust_threshold: Balance,
+    ) -> Result<()> {
+        self.data().liquidation_dust_threshold = new_dust_threshold;
+        Ok(())
+    }
+
+    default fn _set_borrow_cap(&mut self, pool: &AccountId, new_cap: Balance) -> Result<()> {
+        if !self._is_listed(*pool) {
+            return Err(Error::MarketNotListed)
+        }
+        self.data().borrow_caps.insert(pool, &new_cap);
+        Ok(())
+    }
+
+    default fn _set_supply_cap(&mut self, pool: &AccountId, new_cap: Balance) -> Result<()> {
+        if !self._is_listed(*pool) {
+            return Err(Error::MarketNotListed)
+        }
+        self.data().supply_caps.insert(pool, &new_cap);
+        Ok(())
+    }
+
+    default fn _set_emode_category(&mut self, id: u8, category: EModeCategory) -> Result<()> {
+        if id == 0 || !emode_category_is_valid(&category) {
+            return Err(Error::InvalidEModeCategory)
+        }
+        self.data().emode_categories.insert(&id, &category);
+        Ok(())
+    }
+
+    default fn _set_market_emode_category(&mut self, pool: &AccountId, id: u8) -> Result<()> {
+        if !self._is_listed(*pool) {
+            return Err(Error::MarketNotListed)
+        }
+        if id != 0 && self._emode_category(id).is_none() {
+            return Err(Error::EModeCategoryNotFound)
+        }
+        self.data().market_emode_category.insert(pool, &id);
+        Ok(())
+    }
+
+    default fn _set_isolation_mode(
+        &mut self,
+        pool: &AccountId,
+        isolated: bool,
+        debt_ceiling: Balance,
+    ) -> Result<()> {
+        if !self._is_listed(*pool) {
+            return Err(Error::MarketNotListed)
+        }
+        // an isolated pool without a ceiling would allow unlimited borrowing against it
+        if isolated && debt_ceiling == 0 {
+            return Err(Error::InvalidDebtCeiling)
+        }
+        self.data().isolated.insert(pool, &isolated);
+        self.data().debt_ceilings.insert(pool, &debt_ceiling);
+        Ok(())
+    }
+
+    default fn _set_borrowable_in_isolation(
+        &mut self,
+        pool: &AccountId,
+        borrowable: bool,
+    ) -> Result<()> {
+        if !self._is_listed(*pool) {
+            return Err(Error::MarketNotListed)
+        }
+        self.data()
+            .borrowable_in_isolation
+            .insert(pool, &borrowable);
+        Ok(())
+    }
+
+    default fn _deprecate_market(
+        &mut self,
+        pool: &AccountId,
+        ramp_duration: Timestamp,
+    ) -> Result<()> {
+        if !self._is_listed(*pool) {
+            return Err(Error::MarketNotListed)
+        }
+        if self._market_deprecation(*pool).is_some() {
+            return Err(Error::MarketAlreadyDeprecated)
+        }
+        self._set_actions_paused(pool, ACTION_MINT | ACTION_BORROW, true)?;
+        self.data().market_deprecation.insert(
+            pool,
+            &MarketDeprecation {
+                started_at: Self::env().block_timestamp(),
+                ramp_duration,
+            },
+        );
+        Ok(())
+    }
+
+    default fn _remove_market(&mut self, pool: &AccountId) -> Result<()> {
+        if self._market_deprecation(*pool).is_none() {
+            return Err(Error::MarketNotDeprecated)
+        }
+        // the dead shares locked by the first mint are never redeemed
+        let dead_shares = PoolRef::balance_of(pool, AccountId::from(DEAD_SHARES_HOLDER));
+        if PoolRef::total_supply(pool) > dead_shares || PoolRef::total_borrows(pool) != 0 {
+            return Err(Error::MarketNotEmpty)
+        }
+
+        let underlying = self._underlying_of_market(*pool);
+        let mut data = self.data();
+        data.markets.retain(|market| market != pool);
+        if let Some(underlying) = underlying {
+            data.underlying_market_pair.remove(&underlying);
+        }
+        data.market_underlying_pair.remove(pool);
+        data.collateral_factor_mantissa.remove(pool);
+        data.paused_actions.remove(pool);
+        data.market_frozen.remove(pool);
+        data.market_liquidation_incentive_mantissa.remove(pool);
+        data.market_protocol_seize_share_mantissa.remove(pool);
+        data.borrow_caps.remove(pool);
+        data.supply_caps.remove(pool);
+        data.market_emode_category.remove(pool);
+        data.isolated.remove(pool);
+        data.debt_ceilings.remove(pool);
+        data.isolated_debts.remove(pool);
+        data.borrowable_in_isolation.remove(pool);
+        data.market_deprecation.remove(pool);
+        Ok(())
+    }
+
+    default fn _shutdown(&mut self) -> Result<()> {
+        if self._is_shutdown() {
+            return Err(Error::ProtocolIsShutdown)
+        }
+        let oracle = self._oracle().ok_or(Error::OracleIsNotSet)?;
+        // only the last good prices are frozen,
+        // the stale or missing ones are left to `set_settlement_price`
+        for pool in self._markets() {
+            let underlying = self
+                ._underlying_of_market(pool)
+                .ok_or(Error::UnderlyingIsNotSet)?;
+            if let Ok(price) = self._price(oracle, underlying) {
+                self.data().settlement_prices.insert(&underlying, &price);
+            }
+        }
+        self.data().shutdown = true;
+        Ok(())
+    }
+
+    default fn _set_settlement_price(&mut self, pool: &AccountId, price: u128) -> Result<()> {
+        if !self._is_shutdown() {
+            return Err(Error::ProtocolIsNotShutdown)
+        }
+        let underlying = self
+            ._underlying_of_market(*pool)
+            .ok_or(Error::MarketNotListed)?;
+        if price == 0 {
+            return Err(Error::PriceError)
+        }
+        self.data().settlement_prices.insert(&underlying, &price);
+        Ok(())
+    }
+
+    default fn _set_manager(&mut self, manager: AccountId) -> Result<()> {
+        self.data().pending_manager = Some(manager);
+        Ok(())
+    }
+
+    default fn _accept_manager(&mut self) -> Result<()> {
+        let manager = self._manager().ok_or(Error::ManagerIsNotSet)?;
+        let pending_manager = self
+            ._pending_manager()
+            .ok_or(Error::PendingManagerIsNotSet)?;
+        self.data().manager = Some(pending_manager);
+        self.data().pending_manager = None;
+
+        self._emit_manager_updated_event(manager, pending_manager);
+        Ok(())
+    }
+
+    default fn _update_liquidation_auction(
+        &mut self,
+        account: AccountId,
+        pool_attributes: Option<PoolAttributes>,
+    ) -> Result<()> {
+        if let Some(attrs) = pool_attributes.as_ref() {
+            let caller = Self::env().caller();
+            if attrs.pool != Some(caller) || !self._is_listed(caller) {
+                return Err(Error::CallerIsNotPool)
+            }
+        }
+
+        let (_, shortfall) =
+            self._get_hypothetical_account_liquidity(account, None, 0, 0, pool_attributes)?;
+        self._set_liquidation_auction_started(account, !shortfall.is_zero());
+        Ok(())
+    }
+
+    default fn _set_liquidation_auction_started(&mut self, account: AccountId, in_shortfall: bool) {
+        match (in_shortfall, self._liquidation_auction_start(account)) {
+            (true, None) => {
+                let now = Self::env().block_timestamp();
+                self.data::<Data>()
+                    .liquidation_auction_start
+                    .insert(&account, &now);
+                self._emit_liquidation_auction_started_event(account, now);
+            }
+            (false, Some(_)) => {
+                self.data::<Data>()
+                    .liquidation_auction_start
+                    .remove(&account);
+                self._emit_liquidation_auction_reset_event(account);
+            }
+            _ => {}
+        }
+    }
+
+    default fn _set_user_emode(&mut self, account: AccountId, id: u8) -> Result<()> {
+        if id != 0 {
+            if self._emode_category(id).is_none() {
+                return Err(Error::EModeCategoryNotFound)
+            }
+            for pool in self._account_markets(account) {
+                if self._market_emode_category(pool) == id {
+                    continue
+                }
+                let (_, borrowed, _) = self._account_snapshot(pool, account)?;
+                if borrowed > 0 {
+                    return Err(Error::InconsistentEModeCategory)
+                }
+            }
+        }
+        self.data().user_emode.insert(&account, &id);
+
+        // the account must stay healthy with the parameters of the new category
+        let (_, shortfall) = self._get_account_liquidity(account)?;
+        if !shortfall.is_zero() {
+            return Err(Error::InsufficientLiquidity)
+        }
+        Ok(())
+    }
+
+    default fn _update_account_market(
+        &mut self,
+        account: AccountId,
+        pool: AccountId,
+        entered: bool,
+    ) -> Result<()> {
+        let mut markets = self._account_markets(account);
+        let position = markets.iter().position(|market| *market == pool);
+        match (entered, position) {
+            (true, None) => markets.push(pool),
+            (false, Some(position)) => {
+                markets.swap_remove(position);
+            }
+            _ => return Ok(()),
+        }
+        if markets.is_empty() {
+            self.data().account_markets.remove(&account);
+        } else {
+            self.data().account_markets.insert(&account, &markets);
+        }
+        Ok(())
+    }
+
+    default fn _update_isolated_debt(
+        &mut self,
+        account: AccountId,
+        pool: AccountId,
+        borrow_balance_prev: Balance,
+        borrow_balance_new: Balance,
+    ) -> Result<()> {
+        let recorded = self.data().account_isolated_debts.get(&(&account, &pool));
+        if borrow_balance_new > borrow_balance_prev {
+            let collateral = match recorded {
+                Some((collateral, _)) => collateral,
+                None => {
+                    match self._isolated_collateral(account, pool)? {
+                        Some(collateral) => collateral,
+                        None => return Ok(()),
+                    }
+                }
+            };
+            // priced as in the debt ceiling check, by the price source of the e-mode category if any
+            let oracle = self._oracle().ok_or(Error::OracleIsNotSet)?;
+            let oracle = self
+                ._applied_emode_category(account, pool)
+                .and_then(|category| category.price_source)
+                .unwrap_or(oracle);
+            let underlying = self
+                ._underlying_of_market(pool)
+                .ok_or(Error::UnderlyingIsNotSet)?;
+            let price = self._price(oracle, underlying)?;
+            let increase = U256::from(price)
+                .mul(U256::from(borrow_balance_new - borrow_balance_prev))
+                .div(U256::from(PRICE_PRECISION))
+                .min(U256::from(Balance::MAX))
+                .as_u128();
+            let account_debt = recorded.map(|(_, debt)| debt).unwrap_or(0);
+            let total_debt = self._isolated_debt(collateral);
+            let mut data = self.data();
+            data.isolated_debts
+                .insert(&collateral, &total_debt.saturating_add(increase));
+            data.account_isolated_debts.insert(
+                &(&account, &pool),
+                &(collateral, account_debt.saturating_add(increase)),
+            );
+            return Ok(())
+        }
+
+        let (collateral, account_debt) = match recorded {
+            Some(recorded) => recorded,
+            None => return Ok(()),
+        };
+        if borrow_balance_new == borrow_balance_prev {
+            return Ok(())
+        }
+        // the debt is released in proportion to the repaid borrow, without the price of the repayment
+        let decrease = if borrow_balance_new == 0 {
+            account_debt
+        } else {
+            U256::from(account_debt)
+                .mul(U256::from(borrow_balance_prev - borrow_balance_new))
+                .div(U256::from(borrow_balance_prev))
+                .as_u128()
+        };
+        let total_debt = self._isolated_debt(collateral);
+        let mut data = self.data();
+        data.isolated_debts
+            .insert(&collateral, &total_debt.saturating_sub(decrease));
+        if account_debt > decrease {
+            data.account_isolated_debts
+                .insert(&(&account, &pool), &(collateral, account_debt - decrease));
+        } else {
+            data.account_isolated_debts.remove(&(&account, &pool));
+        }
+        Ok(())
+    }
+
+    default fn _markets(&self) -> Vec<AccountId> {
+        self.data().markets.clone()
+    }
+
+    default fn _account_markets(&self, account: AccountId) -> Vec<AccountId> {
+        self.data()
+            .account_markets
+            .get(&account)
+            .unwrap_or_default()
+    }
+
+    default fn _market_of_underlying(&self, underlying: AccountId) -> Option<AccountId> {
+        self.data().underlying_market_pair.get(&underlying)
     }
 
     default fn _underlying_of_market(&self, pool: AccountId) -> Option<AccountId> {
@@ -1082,20 +2333,20 @@ impl<T: Storage<Data>> Internal for T {
         self.data().collateral_factor_mantissa.get(&pool)
     }
 
-    default fn _mint_guardian_paused(&self, pool: AccountId) -> Option<bool> {
-        self.data().mint_guardian_paused.get(&pool)
+    default fn _paused_actions(&self, pool: AccountId) -> u8 {
+        self.data().paused_actions.get(&pool).unwrap_or_default()
     }
 
-    default fn _borrow_guardian_paused(&self, pool: AccountId) -> Option<bool> {
-        self.data().borrow_guardian_paused.get(&pool)
+    default fn _global_paused_actions(&self) -> u8 {
+        self.data().global_paused_actions
     }
 
-    default fn _seize_guardian_paused(&self) -> bool {
-        self.data().seize_guardian_paused
+    default fn _action_paused(&self, pool: AccountId, action: u8) -> bool {
+        (self._global_paused_actions() | self._paused_actions(pool)) & action != 0
     }
 
-    default fn _transfer_guardian_paused(&self) -> bool {
-        self.data().transfer_guardian_paused
+    default fn _market_frozen(&self, pool: AccountId) -> bool {
+        self.data().market_frozen.get(&pool).unwrap_or(false)
     }
 
     default fn _oracle(&self) -> Option<AccountId> {
@@ -1110,10 +2361,206 @@ impl<T: Storage<Data>> Internal for T {
         self.data::<Data>().liquidation_incentive_mantissa
     }
 
+    default fn _market_liquidation_incentive_mantissa(
+        &self,
+        pool: AccountId,
+    ) -> Option<WrappedU256> {
+        self.data::<Data>()
+            .market_liquidation_incentive_mantissa
+            .get(&pool)
+    }
+
+    default fn _protocol_seize_share_mantissa(&self) -> WrappedU256 {
+        self.data::<Data>().protocol_seize_share_mantissa
+    }
+
+    default fn _market_protocol_seize_share_mantissa(
+        &self,
+        pool: AccountId,
+    ) -> Option<WrappedU256> {
+        self.data::<Data>()
+            .market_protocol_seize_share_mantissa
+            .get(&pool)
+    }
+
+    default fn _liquidation_auction_duration(&self) -> Timestamp {
+        self.data::<Data>().liquidation_auction_duration
+    }
+
+    default fn _liquidation_auction_start_incentive_mantissa(&self) -> WrappedU256 {
+        self.data::<Data>()
+            .liquidation_auction_start_incentive_mantissa
+    }
+
+    default fn _liquidation_auction_start(&self, account: AccountId) -> Option<Timestamp> {
+        self.data::<Data>().liquidation_auction_start.get(&account)
+    }
+
+    default fn _critical_health_factor(&self) -> WrappedU256 {
+        self.data::<Data>().critical_health_factor
+    }
+
+    default fn _liquidation_dust_threshold(&self) -> Balance {
+        self.data::<Data>().liquidation_dust_threshold
+    }
+
     default fn _borrow_cap(&self, pool: AccountId) -> Option<Balance> {
         self.data().borrow_caps.get(&pool)
     }
 
+    default fn _supply_cap(&self, pool: AccountId) -> Option<Balance> {
+        self.data().supply_caps.get(&pool)
+    }
+
+    default fn _emode_category(&self, id: u8) -> Option<EModeCategory> {
+        self.data().emode_categories.get(&id)
+    }
+
+    default fn _market_emode_category(&self, pool: AccountId) -> u8 {
+        self.data().market_emode_category.get(&pool).unwrap_or(0)
+    }
+
+    default fn _user_emode(&self, account: AccountId) -> u8 {
+        self.data().user_emode.get(&account).unwrap_or(0)
+    }
+
+    default fn _isolated(&self, pool: AccountId) -> bool {
+        self.data().isolated.get(&pool).unwrap_or(false)
+    }
+
+    default fn _debt_ceiling(&self, pool: AccountId) -> Balance {
+        self.data().debt_ceilings.get(&pool).unwrap_or(0)
+    }
+
+    default fn _isolated_debt(&self, pool: AccountId) -> Balance {
+        self.data().isolated_debts.get(&pool).unwrap_or(0)
+    }
+
+    default fn _isolated_collateral(
+        &self,
+        account: AccountId,
+        pool: AccountId,
+    ) -> Result<Option<AccountId>> {
+        for asset in self._account_markets(account) {
+            // NOTE: the calling pool cannot be called back
+            if asset == pool || !self._isolated(asset) {
+                continue
+            }
+            let (balance, _, _) = self._account_snapshot(asset, account)?;
+            if balance > 0 {
+                return Ok(Some(asset))
+            }
+        }
+        Ok(None)
+    }
+
+    default fn _borrowable_in_isolation(&self, pool: AccountId) -> bool {
+        self.data()
+            .borrowable_in_isolation
+            .get(&pool)
+            .unwrap_or(false)
+    }
+
+    default fn _market_deprecation(&self, pool: AccountId) -> Option<MarketDeprecation> {
+        self.data().market_deprecation.get(&pool)
+    }
+
+    default fn _is_shutdown(&self) -> bool {
+        self.data().shutdown
+    }
+
+    default fn _settlement_price(&self, pool: AccountId) -> Option<u128> {
+        if !self._is_shutdown() {
+            return None
+        }
+        let underlying = self._underlying_of_market(pool)?;
+        self.data().settlement_prices.get(&underlying)
+    }
+
+    default fn _price(&self, oracle: AccountId, underlying: AccountId) -> Result<u128> {
+        let price = if self._is_shutdown() {
+            self.data().settlement_prices.get(&underlying)
+        } else {
+            let (price, updated_at) =
+                PriceOracleRef::get_price_data(&oracle, underlying).ok_or(Error::PriceError)?;
+            let heartbeat = PriceOracleRef::get_heartbeat(&oracle, underlying);
+            if heartbeat != 0
+                && Self::env().block_timestamp().saturating_sub(updated_at) > heartbeat
+            {
+                return Err(Error::StalePrice)
+            }
+            Some(price)
+        };
+        match price {
+            None | Some(0) => Err(Error::PriceError),
+            Some(price) => Ok(price),
+        }
+    }
+
+    default fn _underlying_price(&self, oracle: AccountId, pool: AccountId) -> Result<u128> {
+        let underlying = self
+            ._underlying_of_market(pool)
+            .ok_or(Error::UnderlyingIsNotSet)?;
+        self._price(oracle, underlying)
+    }
+
+    default fn _applied_emode_category(
+        &self,
+        account: AccountId,
+        pool: AccountId,
+    ) -> Option<EModeCategory> {
+        let id = self._user_emode(account);
+        if id == 0 || self._market_emode_category(pool) != id {
+            return None
+        }
+        self._emode_category(id)
+    }
+
+    default fn _risk_parameters(
+        &self,
+        account: AccountId,
+        pool: AccountId,
+        pool_liquidation_threshold: u128,
+    ) -> Result<(WrappedU256, u128, AccountId)> {
+        let oracle = self._oracle().ok_or(Error::OracleIsNotSet)?;
+        let (collateral_factor_mantissa, liquidation_threshold, price_oracle) =
+            if let Some(category) = self._applied_emode_category(account, pool) {
+                (
+                    category.collateral_factor_mantissa,
+                    category.liquidation_threshold,
+                    category.price_source.unwrap_or(oracle),
+                )
+            } else {
+                (
+                    self._collateral_factor_mantissa(pool)
+                        .ok_or(Error::InvalidCollateralFactor)?,
+                    pool_liquidation_threshold,
+                    oracle,
+                )
+            };
+
+        // the collateral factor of a deprecated market ramps down to zero
+        let collateral_factor_mantissa = match self._market_deprecation(pool) {
+            Some(deprecation) => {
+                WrappedU256::from(deprecated_collateral_factor_mantissa(
+                    &DeprecatedCollateralFactorInput {
+                        collateral_factor_mantissa: collateral_factor_mantissa.into(),
+                        elapsed: Self::env()
+                            .block_timestamp()
+                            .saturating_sub(deprecation.started_at),
+                        ramp_duration: deprecation.ramp_duration,
+                    },
+                ))
+            }
+            None => collateral_factor_mantissa,
+        };
+        Ok((
+            collateral_factor_mantissa,
+            liquidation_threshold,
+            price_oracle,
+        ))
+    }
+
     default fn _manager(&self) -> Option<AccountId> {
         self.data().manager
     }
@@ -1127,31 +2574,32 @@ impl<T: Storage<Data>> Internal for T {
         account: AccountId,
         token_modify: Option<AccountId>,
     ) -> Result<Vec<AccountId>> {
-        let mut account_assets = Vec::<AccountId>::new();
-        for pool in self._markets() {
-            if pool == Self::env().caller() {
-                continue // NOTE: if caller is pool, need to check by the pool itself
-            }
-            if token_modify.is_some() && pool == token_modify.unwrap() {
-                account_assets.push(pool); // NOTE: add unconditionally even if balance, borrowed is not already there
-                continue
-            }
-
-            let account_snapshot = PoolRef::get_account_snapshot(&pool, account);
-            if account_snapshot.is_err() {
-                return Err(Error::PoolError)
-            }
-
-            let (balance, borrowed, _) = account_snapshot.unwrap();
-
-            // whether deposits or loans exist
-            if balance > 0 || borrowed > 0 {
+        let caller = Self::env().caller();
+        // NOTE: if caller is pool, need to check by the pool itself
+        let mut account_assets: Vec<AccountId> = self
+            ._account_markets(account)
+            .into_iter()
+            .filter(|pool| *pool != caller)
+            .collect();
+        if let Some(pool) = token_modify {
+            // NOTE: add unconditionally even if balance, borrowed is not already there
+            if pool != caller && !account_assets.contains(&pool) {
                 account_assets.push(pool);
             }
         }
         return Ok(account_assets)
     }
 
+    default fn _account_snapshot(
+        &self,
+        pool: AccountId,
+        account: AccountId,
+    ) -> Result<(Balance, Balance, U256)> {
+        // NOTE: project interest of the pool to the current block, as it may not have been accrued for a while
+        PoolRef::get_account_snapshot_at(&pool, account, Some(Self::env().block_timestamp()))
+            .map_err(|_| Error::PoolError)
+    }
+
     default fn _get_account_liquidity(&self, account: AccountId) -> Result<(U256, U256)> {
         self._get_hypothetical_account_liquidity(account, None, 0, 0, None)
     }
@@ -1194,7 +2642,7 @@ impl<T: Storage<Data>> Internal for T {
         AccountCollateralData,
         Vec<HypotheticalAccountLiquidityCalculationParam>,
     )> {
-        let oracle = self._oracle().ok_or(Error::OracleIsNotSet)?;
+        self._oracle().ok_or(Error::OracleIsNotSet)?;
         let caller = Self::env().caller();
 
         let mut total_collateral_in_base_currency = U256::from(0);
@@ -1211,16 +2659,11 @@ impl<T: Storage<Data>> Internal for T {
             let attr_underlying = pool_attribute.underlying.ok_or(Error::UnderlyingIsNotSet)?;
             let attr_pool = pool_attribute.pool.ok_or(Error::PoolIsNotSet)?;
 
-            let collateral_factor_mantissa = self
-                ._collateral_factor_mantissa(attr_pool)
-                .ok_or(Error::InvalidCollateralFactor)?;
+            let (collateral_factor_mantissa, liquidation_threshold, price_oracle) =
+                self._risk_parameters(account, attr_pool, pool_attribute.liquidation_threshold)?;
             let ltv = U256::from(collateral_factor_mantissa);
 
-            let oracle_price: u128 =
-                PriceOracleRef::get_price(&oracle, attr_underlying).ok_or(Error::PriceError)?;
-            if oracle_price == 0 {
-                return Err(Error::PriceError)
-            }
+            let oracle_price: u128 = self._price(price_oracle, attr_underlying)?;
             let oracle_price_mantissa = Exp {
                 mantissa: WrappedU256::from(U256::from(oracle_price)),
             };
@@ -1247,9 +2690,8 @@ impl<T: Storage<Data>> Internal for T {
                 total_collateral_in_base_currency =
                     total_collateral_in_base_currency.add(liquidity_balance_eth);
                 avg_ltv = avg_ltv.add(liquidity_balance_eth.mul(U256::from(ltv)));
-                avg_liquidation_threshold = avg_liquidation_threshold.add(
-                    liquidity_balance_eth.mul(U256::from(pool_attribute.liquidation_threshold)),
-                );
+                avg_liquidation_threshold = avg_liquidation_threshold
+                    .add(liquidity_balance_eth.mul(U256::from(liquidation_threshold)));
             }
 
             let borrow_balance_stored = pool_attribute.account_borrow_balance;
@@ -1260,28 +2702,25 @@ impl<T: Storage<Data>> Internal for T {
                 total_debt_in_base_currency = total_debt_in_base_currency.add(borrow_balance_eth);
             }
 
-            (
-                oracle_price,
-                pool_attribute.liquidation_threshold,
-                attr_pool,
-            )
+            (oracle_price, liquidation_threshold, attr_pool)
         } else {
             (0, 0, caller)
         };
 
-        // NOTE: Do not use account_assets as it makes doubled cross-contract calling leads to high gas.
-        for asset in self._markets() {
+        // only the markets where the account has a balance or debt, and the one being modified
+        let mut assets = self._account_markets(account);
+        if let Some(pool) = token_modify {
+            if !assets.contains(&pool) && self._is_listed(pool) {
+                assets.push(pool);
+            }
+        }
+        for asset in assets {
             if asset == skip_pool {
                 continue
             }
-            // Read the balances and exchange rate from the pool
-            let account_snapshot = PoolRef::get_account_snapshot(&asset, account);
-            if account_snapshot.is_err() {
-                return Err(Error::PoolError)
-            }
-
+            // Read the balances and exchange rate from the pool with interest up to now
             let (compounded_liquidity_balance, borrow_balance_stored, exchange_rate_mantissa) =
-                account_snapshot.unwrap();
+                self._account_snapshot(asset, account)?;
 
             // If user didn't make any action.
             if compounded_liquidity_balance == 0 && borrow_balance_stored == 0 {
@@ -1298,20 +2737,14 @@ impl<T: Storage<Data>> Internal for T {
                 underlying,
             } = PoolRef::metadata(&asset);
             let pool_underlying = underlying.ok_or(Error::UnderlyingIsNotSet)?;
+            let (collateral_factor_mantissa, liquidation_threshold, price_oracle) =
+                self._risk_parameters(account, asset, liquidation_threshold)?;
             // Get the normalized price of the asset
-            let oracle_price: u128 =
-                PriceOracleRef::get_price(&oracle, pool_underlying).ok_or(Error::PriceError)?;
-            if oracle_price == 0 {
-                return Err(Error::PriceError)
-            }
+            let oracle_price: u128 = self._price(price_oracle, pool_underlying)?;
             let oracle_price_mantissa = Exp {
                 mantissa: WrappedU256::from(U256::from(oracle_price)),
             };
 
-            let collateral_factor_mantissa = self
-                ._collateral_factor_mantissa(asset)
-                .ok_or(Error::InvalidCollateralFactor)?;
-
             // Store data for input to calculate the available capacity
             asset_params.push(HypotheticalAccountLiquidityCalculationParam {
                 asset,
@@ -1384,7 +2817,9 @@ impl<T: Storage<Data>> Internal for T {
         account: AccountId,
         amount: Balance,
     ) -> Result<()> {
-        let oracle = self._oracle().ok_or(Error::OracleIsNotSet)?;
+        let pool = pool_attributes.pool.ok_or(Error::PoolIsNotSet)?;
+        let (_, liquidation_threshold, price_oracle) =
+            self._risk_parameters(account, pool, pool_attributes.liquidation_threshold)?;
 
         let (account_data, _) =
             self._calculate_user_account_data(account, Some(pool_attributes.clone()), None)?;
@@ -1399,11 +2834,7 @@ impl<T: Storage<Data>> Internal for T {
             .underlying
             .ok_or(Error::UnderlyingIsNotSet)?;
 
-        let asset_price: u128 =
-            PriceOracleRef::get_price(&oracle, underlying).ok_or(Error::PriceError)?;
-        if asset_price == 0 {
-            return Err(Error::PriceError)
-        }
+        let asset_price: u128 = self._price(price_oracle, underlying)?;
 
         let result = balance_decrease_allowed(BalanceDecreaseAllowedParam {
             total_collateral_in_base_currency: account_data.total_collateral_in_base_currency,
@@ -1411,7 +2842,7 @@ impl<T: Storage<Data>> Internal for T {
             avg_liquidation_threshold: account_data.avg_liquidation_threshold,
             amount_in_base_currency_unit: amount.into(),
             asset_price: asset_price.into(),
-            liquidation_threshold: pool_attributes.liquidation_threshold.into(),
+            liquidation_threshold: liquidation_threshold.into(),
         });
         if result {
             return Ok(())
@@ -1437,6 +2868,8 @@ impl<T: Storage<Data>> Internal for T {
     ) {
     }
 
+    default fn _emit_market_frozen_event(&self, _pool: AccountId, _frozen: bool) {}
+
     default fn _emit_action_paused_event(&self, _action: String, _paused: bool) {}
 
     default fn _emit_new_price_oracle_event(
@@ -1457,7 +2890,75 @@ impl<T: Storage<Data>> Internal for T {
 
     default fn _emit_new_liquidation_incentive_event(&self, _old: WrappedU256, _new: WrappedU256) {}
 
+    default fn _emit_new_market_liquidation_incentive_event(
+        &self,
+        _pool: AccountId,
+        _new: Option<WrappedU256>,
+    ) {
+    }
+
+    default fn _emit_new_protocol_seize_share_event(&self, _old: WrappedU256, _new: WrappedU256) {}
+
+    default fn _emit_new_market_protocol_seize_share_event(
+        &self,
+        _pool: AccountId,
+        _new: Option<WrappedU256>,
+    ) {
+    }
+
+    default fn _emit_new_critical_health_factor_event(&self, _old: WrappedU256, _new: WrappedU256) {
+    }
+
+    default fn _emit_new_liquidation_dust_threshold_event(&self, _old: Balance, _new: Balance) {}
+
     default fn _emit_new_borrow_cap_event(&self, _pool: AccountId, _new: Balance) {}
 
+    default fn _emit_new_supply_cap_event(&self, _pool: AccountId, _new: Balance) {}
+
+    default fn _emit_emode_category_updated_event(&self, _id: u8, _category: EModeCategory) {}
+
+    default fn _emit_market_emode_category_updated_event(&self, _pool: AccountId, _id: u8) {}
+
+    default fn _emit_user_emode_set_event(&self, _account: AccountId, _id: u8) {}
+
+    default fn _emit_liquidation_auction_updated_event(
+        &self,
+        _duration: Timestamp,
+        _start_incentive_mantissa: WrappedU256,
+    ) {
+    }
+
+    default fn _emit_liquidation_auction_started_event(
+        &self,
+        _account: AccountId,
+        _started_at: Timestamp,
+    ) {
+    }
+
+    default fn _emit_liquidation_auction_reset_event(&self, _account: AccountId) {}
+
+    default fn _emit_isolation_mode_updated_event(
+        &self,
+        _pool: AccountId,
+        _isolated: bool,
+        _debt_ceiling: Balance,
+    ) {
+    }
+
+    default fn _emit_borrowable_in_isolation_updated_event(
+        &self,
+        _pool: AccountId,
+        _borrowable: bool,
+    ) {
+    }
+
+    default fn _emit_market_deprecated_event(&self, _pool: AccountId, _ramp_duration: Timestamp) {}
+
+    default fn _emit_market_removed_event(&self, _pool: AccountId) {}
+
+    default fn _emit_shutdown_event(&self) {}
+
+    default fn _emit_settlement_price_updated_event(&self, _pool: AccountId, _price: u128) {}
+
     default fn _emit_manager_updated_event(&self, _old: AccountId, _new: AccountId) {}
 }
@@ -6,6 +6,10 @@ use crate::{
             Exp,
         },
         percent_math::Percent,
+        pool::{
+            COLLATERAL_FACTOR_MANTISSA_DECIMALS,
+            LIQUIDATION_THRESHOLD_DECIMALS,
+        },
         price_oracle::PRICE_PRECISION,
         wad_ray_math::Wad,
     },
@@ -102,12 +106,96 @@ pub fn balance_decrease_allowed(param: BalanceDecreaseAllowedParam) -> bool {
     health_factor_after_decrease >= U256::from(HEALTH_FACTOR_LIQUIDATION_THRESHOLD)
 }
 
+pub struct LiquidationCloseFactorInput {
+    pub close_factor_mantissa: U256,
+    pub health_factor: U256,
+    pub critical_health_factor: U256,
+    pub debt_in_base_currency: U256,
+    pub collateral_in_base_currency: U256,
+    pub dust_threshold: U256,
+}
+
+/// Close factor applied to a liquidation.
+/// The position can be fully closed if the account is below the critical health factor or the position is dust.
+pub fn liquidation_close_factor_mantissa(input: &LiquidationCloseFactorInput) -> U256 {
+    if input.health_factor < input.critical_health_factor {
+        return exp_scale()
+    }
+    if input.debt_in_base_currency < input.dust_threshold
+        || input.collateral_in_base_currency < input.dust_threshold
+    {
+        return exp_scale()
+    }
+    input.close_factor_mantissa
+}
+
+pub struct LiquidationAuctionIncentiveInput {
+    pub start_incentive_mantissa: U256,
+    pub max_incentive_mantissa: U256,
+    pub elapsed: u64,
+    pub duration: u64,
+}
+
+/// Liquidation Incentive rising linearly from the start to the maximum over the duration of the auction
+pub fn liquidation_auction_incentive_mantissa(input: &LiquidationAuctionIncentiveInput) -> U256 {
+    if input.elapsed >= input.duration
+        || input.start_incentive_mantissa >= input.max_incentive_mantissa
+    {
+        return input.max_incentive_mantissa
+    }
+    input.start_incentive_mantissa.add(
+        input
+            .max_incentive_mantissa
+            .sub(input.start_incentive_mantissa)
+            .mul(U256::from(input.elapsed))
+            .div(U256::from(input.duration)),
+    )
+}
+
+pub struct DeprecatedCollateralFactorInput {
+    pub collateral_factor_mantissa: U256,
+    pub elapsed: u64,
+    pub ramp_duration: u64,
+}
+
+/// Collateral Factor of a deprecated market, decreasing linearly to zero over the ramp duration
+pub fn deprecated_collateral_factor_mantissa(input: &DeprecatedCollateralFactorInput) -> U256 {
+    if input.elapsed >= input.ramp_duration {
+        return U256::zero()
+    }
+    input
+        .collateral_factor_mantissa
+        .mul(U256::from(input.ramp_duration - input.elapsed))
+        .div(U256::from(input.ramp_duration))
+}
+
 /// Maximum value of Collateral Factor
 pub fn collateral_factor_max_mantissa() -> U256 {
     // 90%
     exp_scale().mul(U256::from(90)).div(U256::from(100))
 }
 
+/// Check the parameters of an e-mode category are consistent with each other
+pub fn emode_category_is_valid(category: &EModeCategory) -> bool {
+    let collateral_factor = U256::from(category.collateral_factor_mantissa);
+    let liquidation_threshold = U256::from(category.liquidation_threshold).mul(U256::from(
+        10_u128.pow(COLLATERAL_FACTOR_MANTISSA_DECIMALS - LIQUIDATION_THRESHOLD_DECIMALS),
+    ));
+    let liquidation_incentive = U256::from(category.liquidation_incentive_mantissa);
+
+    if collateral_factor.is_zero() || collateral_factor >= liquidation_threshold {
+        return false
+    }
+    if liquidation_threshold > exp_scale() || liquidation_incentive < exp_scale() {
+        return false
+    }
+    // collateral seized on liquidation must not exceed the collateral covering the debt
+    liquidation_threshold
+        .mul(liquidation_incentive)
+        .div(exp_scale())
+        <= exp_scale()
+}
+
 #[derive(Debug)]
 pub struct GetHypotheticalAccountLiquidityInput {
     pub asset_params: Vec<HypotheticalAccountLiquidityCalculationParam>,
@@ -690,6 +778,89 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_hypothetical_account_liquidity_with_accrued_interest() {
+        use crate::impls::pool::utils::{
+            calculate_interest,
+            CalculateInterestInput,
+        };
+
+        let mantissa = 10_u128.pow(18);
+        let pow10_18 = 10_u128.pow(18);
+        let to_exp = |val: u128| {
+            Exp {
+                mantissa: WrappedU256::from(U256::from(val)),
+            }
+        };
+        let principal = 10_000 * pow10_18;
+        // borrow balance of the account after the interest of the pool is accrued for delta
+        let borrow_balance_after = |delta: u64| {
+            let out = calculate_interest(&CalculateInterestInput {
+                total_borrows: principal,
+                total_stable_borrows: 0,
+                total_reserves: 0,
+                borrow_index: exp_scale(),
+                borrow_rate: U256::from(10_u128.pow(9)), // 0.0000001% / msec
+                average_stable_borrow_rate: U256::zero(),
+                old_block_timestamp: 0,
+                new_block_timestamp: delta,
+                reserve_factor_mantissa: U256::zero(),
+            })
+            .unwrap();
+            U256::from(principal)
+                .mul(out.borrow_index)
+                .div(exp_scale())
+                .as_u128()
+        };
+        let liquidity = |borrow_balance: Balance| {
+            get_hypothetical_account_liquidity(GetHypotheticalAccountLiquidityInput {
+                asset_params: vec![
+                    HypotheticalAccountLiquidityCalculationParam {
+                        asset: AccountId::from([1; 32]),
+                        decimals: 18,
+                        token_balance: 20_000 * pow10_18,
+                        borrow_balance: 0,
+                        exchange_rate_mantissa: to_exp(mantissa),
+                        collateral_factor_mantissa: to_exp(mantissa * 90 / 100), // 90%
+                        oracle_price_mantissa: to_exp(mantissa),
+                    },
+                    HypotheticalAccountLiquidityCalculationParam {
+                        asset: AccountId::from([2; 32]),
+                        decimals: 18,
+                        token_balance: 0,
+                        borrow_balance,
+                        exchange_rate_mantissa: to_exp(mantissa),
+                        collateral_factor_mantissa: to_exp(mantissa * 90 / 100), // 90%
+                        oracle_price_mantissa: to_exp(mantissa),
+                    },
+                ],
+                token_modify: None,
+                redeem_tokens: 0,
+                borrow_amount: 0,
+            })
+        };
+
+        let stale = liquidity(principal);
+
+        // agree when the pool has been accrued at the current timestamp
+        assert_eq!(borrow_balance_after(0), principal);
+        assert_eq!(liquidity(borrow_balance_after(0)), stale);
+
+        // drift grows with the time since the last accrual
+        let mut last_sum_borrow_plus_effect = stale.1;
+        for delta in [60 * 1000, 60 * 60 * 1000, 24 * 60 * 60 * 1000] {
+            let borrow_balance = borrow_balance_after(delta);
+            let (sum_collateral, sum_borrow_plus_effect) = liquidity(borrow_balance);
+            assert_eq!(sum_collateral, stale.0);
+            assert_eq!(
+                sum_borrow_plus_effect.sub(stale.1),
+                U256::from(borrow_balance - principal)
+            );
+            assert!(sum_borrow_plus_effect > last_sum_borrow_plus_effect);
+            last_sum_borrow_plus_effect = sum_borrow_plus_effect;
+        }
+    }
+
     #[test]
     fn test_calculate_health_factor_from_balances() {
         struct Case {
@@ -822,4 +993,269 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_liquidation_close_factor_mantissa() {
+        struct Case {
+            input: LiquidationCloseFactorInput,
+            expected: U256,
+            name: &'static str,
+        }
+        let percent = |val: u128| exp_scale().mul(U256::from(val)).div(U256::from(100));
+        let cases = vec![
+            Case {
+                name: "just below health factor 1",
+                input: LiquidationCloseFactorInput {
+                    close_factor_mantissa: percent(50),
+                    health_factor: percent(99),
+                    critical_health_factor: percent(95),
+                    debt_in_base_currency: exp_scale().mul(U256::from(1000)),
+                    collateral_in_base_currency: exp_scale().mul(U256::from(1000)),
+                    dust_threshold: exp_scale(),
+                },
+                expected: percent(50),
+            },
+            Case {
+                name: "below critical health factor",
+                input: LiquidationCloseFactorInput {
+                    close_factor_mantissa: percent(50),
+                    health_factor: percent(94),
+                    critical_health_factor: percent(95),
+                    debt_in_base_currency: exp_scale().mul(U256::from(1000)),
+                    collateral_in_base_currency: exp_scale().mul(U256::from(1000)),
+                    dust_threshold: exp_scale(),
+                },
+                expected: exp_scale(),
+            },
+            Case {
+                name: "critical health factor is not set",
+                input: LiquidationCloseFactorInput {
+                    close_factor_mantissa: percent(50),
+                    health_factor: 0.into(),
+                    critical_health_factor: 0.into(),
+                    debt_in_base_currency: exp_scale().mul(U256::from(1000)),
+                    collateral_in_base_currency: exp_scale().mul(U256::from(1000)),
+                    dust_threshold: 0.into(),
+                },
+                expected: percent(50),
+            },
+            Case {
+                name: "debt is dust",
+                input: LiquidationCloseFactorInput {
+                    close_factor_mantissa: percent(50),
+                    health_factor: percent(99),
+                    critical_health_factor: percent(95),
+                    debt_in_base_currency: percent(99),
+                    collateral_in_base_currency: exp_scale().mul(U256::from(1000)),
+                    dust_threshold: exp_scale(),
+                },
+                expected: exp_scale(),
+            },
+            Case {
+                name: "collateral is dust",
+                input: LiquidationCloseFactorInput {
+                    close_factor_mantissa: percent(50),
+                    health_factor: percent(99),
+                    critical_health_factor: percent(95),
+                    debt_in_base_currency: exp_scale().mul(U256::from(1000)),
+                    collateral_in_base_currency: percent(99),
+                    dust_threshold: exp_scale(),
+                },
+                expected: exp_scale(),
+            },
+        ];
+        for case in cases {
+            let got = liquidation_close_factor_mantissa(&case.input);
+            assert_eq!(got, case.expected, "{}", case.name);
+        }
+    }
+
+    #[test]
+    fn test_liquidation_auction_incentive_mantissa() {
+        struct Case {
+            input: LiquidationAuctionIncentiveInput,
+            expected: U256,
+            name: &'static str,
+        }
+        let percent = |val: u128| exp_scale().mul(U256::from(val)).div(U256::from(100));
+        let cases = vec![
+            Case {
+                name: "auction just started",
+                input: LiquidationAuctionIncentiveInput {
+                    start_incentive_mantissa: percent(100),
+                    max_incentive_mantissa: percent(110),
+                    elapsed: 0,
+                    duration: 1000,
+                },
+                expected: percent(100),
+            },
+            Case {
+                name: "half of the duration elapsed",
+                input: LiquidationAuctionIncentiveInput {
+                    start_incentive_mantissa: percent(100),
+                    max_incentive_mantissa: percent(110),
+                    elapsed: 500,
+                    duration: 1000,
+                },
+                expected: percent(105),
+            },
+            Case {
+                name: "auction ended",
+                input: LiquidationAuctionIncentiveInput {
+                    start_incentive_mantissa: percent(100),
+                    max_incentive_mantissa: percent(110),
+                    elapsed: 1500,
+                    duration: 1000,
+                },
+                expected: percent(110),
+            },
+            Case {
+                name: "start is above maximum",
+                input: LiquidationAuctionIncentiveInput {
+                    start_incentive_mantissa: percent(112),
+                    max_incentive_mantissa: percent(110),
+                    elapsed: 0,
+                    duration: 1000,
+                },
+                expected: percent(110),
+            },
+        ];
+        for case in cases {
+            let got = liquidation_auction_incentive_mantissa(&case.input);
+            assert_eq!(got, case.expected, "{}", case.name);
+        }
+    }
+
+    #[test]
+    fn test_deprecated_collateral_factor_mantissa() {
+        struct Case {
+            input: DeprecatedCollateralFactorInput,
+            expected: U256,
+            name: &'static str,
+        }
+        let percent = |val: u128| exp_scale().mul(U256::from(val)).div(U256::from(100));
+        let cases = vec![
+            Case {
+                name: "ramp just started",
+                input: DeprecatedCollateralFactorInput {
+                    collateral_factor_mantissa: percent(80),
+                    elapsed: 0,
+                    ramp_duration: 1000,
+                },
+                expected: percent(80),
+            },
+            Case {
+                name: "a quarter of the ramp elapsed",
+                input: DeprecatedCollateralFactorInput {
+                    collateral_factor_mantissa: percent(80),
+                    elapsed: 250,
+                    ramp_duration: 1000,
+                },
+                expected: percent(60),
+            },
+            Case {
+                name: "ramp ended",
+                input: DeprecatedCollateralFactorInput {
+                    collateral_factor_mantissa: percent(80),
+                    elapsed: 1000,
+                    ramp_duration: 1000,
+                },
+                expected: U256::zero(),
+            },
+            Case {
+                name: "no ramp",
+                input: DeprecatedCollateralFactorInput {
+                    collateral_factor_mantissa: percent(80),
+                    elapsed: 0,
+                    ramp_duration: 0,
+                },
+                expected: U256::zero(),
+            },
+        ];
+        for case in cases {
+            let got = deprecated_collateral_factor_mantissa(&case.input);
+            assert_eq!(got, case.expected, "{}", case.name);
+        }
+    }
+
+    #[test]
+    fn test_emode_category_is_valid() {
+        struct Case {
+            input: EModeCategory,
+            expected: bool,
+            name: &'static str,
+        }
+        let percent =
+            |val: u128| WrappedU256::from(exp_scale().mul(U256::from(val)).div(U256::from(100)));
+        let cases = vec![
+            Case {
+                name: "stablecoins",
+                input: EModeCategory {
+                    collateral_factor_mantissa: percent(97),
+                    liquidation_threshold: 9750,
+                    liquidation_incentive_mantissa: percent(101),
+                    price_source: None,
+                },
+                expected: true,
+            },
+            Case {
+                name: "collateral factor is zero",
+                input: EModeCategory {
+                    collateral_factor_mantissa: percent(0),
+                    liquidation_threshold: 9750,
+                    liquidation_incentive_mantissa: percent(101),
+                    price_source: None,
+                },
+                expected: false,
+            },
+            Case {
+                name: "collateral factor reaches liquidation threshold",
+                input: EModeCategory {
+                    collateral_factor_mantissa: percent(98),
+                    liquidation_threshold: 9800,
+                    liquidation_incentive_mantissa: percent(101),
+                    price_source: None,
+                },
+                expected: false,
+            },
+            Case {
+                name: "liquidation threshold is over 100%",
+                input: EModeCategory {
+                    collateral_factor_mantissa: percent(97),
+                    liquidation_threshold: 10001,
+                    liquidation_incentive_mantissa: percent(100),
+                    price_source: None,
+                },
+                expected: false,
+            },
+            Case {
+                name: "liquidation incentive is under 100%",
+                input: EModeCategory {
+                    collateral_factor_mantissa: percent(97),
+                    liquidation_threshold: 9750,
+                    liquidation_incentive_mantissa: percent(99),
+                    price_source: None,
+                },
+                expected: false,
+            },
+            Case {
+                name: "liquidation seizes more than the collateral",
+                input: EModeCategory {
+                    collateral_factor_mantissa: percent(97),
+                    liquidation_threshold: 9750,
+                    liquidation_incentive_mantissa: percent(103),
+                    price_source: None,
+                },
+                expected: false,
+            },
+        ];
+        for case in cases {
+            assert_eq!(
+                emode_category_is_valid(&case.input),
+                case.expected,
+                "case: {}",
+                case.name
+            );
+        }
+    }
 }
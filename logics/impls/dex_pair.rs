@@ -0,0 +1,129 @@
+// Copyright 2023 Asynmatrix Pte. Ltd.
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::price_oracle::PRICE_PRECISION;
+pub use crate::traits::dex_pair::*;
+use crate::traits::types::WrappedU256;
+use core::ops::{
+    Div,
+    Mul,
+};
+use openbrush::traits::{
+    AccountId,
+    Balance,
+    Storage,
+    Timestamp,
+};
+use primitive_types::U256;
+
+pub const STORAGE_KEY: u32 = openbrush::storage_unique_key!(Data);
+
+#[derive(Debug)]
+#[openbrush::upgradeable_storage(STORAGE_KEY)]
+pub struct Data {
+    /// For mock only: pair holding reserves without swaps
+    pub token_0: AccountId,
+    pub token_1: AccountId,
+    pub reserve_0: Balance,
+    pub reserve_1: Balance,
+    pub block_timestamp_last: Timestamp,
+    pub price_0_cumulative_last: WrappedU256,
+    pub price_1_cumulative_last: WrappedU256,
+}
+
+/// Price of the token of reserve_in in the token of reserve_out, None without liquidity
+pub fn spot_price(reserve_in: Balance, reserve_out: Balance) -> Option<U256> {
+    if reserve_in == 0 {
+        return None
+    }
+    Some(
+        U256::from(reserve_out)
+            .mul(U256::from(PRICE_PRECISION))
+            .div(U256::from(reserve_in)),
+    )
+}
+
+/// Adds the price held for the elapsed time to the accumulator, wrapping around on overflow
+pub fn accumulate(cumulative: U256, price: U256, elapsed: Timestamp) -> U256 {
+    let (weighted, _) = price.overflowing_mul(U256::from(elapsed));
+    let (result, _) = cumulative.overflowing_add(weighted);
+    result
+}
+
+pub trait Internal {
+    fn _update(&mut self, reserve_0: Balance, reserve_1: Balance) -> Result<()>;
+}
+
+impl<T: Storage<Data>> DexPair for T {
+    default fn token_0(&self) -> AccountId {
+        self.data::<Data>().token_0
+    }
+    default fn token_1(&self) -> AccountId {
+        self.data::<Data>().token_1
+    }
+    default fn get_reserves(&self) -> (Balance, Balance, Timestamp) {
+        let data = self.data::<Data>();
+        (data.reserve_0, data.reserve_1, data.block_timestamp_last)
+    }
+    default fn price_0_cumulative_last(&self) -> WrappedU256 {
+        self.data::<Data>().price_0_cumulative_last
+    }
+    default fn price_1_cumulative_last(&self) -> WrappedU256 {
+        self.data::<Data>().price_1_cumulative_last
+    }
+}
+
+impl<T: Storage<Data>> Internal for T {
+    default fn _update(&mut self, reserve_0: Balance, reserve_1: Balance) -> Result<()> {
+        if reserve_0 == 0 || reserve_1 == 0 {
+            return Err(Error::InsufficientLiquidity)
+        }
+        let now = Self::env().block_timestamp();
+        let mut data = self.data::<Data>();
+        let elapsed = now.saturating_sub(data.block_timestamp_last);
+        if elapsed > 0 {
+            // the prices held until now are the ones of the previous reserves
+            if let (Some(price_0), Some(price_1)) = (
+                spot_price(data.reserve_0, data.reserve_1),
+                spot_price(data.reserve_1, data.reserve_0),
+            ) {
+                data.price_0_cumulative_last =
+                    accumulate(data.price_0_cumulative_last.into(), price_0, elapsed).into();
+                data.price_1_cumulative_last =
+                    accumulate(data.price_1_cumulative_last.into(), price_1, elapsed).into();
+            }
+        }
+        data.reserve_0 = reserve_0;
+        data.reserve_1 = reserve_1;
+        data.block_timestamp_last = now;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spot_price() {
+        assert_eq!(spot_price(0, 100), None);
+        assert_eq!(
+            spot_price(100, 250),
+            Some(U256::from(PRICE_PRECISION * 5 / 2))
+        );
+        assert_eq!(
+            spot_price(250, 100),
+            Some(U256::from(PRICE_PRECISION * 2 / 5))
+        );
+    }
+
+    #[test]
+    fn test_accumulate() {
+        assert_eq!(accumulate(U256::from(10), U256::from(3), 5), U256::from(25));
+        assert_eq!(accumulate(U256::MAX, U256::from(2), 1), U256::from(1));
+    }
+}
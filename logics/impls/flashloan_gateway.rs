@@ -7,7 +7,10 @@
 
 pub use crate::traits::flashloan_gateway::*;
 use crate::traits::{
-    controller::ControllerRef,
+    controller::{
+        ControllerRef,
+        ACTION_FLASHLOAN,
+    },
     flashloan_receiver::FlashloanReceiverRef,
     pool::PoolRef,
 };
@@ -81,6 +84,9 @@ impl<T: Storage<Data>> FlashloanGateway for T {
         let mut premiums: Vec<Balance> = Vec::with_capacity(assets.len());
 
         let controller = self._controller().ok_or(Error::ControllerIsNotSet)?;
+        if ControllerRef::is_shutdown(&controller) {
+            return Err(Error::ProtocolIsShutdown)
+        }
         let flashloan_premium_total = self._flashloan_premium_total();
         for index in 0..assets.len() {
             if amounts[index] < MINUMUM_FLASHLOAN_AMOUNT {
@@ -88,6 +94,9 @@ impl<T: Storage<Data>> FlashloanGateway for T {
             }
             let market = ControllerRef::market_of_underlying(&controller, assets[index])
                 .ok_or(Error::MarketNotListed)?;
+            if ControllerRef::action_paused(&controller, market, ACTION_FLASHLOAN) {
+                return Err(Error::FlashloanIsPaused)
+            }
             lp_token_addresses.push(market);
             let premium: u128 = amounts[index] * flashloan_premium_total / 10000;
             premiums.push(premium);
@@ -123,13 +132,20 @@ impl<T: Storage<Data>> FlashloanGateway for T {
             if mods[index] == FlashLoanType::None as u8 {
                 PoolRef::accrue_interest(&current_lp_token)?;
 
+                // NOTE: repay via the pool so that it is counted as the cash of the pool
                 PSP22Ref::transfer_from(
                     &current_asset,
                     receiver_address,
-                    current_lp_token,
+                    Self::env().account_id(),
                     current_amount_plus_premium,
                     Vec::<u8>::new(),
                 )?;
+                PSP22Ref::approve(
+                    &current_asset,
+                    current_lp_token,
+                    current_amount_plus_premium,
+                )?;
+                PoolRef::repay_flashloan(&current_lp_token, current_amount_plus_premium)?;
             } else {
                 PoolRef::borrow_for_flashloan(&current_lp_token, caller, current_amount)?;
             }
@@ -35,6 +35,8 @@ pub struct Data {
     /// Borrow interest per milli seconds
     base_rate_per_milli_second: WrappedU256,
     kink: WrappedU256,
+    /// Premium added to the variable rate for stable borrows
+    stable_rate_premium_per_milli_second: WrappedU256,
 }
 
 fn base() -> U256 {
@@ -68,6 +70,12 @@ fn utilization_rate(cash: Balance, borrows: Balance, reserves: Balance) -> U256
 
 pub trait Internal {
     fn _get_borrow_rate(&self, cash: Balance, borrows: Balance, reserves: Balance) -> WrappedU256;
+    fn _get_stable_borrow_rate(
+        &self,
+        cash: Balance,
+        borrows: Balance,
+        reserves: Balance,
+    ) -> WrappedU256;
     fn _get_supply_rate(
         &self,
         cash: Balance,
@@ -83,6 +91,22 @@ impl Data {
         multiplier_per_year_slope_1: WrappedU256,
         multiplier_per_year_slope_2: WrappedU256,
         kink: WrappedU256,
+    ) -> Self {
+        Self::new_with_stable_rate_premium(
+            base_rate_per_year,
+            multiplier_per_year_slope_1,
+            multiplier_per_year_slope_2,
+            kink,
+            WrappedU256::from(U256::zero()),
+        )
+    }
+
+    pub fn new_with_stable_rate_premium(
+        base_rate_per_year: WrappedU256,
+        multiplier_per_year_slope_1: WrappedU256,
+        multiplier_per_year_slope_2: WrappedU256,
+        kink: WrappedU256,
+        stable_rate_premium_per_year: WrappedU256,
     ) -> Self {
         let to_seconds_func = |val: WrappedU256| -> WrappedU256 {
             WrappedU256::from(U256::from(val).div(milliseconds_per_year()))
@@ -92,6 +116,7 @@ impl Data {
             multiplier_per_milli_second_slope_2: to_seconds_func(multiplier_per_year_slope_2),
             base_rate_per_milli_second: to_seconds_func(base_rate_per_year),
             kink,
+            stable_rate_premium_per_milli_second: to_seconds_func(stable_rate_premium_per_year),
         }
     }
 
@@ -116,6 +141,18 @@ impl Data {
         WrappedU256::from(normal_rate.add(excess_rate))
     }
 
+    fn stable_borrow_rate(
+        &self,
+        cash: Balance,
+        borrows: Balance,
+        reserves: Balance,
+    ) -> WrappedU256 {
+        let borrow_rate = self.borrow_rate(cash, borrows, reserves);
+        WrappedU256::from(
+            U256::from(borrow_rate).add(U256::from(self.stable_rate_premium_per_milli_second)),
+        )
+    }
+
     fn supply_rate(
         &self,
         cash: Balance,
@@ -153,6 +190,15 @@ impl<T: Storage<Data>> InterestRateModel for T {
         self._get_borrow_rate(cash, borrows, reserves)
     }
 
+    default fn get_stable_borrow_rate(
+        &self,
+        cash: Balance,
+        borrows: Balance,
+        reserves: Balance,
+    ) -> WrappedU256 {
+        self._get_stable_borrow_rate(cash, borrows, reserves)
+    }
+
     default fn get_supply_rate(
         &self,
         cash: Balance,
@@ -173,6 +219,14 @@ impl<T: Storage<Data>> Internal for T {
     ) -> WrappedU256 {
         self.data().borrow_rate(cash, borrows, reserves)
     }
+    default fn _get_stable_borrow_rate(
+        &self,
+        cash: Balance,
+        borrows: Balance,
+        reserves: Balance,
+    ) -> WrappedU256 {
+        self.data().stable_borrow_rate(cash, borrows, reserves)
+    }
     default fn _get_supply_rate(
         &self,
         cash: Balance,
@@ -326,6 +380,35 @@ mod tests {
             assert_eq!(U256::from(result), U256::from(want))
         }
     }
+    #[test]
+    fn test_stable_borrow_rate() {
+        let total_borrow: u32 = 100;
+        let total_cash: u32 = 900;
+        let reserves: u32 = 0;
+        let premium = 2;
+        let model = Data::new_with_stable_rate_premium(
+            wr(milliseconds_per_year().mul(mul_base(10))),
+            wr(milliseconds_per_year().mul(mul_base(50))),
+            wr(milliseconds_per_year().mul(mul_base(100))),
+            wr(percent(80)),
+            wr(milliseconds_per_year().mul(mul_base(premium))),
+        );
+        let variable = model.borrow_rate(
+            Balance::from(total_cash),
+            Balance::from(total_borrow),
+            Balance::from(reserves),
+        );
+        let stable = model.stable_borrow_rate(
+            Balance::from(total_cash),
+            Balance::from(total_borrow),
+            Balance::from(reserves),
+        );
+        assert_eq!(
+            U256::from(stable),
+            U256::from(variable).add(mul_base(premium))
+        );
+    }
+
     #[test]
     fn test_get_supply_rate() {
         struct Case {
@@ -438,11 +438,15 @@ impl<T: Storage<Data>> Internal for T {
             return Err(Error::InappropriateLoopCount)
         }
 
+        let controller = self._controller().ok_or(Error::ControllerIsNotSet)?;
+        if ControllerRef::is_shutdown(&controller) {
+            return Err(Error::ProtocolIsShutdown)
+        }
+
         let caller = Self::env().caller();
         let contract_addr = Self::env().account_id();
         PSP22Ref::transfer_from(&asset, caller, contract_addr, amount, Default::default())?;
 
-        let controller = self._controller().ok_or(Error::ControllerIsNotSet)?;
         let pool = ControllerRef::market_of_underlying(&controller, asset)
             .ok_or(Error::MarketNotListed)?;
         PSP22Ref::approve(&asset, pool, u128::MAX)?;
@@ -463,6 +467,9 @@ impl<T: Storage<Data>> Internal for T {
         let deposit_value = Self::env().transferred_value();
 
         let controller = self._controller().ok_or(Error::ControllerIsNotSet)?;
+        if ControllerRef::is_shutdown(&controller) {
+            return Err(Error::ProtocolIsShutdown)
+        }
         let pool =
             ControllerRef::market_of_underlying(&controller, weth).ok_or(Error::MarketNotListed)?;
         WETHRef::deposit_builder(&weth)
@@ -517,6 +524,10 @@ impl<T: Storage<Data>> Internal for T {
         let caller = Self::env().caller();
         let contract_addr = Self::env().account_id();
         let controller = self._controller().ok_or(Error::ControllerIsNotSet)?;
+        // unwinding redeems collateral while borrowing, which is not allowed once shut down
+        if ControllerRef::is_shutdown(&controller) {
+            return Err(Error::ProtocolIsShutdown)
+        }
         let pool = ControllerRef::market_of_underlying(&controller, asset)
             .ok_or(Error::MarketNotListed)?;
         PSP22Ref::approve(&asset, pool, u128::MAX)?;
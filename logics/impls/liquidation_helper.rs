@@ -0,0 +1,184 @@
+// Copyright 2023 Asynmatrix Pte. Ltd.
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+pub use crate::traits::liquidation_helper::*;
+use crate::traits::{
+    controller::ControllerRef,
+    pool::PoolRef,
+};
+use ink::prelude::{
+    vec,
+    vec::Vec,
+};
+use openbrush::{
+    contracts::psp22::PSP22Ref,
+    traits::{
+        AccountId,
+        Balance,
+        Storage,
+    },
+};
+
+pub const STORAGE_KEY: u32 = openbrush::storage_unique_key!(Data);
+
+#[derive(Debug)]
+#[openbrush::upgradeable_storage(STORAGE_KEY)]
+pub struct Data {
+    /// AccountId of Controller managing the pools to liquidate
+    pub controller: AccountId,
+}
+
+pub trait Internal {
+    fn _controller(&self) -> AccountId;
+    fn _liquidate_borrows(
+        &mut self,
+        pool: AccountId,
+        liquidations: Vec<LiquidationParams>,
+        receive_underlying: bool,
+        skip_failures: bool,
+    ) -> Result<Vec<Result<()>>>;
+    fn _liquidate_borrow(
+        &mut self,
+        pool: AccountId,
+        liquidator: AccountId,
+        liquidation: LiquidationParams,
+        receive_underlying: bool,
+    ) -> Result<()>;
+}
+
+impl<T: Storage<Data>> LiquidationHelper for T {
+    default fn controller(&self) -> AccountId {
+        self._controller()
+    }
+
+    default fn liquidate_borrows(
+        &mut self,
+        pool: AccountId,
+        liquidations: Vec<LiquidationParams>,
+        receive_underlying: bool,
+        skip_failures: bool,
+    ) -> Result<Vec<Result<()>>> {
+        self._liquidate_borrows(pool, liquidations, receive_underlying, skip_failures)
+    }
+}
+
+impl<T: Storage<Data>> Internal for T {
+    default fn _controller(&self) -> AccountId {
+        self.data::<Data>().controller
+    }
+
+    default fn _liquidate_borrows(
+        &mut self,
+        pool: AccountId,
+        liquidations: Vec<LiquidationParams>,
+        receive_underlying: bool,
+        skip_failures: bool,
+    ) -> Result<Vec<Result<()>>> {
+        let controller = self._controller();
+        if !ControllerRef::is_listed(&controller, pool) {
+            return Err(Error::MarketNotListed)
+        }
+        if ControllerRef::is_shutdown(&controller) {
+            return Err(Error::ProtocolIsShutdown)
+        }
+        let caller = Self::env().caller();
+        let contract_addr = Self::env().account_id();
+        let underlying = PoolRef::underlying(&pool).ok_or(Error::UnderlyingIsNotSet)?;
+
+        // involved markets, the entries with an unlisted collateral fail by themselves
+        let mut markets = vec![pool];
+        for liquidation in liquidations.iter() {
+            if !markets.contains(&liquidation.collateral)
+                && ControllerRef::is_listed(&controller, liquidation.collateral)
+            {
+                markets.push(liquidation.collateral);
+            }
+        }
+
+        // tokens returned to the caller: the unused underlying and the seized collateral
+        let mut tokens = vec![underlying];
+        for market in markets.iter() {
+            let token = if receive_underlying {
+                PoolRef::underlying(market).ok_or(Error::UnderlyingIsNotSet)?
+            } else {
+                *market
+            };
+            if !tokens.contains(&token) {
+                tokens.push(token);
+            }
+        }
+        let balances_before: Vec<_> = tokens
+            .iter()
+            .map(|token| PSP22Ref::balance_of(token, contract_addr))
+            .collect();
+
+        // accrue interest once per market, the pools skip it afterwards in the same block
+        for market in markets.iter() {
+            PoolRef::accrue_interest(market)?;
+        }
+
+        let total_repay_amount: Balance = liquidations
+            .iter()
+            .map(|liquidation| liquidation.repay_amount)
+            .sum();
+        PSP22Ref::transfer_from(
+            &underlying,
+            caller,
+            contract_addr,
+            total_repay_amount,
+            Vec::<u8>::new(),
+        )?;
+        PSP22Ref::approve(&underlying, pool, total_repay_amount)?;
+
+        let mut results = Vec::<Result<()>>::new();
+        for liquidation in liquidations {
+            match self._liquidate_borrow(pool, caller, liquidation, receive_underlying) {
+                Err(error) if !skip_failures => return Err(error),
+                result => results.push(result),
+            }
+        }
+
+        PSP22Ref::approve(&underlying, pool, 0)?;
+        for (token, balance_before) in tokens.iter().zip(balances_before) {
+            let amount = PSP22Ref::balance_of(token, contract_addr).saturating_sub(balance_before);
+            if amount != 0 {
+                PSP22Ref::transfer(token, caller, amount, Vec::<u8>::new())?;
+            }
+        }
+
+        Ok(results)
+    }
+
+    default fn _liquidate_borrow(
+        &mut self,
+        pool: AccountId,
+        liquidator: AccountId,
+        liquidation: LiquidationParams,
+        receive_underlying: bool,
+    ) -> Result<()> {
+        if liquidation.borrower == liquidator {
+            return Err(Error::LiquidatorIsBorrower)
+        }
+        if !ControllerRef::is_listed(&self._controller(), liquidation.collateral) {
+            return Err(Error::MarketNotListed)
+        }
+
+        // the failed entry is reverted by itself as a separate call
+        PoolRef::liquidate_borrow_builder(
+            &pool,
+            liquidation.borrower,
+            liquidation.repay_amount,
+            liquidation.collateral,
+            receive_underlying,
+        )
+        .try_invoke()
+        .map_err(|_| Error::LiquidationTrapped)?
+        .map_err(|_| Error::LiquidationTrapped)??;
+
+        Ok(())
+    }
+}
@@ -9,6 +9,7 @@ pub use crate::traits::manager::*;
 use crate::traits::{
     controller::{
         ControllerRef,
+        EModeCategory,
         Error as ControllerError,
     },
     pool::{
@@ -32,6 +33,7 @@ use openbrush::{
         AccountId,
         Balance,
         Storage,
+        Timestamp,
     },
 };
 pub const STORAGE_KEY: u32 = openbrush::storage_unique_key!(Data);
@@ -47,6 +49,7 @@ pub const CONTROLLER_ADMIN: RoleType = ink::selector_id!("CONTROLLER_ADMIN");
 pub const TOKEN_ADMIN: RoleType = ink::selector_id!("TOKEN_ADMIN");
 pub const BORROW_CAP_GUARDIAN: RoleType = ink::selector_id!("BORROW_CAP_GUARDIAN");
 pub const PAUSE_GUARDIAN: RoleType = ink::selector_id!("PAUSE_GUARDIAN");
+pub const EMERGENCY_GUARDIAN: RoleType = ink::selector_id!("EMERGENCY_GUARDIAN");
 
 pub trait Internal {
     fn _controller(&self) -> AccountId;
@@ -67,20 +70,73 @@ pub trait Internal {
     ) -> Result<()>;
     fn _set_mint_guardian_paused(&mut self, pool: AccountId, paused: bool) -> Result<()>;
     fn _set_borrow_guardian_paused(&mut self, pool: AccountId, paused: bool) -> Result<()>;
+    fn _set_actions_paused(&mut self, pool: AccountId, actions: u8, paused: bool) -> Result<()>;
+    fn _set_global_actions_paused(&mut self, actions: u8, paused: bool) -> Result<()>;
+    fn _set_market_frozen(&mut self, pool: AccountId, frozen: bool) -> Result<()>;
+    fn _shutdown(&mut self) -> Result<()>;
+    fn _set_settlement_price(&mut self, pool: AccountId, price: u128) -> Result<()>;
     fn _set_close_factor_mantissa(&mut self, new_close_factor_mantissa: WrappedU256) -> Result<()>;
     fn _set_liquidation_incentive_mantissa(
         &mut self,
         new_liquidation_incentive_mantissa: WrappedU256,
     ) -> Result<()>;
+    fn _set_market_liquidation_incentive_mantissa(
+        &mut self,
+        pool: AccountId,
+        new_liquidation_incentive_mantissa: Option<WrappedU256>,
+    ) -> Result<()>;
+    fn _set_protocol_seize_share_mantissa(
+        &mut self,
+        new_protocol_seize_share_mantissa: WrappedU256,
+    ) -> Result<()>;
+    fn _set_market_protocol_seize_share_mantissa(
+        &mut self,
+        pool: AccountId,
+        new_protocol_seize_share_mantissa: Option<WrappedU256>,
+    ) -> Result<()>;
+    fn _deprecate_market(&mut self, pool: AccountId, ramp_duration: Timestamp) -> Result<()>;
+    fn _remove_market(&mut self, pool: AccountId) -> Result<()>;
+    fn _set_liquidation_auction(
+        &mut self,
+        duration: Timestamp,
+        start_incentive_mantissa: WrappedU256,
+    ) -> Result<()>;
+    fn _set_critical_health_factor(
+        &mut self,
+        new_critical_health_factor: WrappedU256,
+    ) -> Result<()>;
+    fn _set_liquidation_dust_threshold(&mut self, new_dust_threshold: Balance) -> Result<()>;
+    fn _set_emode_category(&mut self, id: u8, category: EModeCategory) -> Result<()>;
+    fn _set_market_emode_category(&mut self, pool: AccountId, id: u8) -> Result<()>;
+    fn _set_isolation_mode(
+        &mut self,
+        pool: AccountId,
+        isolated: bool,
+        debt_ceiling: Balance,
+    ) -> Result<()>;
+    fn _set_borrowable_in_isolation(&mut self, pool: AccountId, borrowable: bool) -> Result<()>;
     fn _set_borrow_cap(&mut self, pool: AccountId, new_cap: Balance) -> Result<()>;
+    fn _set_supply_cap(&mut self, pool: AccountId, new_cap: Balance) -> Result<()>;
     fn _set_reserve_factor_mantissa(
         &mut self,
         pool: AccountId,
         new_reserve_factor_mantissa: WrappedU256,
     ) -> Result<()>;
+    fn _set_minimum_initial_deposit(
+        &mut self,
+        pool: AccountId,
+        new_minimum_initial_deposit: Balance,
+    ) -> Result<()>;
+    fn _set_socialize_bad_debt(&mut self, pool: AccountId, socialize: bool) -> Result<()>;
+    fn _set_max_stable_borrow_size_mantissa(
+        &mut self,
+        pool: AccountId,
+        new_max_stable_borrow_size_mantissa: WrappedU256,
+    ) -> Result<()>;
     fn _add_reserves(&mut self, pool: AccountId, amount: Balance) -> Result<()>;
     fn _reduce_reserves(&mut self, pool: AccountId, amount: Balance) -> Result<()>;
     fn _sweep_token(&mut self, pool: AccountId, asset: AccountId) -> Result<()>;
+    fn _skim(&mut self, pool: AccountId) -> Result<()>;
     fn _set_seize_guardian_paused(&mut self, paused: bool) -> Result<()>;
     fn _set_transfer_guardian_paused(&mut self, paused: bool) -> Result<()>;
     fn _set_liquidation_threshold(
@@ -171,6 +227,100 @@ impl<T: Storage<Data> + Storage<access_control::Data>> Manager for T {
         self._set_liquidation_incentive_mantissa(new_liquidation_incentive_mantissa)
     }
 
+    #[modifiers(access_control::only_role(CONTROLLER_ADMIN))]
+    default fn set_market_liquidation_incentive_mantissa(
+        &mut self,
+        pool: AccountId,
+        new_liquidation_incentive_mantissa: Option<WrappedU256>,
+    ) -> Result<()> {
+        self._set_market_liquidation_incentive_mantissa(pool, new_liquidation_incentive_mantissa)
+    }
+
+    #[modifiers(access_control::only_role(CONTROLLER_ADMIN))]
+    default fn set_protocol_seize_share_mantissa(
+        &mut self,
+        new_protocol_seize_share_mantissa: WrappedU256,
+    ) -> Result<()> {
+        self._set_protocol_seize_share_mantissa(new_protocol_seize_share_mantissa)
+    }
+
+    #[modifiers(access_control::only_role(CONTROLLER_ADMIN))]
+    default fn set_market_protocol_seize_share_mantissa(
+        &mut self,
+        pool: AccountId,
+        new_protocol_seize_share_mantissa: Option<WrappedU256>,
+    ) -> Result<()> {
+        self._set_market_protocol_seize_share_mantissa(pool, new_protocol_seize_share_mantissa)
+    }
+
+    #[modifiers(access_control::only_role(CONTROLLER_ADMIN))]
+    default fn deprecate_market(
+        &mut self,
+        pool: AccountId,
+        ramp_duration: Timestamp,
+    ) -> Result<()> {
+        self._deprecate_market(pool, ramp_duration)
+    }
+
+    #[modifiers(access_control::only_role(CONTROLLER_ADMIN))]
+    default fn remove_market(&mut self, pool: AccountId) -> Result<()> {
+        self._remove_market(pool)
+    }
+
+    #[modifiers(access_control::only_role(CONTROLLER_ADMIN))]
+    default fn set_liquidation_auction(
+        &mut self,
+        duration: Timestamp,
+        start_incentive_mantissa: WrappedU256,
+    ) -> Result<()> {
+        self._set_liquidation_auction(duration, start_incentive_mantissa)
+    }
+
+    #[modifiers(access_control::only_role(CONTROLLER_ADMIN))]
+    default fn set_critical_health_factor(
+        &mut self,
+        new_critical_health_factor: WrappedU256,
+    ) -> Result<()> {
+        self._set_critical_health_factor(new_critical_health_factor)
+    }
+
+    #[modifiers(access_control::only_role(CONTROLLER_ADMIN))]
+    default fn set_liquidation_dust_threshold(
+        &mut self,
+        new_dust_threshold: Balance,
+    ) -> Result<()> {
+        self._set_liquidation_dust_threshold(new_dust_threshold)
+    }
+
+    #[modifiers(access_control::only_role(CONTROLLER_ADMIN))]
+    default fn set_emode_category(&mut self, id: u8, category: EModeCategory) -> Result<()> {
+        self._set_emode_category(id, category)
+    }
+
+    #[modifiers(access_control::only_role(CONTROLLER_ADMIN))]
+    default fn set_market_emode_category(&mut self, pool: AccountId, id: u8) -> Result<()> {
+        self._set_market_emode_category(pool, id)
+    }
+
+    #[modifiers(access_control::only_role(CONTROLLER_ADMIN))]
+    default fn set_isolation_mode(
+        &mut self,
+        pool: AccountId,
+        isolated: bool,
+        debt_ceiling: Balance,
+    ) -> Result<()> {
+        self._set_isolation_mode(pool, isolated, debt_ceiling)
+    }
+
+    #[modifiers(access_control::only_role(CONTROLLER_ADMIN))]
+    default fn set_borrowable_in_isolation(
+        &mut self,
+        pool: AccountId,
+        borrowable: bool,
+    ) -> Result<()> {
+        self._set_borrowable_in_isolation(pool, borrowable)
+    }
+
     #[modifiers(access_control::only_role(CONTROLLER_ADMIN))]
     default fn set_controller_manager(&mut self, manager: AccountId) -> Result<()> {
         self._set_controller_manager(manager)
@@ -187,6 +337,11 @@ impl<T: Storage<Data> + Storage<access_control::Data>> Manager for T {
         self._set_borrow_cap(pool, new_cap)
     }
 
+    #[modifiers(access_control::only_role(BORROW_CAP_GUARDIAN))]
+    default fn set_supply_cap(&mut self, pool: AccountId, new_cap: Balance) -> Result<()> {
+        self._set_supply_cap(pool, new_cap)
+    }
+
     // For Pause Guardian
     #[modifiers(access_control::only_role(PAUSE_GUARDIAN))]
     default fn set_mint_guardian_paused(&mut self, pool: AccountId, paused: bool) -> Result<()> {
@@ -198,6 +353,26 @@ impl<T: Storage<Data> + Storage<access_control::Data>> Manager for T {
         self._set_borrow_guardian_paused(pool, paused)
     }
 
+    #[modifiers(access_control::only_role(PAUSE_GUARDIAN))]
+    default fn set_actions_paused(
+        &mut self,
+        pool: AccountId,
+        actions: u8,
+        paused: bool,
+    ) -> Result<()> {
+        self._set_actions_paused(pool, actions, paused)
+    }
+
+    #[modifiers(access_control::only_role(PAUSE_GUARDIAN))]
+    default fn set_global_actions_paused(&mut self, actions: u8, paused: bool) -> Result<()> {
+        self._set_global_actions_paused(actions, paused)
+    }
+
+    #[modifiers(access_control::only_role(PAUSE_GUARDIAN))]
+    default fn set_market_frozen(&mut self, pool: AccountId, frozen: bool) -> Result<()> {
+        self._set_market_frozen(pool, frozen)
+    }
+
     #[modifiers(access_control::only_role(PAUSE_GUARDIAN))]
     default fn set_seize_guardian_paused(&mut self, paused: bool) -> Result<()> {
         self._set_seize_guardian_paused(paused)
@@ -208,6 +383,17 @@ impl<T: Storage<Data> + Storage<access_control::Data>> Manager for T {
         self._set_transfer_guardian_paused(paused)
     }
 
+    // For Emergency Guardian
+    #[modifiers(access_control::only_role(EMERGENCY_GUARDIAN))]
+    default fn shutdown(&mut self) -> Result<()> {
+        self._shutdown()
+    }
+
+    #[modifiers(access_control::only_role(EMERGENCY_GUARDIAN))]
+    default fn set_settlement_price(&mut self, pool: AccountId, price: u128) -> Result<()> {
+        self._set_settlement_price(pool, price)
+    }
+
     // For Pool Admin
     #[modifiers(access_control::only_role(TOKEN_ADMIN))]
     default fn add_reserves(&mut self, pool: AccountId, amount: Balance) -> Result<()> {
@@ -224,6 +410,11 @@ impl<T: Storage<Data> + Storage<access_control::Data>> Manager for T {
         self._sweep_token(pool, asset)
     }
 
+    #[modifiers(access_control::only_role(TOKEN_ADMIN))]
+    default fn skim(&mut self, pool: AccountId) -> Result<()> {
+        self._skim(pool)
+    }
+
     #[modifiers(access_control::only_role(TOKEN_ADMIN))]
     default fn set_liquidation_threshold(
         &mut self,
@@ -242,6 +433,29 @@ impl<T: Storage<Data> + Storage<access_control::Data>> Manager for T {
         self._set_reserve_factor_mantissa(pool, new_reserve_factor_mantissa)
     }
 
+    #[modifiers(access_control::only_role(TOKEN_ADMIN))]
+    default fn set_minimum_initial_deposit(
+        &mut self,
+        pool: AccountId,
+        new_minimum_initial_deposit: Balance,
+    ) -> Result<()> {
+        self._set_minimum_initial_deposit(pool, new_minimum_initial_deposit)
+    }
+
+    #[modifiers(access_control::only_role(TOKEN_ADMIN))]
+    default fn set_socialize_bad_debt(&mut self, pool: AccountId, socialize: bool) -> Result<()> {
+        self._set_socialize_bad_debt(pool, socialize)
+    }
+
+    #[modifiers(access_control::only_role(TOKEN_ADMIN))]
+    default fn set_max_stable_borrow_size_mantissa(
+        &mut self,
+        pool: AccountId,
+        new_max_stable_borrow_size_mantissa: WrappedU256,
+    ) -> Result<()> {
+        self._set_max_stable_borrow_size_mantissa(pool, new_max_stable_borrow_size_mantissa)
+    }
+
     #[modifiers(access_control::only_role(TOKEN_ADMIN))]
     default fn set_incentives_controller(
         &mut self,
@@ -340,6 +554,31 @@ impl<T: Storage<Data>> Internal for T {
         ControllerRef::set_borrow_guardian_paused(&self._controller(), pool, paused)?;
         Ok(())
     }
+    default fn _set_actions_paused(
+        &mut self,
+        pool: AccountId,
+        actions: u8,
+        paused: bool,
+    ) -> Result<()> {
+        ControllerRef::set_actions_paused(&self._controller(), pool, actions, paused)?;
+        Ok(())
+    }
+    default fn _set_global_actions_paused(&mut self, actions: u8, paused: bool) -> Result<()> {
+        ControllerRef::set_global_actions_paused(&self._controller(), actions, paused)?;
+        Ok(())
+    }
+    default fn _set_market_frozen(&mut self, pool: AccountId, frozen: bool) -> Result<()> {
+        ControllerRef::set_market_frozen(&self._controller(), pool, frozen)?;
+        Ok(())
+    }
+    default fn _shutdown(&mut self) -> Result<()> {
+        ControllerRef::shutdown(&self._controller())?;
+        Ok(())
+    }
+    default fn _set_settlement_price(&mut self, pool: AccountId, price: u128) -> Result<()> {
+        ControllerRef::set_settlement_price(&self._controller(), pool, price)?;
+        Ok(())
+    }
     default fn _set_close_factor_mantissa(
         &mut self,
         new_close_factor_mantissa: WrappedU256,
@@ -357,10 +596,111 @@ impl<T: Storage<Data>> Internal for T {
         )?;
         Ok(())
     }
+    default fn _set_market_liquidation_incentive_mantissa(
+        &mut self,
+        pool: AccountId,
+        new_liquidation_incentive_mantissa: Option<WrappedU256>,
+    ) -> Result<()> {
+        ControllerRef::set_market_liquidation_incentive_mantissa(
+            &self._controller(),
+            pool,
+            new_liquidation_incentive_mantissa,
+        )?;
+        Ok(())
+    }
+    default fn _set_protocol_seize_share_mantissa(
+        &mut self,
+        new_protocol_seize_share_mantissa: WrappedU256,
+    ) -> Result<()> {
+        ControllerRef::set_protocol_seize_share_mantissa(
+            &self._controller(),
+            new_protocol_seize_share_mantissa,
+        )?;
+        Ok(())
+    }
+    default fn _set_market_protocol_seize_share_mantissa(
+        &mut self,
+        pool: AccountId,
+        new_protocol_seize_share_mantissa: Option<WrappedU256>,
+    ) -> Result<()> {
+        ControllerRef::set_market_protocol_seize_share_mantissa(
+            &self._controller(),
+            pool,
+            new_protocol_seize_share_mantissa,
+        )?;
+        Ok(())
+    }
+    default fn _deprecate_market(
+        &mut self,
+        pool: AccountId,
+        ramp_duration: Timestamp,
+    ) -> Result<()> {
+        ControllerRef::deprecate_market(&self._controller(), pool, ramp_duration)?;
+        Ok(())
+    }
+    default fn _remove_market(&mut self, pool: AccountId) -> Result<()> {
+        ControllerRef::remove_market(&self._controller(), pool)?;
+        Ok(())
+    }
+    default fn _set_liquidation_auction(
+        &mut self,
+        duration: Timestamp,
+        start_incentive_mantissa: WrappedU256,
+    ) -> Result<()> {
+        ControllerRef::set_liquidation_auction(
+            &self._controller(),
+            duration,
+            start_incentive_mantissa,
+        )?;
+        Ok(())
+    }
+    default fn _set_critical_health_factor(
+        &mut self,
+        new_critical_health_factor: WrappedU256,
+    ) -> Result<()> {
+        ControllerRef::set_critical_health_factor(&self._controller(), new_critical_health_factor)?;
+        Ok(())
+    }
+    default fn _set_liquidation_dust_threshold(
+        &mut self,
+        new_dust_threshold: Balance,
+    ) -> Result<()> {
+        ControllerRef::set_liquidation_dust_threshold(&self._controller(), new_dust_threshold)?;
+        Ok(())
+    }
+    default fn _set_emode_category(&mut self, id: u8, category: EModeCategory) -> Result<()> {
+        ControllerRef::set_emode_category(&self._controller(), id, category)?;
+        Ok(())
+    }
+    default fn _set_market_emode_category(&mut self, pool: AccountId, id: u8) -> Result<()> {
+        ControllerRef::set_market_emode_category(&self._controller(), pool, id)?;
+        Ok(())
+    }
+    default fn _set_isolation_mode(
+        &mut self,
+        pool: AccountId,
+        isolated: bool,
+        debt_ceiling: Balance,
+    ) -> Result<()> {
+        ControllerRef::set_isolation_mode(&self._controller(), pool, isolated, debt_ceiling)?;
+        Ok(())
+    }
+    default fn _set_borrowable_in_isolation(
+        &mut self,
+        pool: AccountId,
+        borrowable: bool,
+    ) -> Result<()> {
+        ControllerRef::set_borrowable_in_isolation(&self._controller(), pool, borrowable)?;
+        Ok(())
+    }
     default fn _set_borrow_cap(&mut self, pool: AccountId, new_cap: Balance) -> Result<()> {
         ControllerRef::set_borrow_cap(&self._controller(), pool, new_cap)?;
         Ok(())
     }
+    default fn _set_supply_cap(&mut self, pool: AccountId, new_cap: Balance) -> Result<()> {
+        ControllerRef::set_supply_cap(&self._controller(), pool, new_cap)?;
+        Ok(())
+    }
     default fn _set_reserve_factor_mantissa(
         &mut self,
         pool: AccountId,
@@ -375,6 +715,44 @@ impl<T: Storage<Data>> Internal for T {
         PoolRef::set_reserve_factor_mantissa(&pool, new_reserve_factor_mantissa)?;
         Ok(())
     }
+    default fn _set_minimum_initial_deposit(
+        &mut self,
+        pool: AccountId,
+        new_minimum_initial_deposit: Balance,
+    ) -> Result<()> {
+        let controller = self.data().controller;
+        let is_listed: bool = ControllerRef::is_listed(&controller, pool);
+        if !is_listed {
+            return Err(Error::from(ControllerError::MarketNotListed))
+        }
+
+        PoolRef::set_minimum_initial_deposit(&pool, new_minimum_initial_deposit)?;
+        Ok(())
+    }
+    default fn _set_socialize_bad_debt(&mut self, pool: AccountId, socialize: bool) -> Result<()> {
+        let controller = self.data().controller;
+        let is_listed: bool = ControllerRef::is_listed(&controller, pool);
+        if !is_listed {
+            return Err(Error::from(ControllerError::MarketNotListed))
+        }
+
+        PoolRef::set_socialize_bad_debt(&pool, socialize)?;
+        Ok(())
+    }
+    default fn _set_max_stable_borrow_size_mantissa(
+        &mut self,
+        pool: AccountId,
+        new_max_stable_borrow_size_mantissa: WrappedU256,
+    ) -> Result<()> {
+        let controller = self.data().controller;
+        let is_listed: bool = ControllerRef::is_listed(&controller, pool);
+        if !is_listed {
+            return Err(Error::from(ControllerError::MarketNotListed))
+        }
+
+        PoolRef::set_max_stable_borrow_size_mantissa(&pool, new_max_stable_borrow_size_mantissa)?;
+        Ok(())
+    }
     default fn _add_reserves(&mut self, pool: AccountId, amount: Balance) -> Result<()> {
         let controller = self.data().controller;
         let is_listed: bool = ControllerRef::is_listed(&controller, pool);
@@ -438,6 +816,26 @@ impl<T: Storage<Data>> Internal for T {
         )?;
         Ok(())
     }
+    default fn _skim(&mut self, pool: AccountId) -> Result<()> {
+        let controller = self.data().controller;
+        let is_listed: bool = ControllerRef::is_listed(&controller, pool);
+        if !is_listed {
+            return Err(Error::from(ControllerError::MarketNotListed))
+        }
+
+        PoolRef::skim(&pool)?;
+
+        let underlying: Option<AccountId> = PoolRef::underlying(&pool);
+        let underlying_asset = underlying.ok_or(Error::from(PoolError::UnderlyingIsNotSet))?;
+        let balance = PSP22Ref::balance_of(&underlying_asset, Self::env().account_id());
+        PSP22Ref::transfer(
+            &underlying_asset,
+            Self::env().caller(),
+            balance,
+            Vec::<u8>::new(),
+        )?;
+        Ok(())
+    }
     default fn _set_seize_guardian_paused(&mut self, paused: bool) -> Result<()> {
         ControllerRef::set_seize_guardian_paused(&self._controller(), paused)?;
         Ok(())
@@ -14,7 +14,7 @@ use core::ops::{
 };
 use primitive_types::U256;
 
-fn percentage_factor() -> U256 {
+pub fn percentage_factor() -> U256 {
     U256::from(10000)
 }
 
@@ -22,6 +22,17 @@ fn half_percent() -> U256 {
     U256::from(5000)
 }
 
+/// Whether the price is within the max deviation (in basis points) of the reference
+pub fn deviation_within(price: u128, reference: u128, max_deviation: u128) -> bool {
+    let diff = if price > reference {
+        price.sub(reference)
+    } else {
+        reference.sub(price)
+    };
+    U256::from(diff).mul(percentage_factor())
+        <= U256::from(max_deviation).mul(U256::from(reference))
+}
+
 pub struct Percent {
     pub percentage: U256,
 }
@@ -63,7 +74,10 @@ impl Percent {
 
 #[cfg(test)]
 mod tests {
-    use super::Percent;
+    use super::{
+        deviation_within,
+        Percent,
+    };
     use crate::impls::wad_ray_math::Error;
     use primitive_types::U256;
     #[test]
@@ -161,4 +175,52 @@ mod tests {
             assert_eq!(got.unwrap(), case.expected);
         }
     }
+
+    #[test]
+    fn test_deviation_within() {
+        struct Case {
+            price: u128,
+            reference: u128,
+            max_deviation: u128,
+            want: bool,
+        }
+        let cases: &[Case] = &[
+            Case {
+                price: 100,
+                reference: 100,
+                max_deviation: 0,
+                want: true,
+            },
+            Case {
+                price: 105,
+                reference: 100,
+                max_deviation: 500,
+                want: true,
+            },
+            Case {
+                price: 95,
+                reference: 100,
+                max_deviation: 500,
+                want: true,
+            },
+            Case {
+                price: 106,
+                reference: 100,
+                max_deviation: 500,
+                want: false,
+            },
+            Case {
+                price: 94,
+                reference: 100,
+                max_deviation: 500,
+                want: false,
+            },
+        ];
+        for case in cases {
+            assert_eq!(
+                deviation_within(case.price, case.reference, case.max_deviation),
+                case.want
+            );
+        }
+    }
 }
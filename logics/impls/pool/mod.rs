@@ -63,11 +63,15 @@ use primitive_types::U256;
 
 pub mod utils;
 use self::utils::{
+    average_stable_rate_on_decrease,
+    average_stable_rate_on_increase,
     calculate_interest,
     exchange_rate,
+    max_stable_borrow_amount,
+    max_stable_borrow_size_mantissa,
     protocol_seize_amount,
-    protocol_seize_share_mantissa,
     reserve_factor_max_mantissa,
+    stable_borrow_balance,
     underlying_balance,
     CalculateInterestInput,
     CalculateInterestOutput,
@@ -76,6 +80,10 @@ use self::utils::{
 pub const STORAGE_KEY: u32 = openbrush::storage_unique_key!(Data);
 pub const COLLATERAL_FACTOR_MANTISSA_DECIMALS: u32 = 18;
 pub const LIQUIDATION_THRESHOLD_DECIMALS: u32 = 4;
+/// Pool tokens locked by the first mint so that the total supply never returns to zero
+pub const DEAD_SHARES: Balance = 1_000;
+/// Holder of the dead shares, an address nobody has the key for
+pub const DEAD_SHARES_HOLDER: [u8; 32] = [0xff; 32];
 
 #[derive(Debug, scale::Decode, scale::Encode, Default)]
 #[cfg_attr(
@@ -87,6 +95,17 @@ pub struct BorrowSnapshot {
     interest_index: WrappedU256,
 }
 
+#[derive(Debug, scale::Decode, scale::Encode, Default)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct StableBorrowSnapshot {
+    principal: Balance,
+    stable_rate: WrappedU256,
+    last_updated_timestamp: Timestamp,
+}
+
 #[derive(Debug)]
 #[openbrush::upgradeable_storage(STORAGE_KEY)]
 pub struct Data {
@@ -108,6 +127,20 @@ pub struct Data {
     pub total_reserves: Balance,
     /// Borrow balance for accounts
     pub account_borrows: Mapping<AccountId, BorrowSnapshot>,
+    /// Accounts with debt by their position in the registry
+    pub borrowers: Mapping<u32, AccountId>,
+    /// Position of the accounts with debt in the registry
+    pub borrower_positions: Mapping<AccountId, u32>,
+    /// Number of accounts with debt
+    pub borrowers_count: u32,
+    /// Whether the account is recorded in Controller as having a balance or debt in this pool
+    pub account_market_entered: Mapping<AccountId, bool>,
+    /// Stable rate borrow balance for accounts
+    pub account_stable_borrows: Mapping<AccountId, StableBorrowSnapshot>,
+    /// Total stable rate borrows, part of total borrows
+    pub total_stable_borrows: Balance,
+    /// Average rate of stable rate borrows weighted by their amount
+    pub average_stable_borrow_rate: WrappedU256,
     /// Last block number of interest calculation process execution
     pub accrual_block_timestamp: Timestamp,
     /// Borrow index for interests
@@ -122,6 +155,16 @@ pub struct Data {
     pub delegate_allowance: Mapping<(AccountId, AccountId), Balance, AllowancesKey>,
     /// Represent if user is using his reserve as collateral or not
     pub using_reserve_as_collateral: Mapping<AccountId, bool>,
+    /// Underlying held by the pool, tracked without direct transfers
+    pub total_cash: Balance,
+    /// Minimum underlying amount of the first mint
+    pub minimum_initial_deposit: Balance,
+    /// Bad debt not covered by reserves, counted as an asset of the pool until covered
+    pub deficit: Balance,
+    /// Whether bad debt not covered by reserves is socialized to the suppliers instead of recorded as deficit
+    pub socialize_bad_debt: bool,
+    /// Maximum size of a stable rate borrow relative to the available liquidity
+    pub max_stable_borrow_size_mantissa: WrappedU256,
 }
 
 pub struct AllowancesKey;
@@ -141,7 +184,19 @@ impl Default for Data {
             incentives_controller: None,
             total_borrows: Default::default(),
             total_reserves: Default::default(),
+            total_cash: Default::default(),
+            minimum_initial_deposit: Default::default(),
+            deficit: Default::default(),
+            socialize_bad_debt: Default::default(),
+            max_stable_borrow_size_mantissa: WrappedU256::from(max_stable_borrow_size_mantissa()),
             account_borrows: Default::default(),
+            borrowers: Default::default(),
+            borrower_positions: Default::default(),
+            borrowers_count: Default::default(),
+            account_market_entered: Default::default(),
+            account_stable_borrows: Default::default(),
+            total_stable_borrows: Default::default(),
+            average_stable_borrow_rate: WrappedU256::from(U256::zero()),
             delegate_allowance: Default::default(),
             accrual_block_timestamp: 0,
             borrow_index: exp_scale().into(),
@@ -174,6 +229,7 @@ pub trait Internal {
         borrower: AccountId,
         borrow_amount: Balance,
         release_underlying: bool,
+        rate_mode: BorrowRateMode,
     ) -> Result<()>;
     fn _repay_borrow(
         &mut self,
@@ -181,12 +237,24 @@ pub trait Internal {
         borrower: AccountId,
         repay_amount: Balance,
     ) -> Result<Balance>;
+    fn _swap_borrow_rate_mode(
+        &mut self,
+        borrower: AccountId,
+        rate_mode: BorrowRateMode,
+    ) -> Result<()>;
+    fn _rebalance_stable_borrow_rate(&mut self, borrower: AccountId) -> Result<()>;
+    fn _increase_stable_borrow(&mut self, borrower: AccountId, amount: Balance, rate: WrappedU256);
+    fn _decrease_stable_borrow(&mut self, borrower: AccountId, amount: Balance);
+    fn _add_borrower(&mut self, borrower: AccountId);
+    fn _remove_borrower(&mut self, borrower: AccountId);
+    fn _update_account_market(&mut self, account: AccountId) -> Result<()>;
     fn _liquidate_borrow(
         &mut self,
         liquidator: AccountId,
         borrower: AccountId,
         repay_amount: Balance,
         collateral: AccountId,
+        receive_underlying: bool,
     ) -> Result<()>;
     fn _seize(
         &mut self,
@@ -194,6 +262,16 @@ pub trait Internal {
         liquidator: AccountId,
         borrower: AccountId,
         seize_tokens: Balance,
+        receive_underlying: bool,
+    ) -> Result<()>;
+    fn _settle_bad_debt(&mut self, borrower: AccountId) -> Result<Balance>;
+    fn _write_off_bad_debt(&mut self, borrower: AccountId, bad_debt: Balance) -> Result<()>;
+    fn _update_liquidation_auction(&mut self, account: AccountId) -> Result<()>;
+    fn _update_isolated_debt(
+        &mut self,
+        account: AccountId,
+        borrow_balance_prev: Balance,
+        borrow_balance_new: Balance,
     ) -> Result<()>;
 
     // admin functions
@@ -203,10 +281,18 @@ pub trait Internal {
         new_reserve_factor_mantissa: WrappedU256,
     ) -> Result<()>;
     fn _set_interest_rate_model(&mut self, new_interest_rate_model: AccountId) -> Result<()>;
+    fn _set_minimum_initial_deposit(&mut self, new_minimum_initial_deposit: Balance) -> Result<()>;
     fn _add_reserves(&mut self, amount: Balance) -> Result<()>;
+    fn _cover_deficit(&mut self, amount: Balance) -> Result<()>;
     fn _reduce_reserves(&mut self, admin: AccountId, amount: Balance) -> Result<()>;
     fn _sweep_token(&mut self, asset: AccountId) -> Result<()>;
+    fn _skim(&mut self, to: AccountId) -> Result<Balance>;
     fn _set_liquidation_threshold(&mut self, new_liquidation_threshold: u128) -> Result<()>;
+    fn _set_socialize_bad_debt(&mut self, socialize: bool) -> Result<()>;
+    fn _set_max_stable_borrow_size_mantissa(
+        &mut self,
+        new_max_stable_borrow_size_mantissa: WrappedU256,
+    ) -> Result<()>;
     fn _approve_delegate(
         &mut self,
         owner: AccountId,
@@ -216,12 +302,12 @@ pub trait Internal {
     fn _set_use_reserve_as_collateral(&mut self, user: AccountId, use_as_collateral: bool);
     // utilities
     fn _transfer_underlying_from(
-        &self,
+        &mut self,
         from: AccountId,
         to: AccountId,
         value: Balance,
     ) -> Result<()>;
-    fn _transfer_underlying(&self, to: AccountId, value: Balance) -> Result<()>;
+    fn _transfer_underlying(&mut self, to: AccountId, value: Balance) -> Result<()>;
     fn _assert_manager(&self) -> Result<()>;
     fn _assert_pending_manager(&self) -> Result<()>;
     fn _validate_set_use_reserve_as_collateral(
@@ -240,8 +326,16 @@ pub trait Internal {
     fn _pending_manager(&self) -> Option<AccountId>;
     fn _incentives_controller(&self) -> Option<AccountId>;
     fn _get_cash_prior(&self) -> Balance;
+    fn _underlying_balance_of_pool(&self) -> Balance;
     fn _total_borrows(&self) -> Balance;
+    fn _borrowers_count(&self) -> u32;
+    fn _borrowers(&self, start: u32, limit: u32) -> Vec<AccountId>;
     fn _total_reserves(&self) -> Balance;
+    fn _deficit(&self) -> Balance;
+    fn _socialize_bad_debt(&self) -> bool;
+    fn _max_stable_borrow_size_mantissa(&self) -> WrappedU256;
+    /// Fails if the stable rate borrow takes more of the available liquidity than allowed
+    fn _assert_stable_borrow_size(&self, amount: Balance) -> Result<()>;
     fn _rate_model(&self) -> Option<AccountId>;
     fn _borrow_rate_per_msec(
         &self,
@@ -249,6 +343,12 @@ pub trait Internal {
         borrows: Balance,
         reserves: Balance,
     ) -> WrappedU256;
+    fn _stable_borrow_rate_per_msec(
+        &self,
+        cash: Balance,
+        borrows: Balance,
+        reserves: Balance,
+    ) -> WrappedU256;
     fn _supply_rate_per_msec(
         &self,
         cash: Balance,
@@ -257,16 +357,33 @@ pub trait Internal {
         reserve_factor: WrappedU256,
     ) -> WrappedU256;
     fn _borrow_balance_stored(&self, account: AccountId) -> Balance;
+    fn _variable_borrow_balance_stored(&self, account: AccountId) -> Balance;
+    fn _stable_borrow_balance_stored(&self, account: AccountId) -> Balance;
+    fn _stable_borrow_rate_of(&self, account: AccountId) -> WrappedU256;
+    fn _total_stable_borrows(&self) -> Balance;
+    fn _average_stable_borrow_rate(&self) -> WrappedU256;
     fn _balance_of_underlying(&self, account: AccountId) -> Balance;
     fn _accrual_block_timestamp(&self) -> Timestamp;
     fn _borrow_index(&self) -> WrappedU256;
     fn _initial_exchange_rate_mantissa(&self) -> WrappedU256;
     fn _reserve_factor_mantissa(&self) -> WrappedU256;
+    fn _minimum_initial_deposit(&self) -> Balance;
     fn _exchange_rate_stored(&self) -> U256;
     fn _liquidation_threshold(&self) -> u128;
     fn _delegate_allowance(&self, owner: &AccountId, delegatee: &AccountId) -> Balance;
     fn _using_reserve_as_collateral(&self, user: AccountId) -> Option<bool>;
     fn _get_interest_at(&self, at: Timestamp) -> Result<CalculateInterestOutput>;
+    fn _get_projected_interest(
+        &self,
+        at: Option<Timestamp>,
+    ) -> Result<(Timestamp, CalculateInterestOutput)>;
+    fn _projected_exchange_rate(&self, interest: &CalculateInterestOutput) -> U256;
+    fn _projected_borrow_balance(
+        &self,
+        account: AccountId,
+        at: Timestamp,
+        interest: &CalculateInterestOutput,
+    ) -> Balance;
 
     // event emission
     fn _emit_mint_event(&self, minter: AccountId, mint_amount: Balance, mint_tokens: Balance);
@@ -307,9 +424,21 @@ pub trait Internal {
         new_total_reserves: Balance,
     );
     fn _emit_reserves_reduced_event(&self, reduce_amount: Balance, total_reserves_new: Balance);
+    fn _emit_bad_debt_detected_event(&self, borrower: AccountId, amount: Balance);
+    fn _emit_bad_debt_covered_by_reserves_event(
+        &self,
+        borrower: AccountId,
+        amount: Balance,
+        total_reserves_new: Balance,
+    );
+    fn _emit_bad_debt_socialized_event(&self, borrower: AccountId, amount: Balance);
+    fn _emit_deficit_recorded_event(&self, borrower: AccountId, amount: Balance, deficit: Balance);
+    fn _emit_deficit_covered_event(&self, benefactor: AccountId, amount: Balance, deficit: Balance);
     fn _emit_new_controller_event(&self, old: Option<AccountId>, new: Option<AccountId>);
     fn _emit_new_interest_rate_model_event(&self, old: Option<AccountId>, new: Option<AccountId>);
     fn _emit_new_reserve_factor_event(&self, old: WrappedU256, new: WrappedU256);
+    fn _emit_new_minimum_initial_deposit_event(&self, old: Balance, new: Balance);
+    fn _emit_new_max_stable_borrow_size_event(&self, old: WrappedU256, new: WrappedU256);
     fn _emit_delegate_approval_event(
         &self,
         owner: AccountId,
@@ -319,6 +448,12 @@ pub trait Internal {
     fn _emit_reserve_used_as_collateral_enabled_event(&self, user: AccountId);
     fn _emit_reserve_used_as_collateral_disabled_event(&self, user: AccountId);
     fn _emit_manager_updated_event(&self, old: AccountId, new: AccountId);
+    fn _emit_swap_borrow_rate_mode_event(&self, borrower: AccountId, rate_mode: BorrowRateMode);
+    fn _emit_rebalance_stable_borrow_rate_event(
+        &self,
+        borrower: AccountId,
+        stable_rate: WrappedU256,
+    );
 }
 
 #[modifier_definition]
@@ -368,12 +503,15 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
 
     default fn mint(&mut self, mint_amount: Balance) -> Result<()> {
         self._accrue_interest()?;
-        self._mint(Self::env().caller(), mint_amount)
+        let caller = Self::env().caller();
+        self._mint(caller, mint_amount)?;
+        self._update_liquidation_auction(caller)
     }
 
     default fn mint_to(&mut self, mint_account: AccountId, mint_amount: Balance) -> Result<()> {
         self._accrue_interest()?;
-        self._mint(mint_account, mint_amount)
+        self._mint(mint_account, mint_amount)?;
+        self._update_liquidation_auction(mint_account)
     }
 
     default fn get_accrual_block_timestamp(&self) -> Timestamp {
@@ -382,25 +520,33 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
 
     default fn redeem_underlying(&mut self, redeem_amount: Balance) -> Result<()> {
         self._accrue_interest()?;
-        self._redeem(Self::env().caller(), redeem_amount)
+        let caller = Self::env().caller();
+        self._redeem(caller, redeem_amount)?;
+        self._update_liquidation_auction(caller)
     }
 
     default fn redeem_all(&mut self) -> Result<()> {
         self._accrue_interest()?;
         let caller = Self::env().caller();
         let all_tokens_redeemed = self._balance_of(&caller);
-        self._redeem(caller, all_tokens_redeemed)
+        self._redeem(caller, all_tokens_redeemed)?;
+        self._update_liquidation_auction(caller)
     }
 
     default fn borrow(&mut self, borrow_amount: Balance) -> Result<()> {
         self._accrue_interest()?;
-        self._borrow(Self::env().caller(), borrow_amount, true)
+        self._borrow(
+            Self::env().caller(),
+            borrow_amount,
+            true,
+            BorrowRateMode::Variable,
+        )
     }
 
     #[modifiers(delegated_allowed(borrower, borrow_amount))]
     default fn borrow_for(&mut self, borrower: AccountId, borrow_amount: Balance) -> Result<()> {
         self._accrue_interest()?;
-        self._borrow(borrower, borrow_amount, true)?;
+        self._borrow(borrower, borrow_amount, true, BorrowRateMode::Variable)?;
 
         let delegatee = Self::env().caller();
         let delegate_allowance = self._delegate_allowance(&borrower, &delegatee);
@@ -414,19 +560,28 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         borrow_amount: Balance,
     ) -> Result<()> {
         self._accrue_interest()?;
-        self._borrow(borrower, borrow_amount, false)
+        self._borrow(borrower, borrow_amount, false, BorrowRateMode::Variable)
+    }
+
+    default fn borrow_with_rate_mode(
+        &mut self,
+        borrow_amount: Balance,
+        rate_mode: BorrowRateMode,
+    ) -> Result<()> {
+        self._accrue_interest()?;
+        self._borrow(Self::env().caller(), borrow_amount, true, rate_mode)
     }
 
     default fn repay_borrow(&mut self, repay_amount: Balance) -> Result<()> {
         self._accrue_interest()?;
         self._repay_borrow(Self::env().caller(), Self::env().caller(), repay_amount)?;
-        Ok(())
+        self._update_liquidation_auction(Self::env().caller())
     }
 
     default fn repay_borrow_all(&mut self) -> Result<()> {
         self._accrue_interest()?;
         self._repay_borrow(Self::env().caller(), Self::env().caller(), u128::MAX)?;
-        Ok(())
+        self._update_liquidation_auction(Self::env().caller())
     }
 
     default fn repay_borrow_behalf(
@@ -436,7 +591,17 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
     ) -> Result<()> {
         self._accrue_interest()?;
         self._repay_borrow(Self::env().caller(), borrower, repay_amount)?;
-        Ok(())
+        self._update_liquidation_auction(borrower)
+    }
+
+    default fn swap_borrow_rate_mode(&mut self, rate_mode: BorrowRateMode) -> Result<()> {
+        self._accrue_interest()?;
+        self._swap_borrow_rate_mode(Self::env().caller(), rate_mode)
+    }
+
+    default fn rebalance_stable_borrow_rate(&mut self, user: AccountId) -> Result<()> {
+        self._accrue_interest()?;
+        self._rebalance_stable_borrow_rate(user)
     }
 
     default fn liquidate_borrow(
@@ -444,12 +609,20 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         borrower: AccountId,
         repay_amount: Balance,
         collateral: AccountId,
+        receive_underlying: bool,
     ) -> Result<()> {
         self._accrue_interest()?;
         if collateral != Self::env().account_id() {
             PoolRef::accrue_interest(&collateral)?;
         }
-        self._liquidate_borrow(Self::env().caller(), borrower, repay_amount, collateral)
+        self._liquidate_borrow(
+            Self::env().caller(),
+            borrower,
+            repay_amount,
+            collateral,
+            receive_underlying,
+        )?;
+        self._update_liquidation_auction(borrower)
     }
 
     default fn seize(
@@ -457,9 +630,22 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         liquidator: AccountId,
         borrower: AccountId,
         seize_tokens: Balance,
+        receive_underlying: bool,
     ) -> Result<()> {
         self._accrue_interest()?;
-        self._seize(Self::env().caller(), liquidator, borrower, seize_tokens)
+        self._seize(
+            Self::env().caller(),
+            liquidator,
+            borrower,
+            seize_tokens,
+            receive_underlying,
+        )
+    }
+
+    default fn settle_bad_debt(&mut self, borrower: AccountId) -> Result<()> {
+        self._accrue_interest()?;
+        self._settle_bad_debt(borrower)?;
+        Ok(())
     }
 
     #[modifiers(only_flashloan_gateway)]
@@ -468,6 +654,12 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         self._transfer_underlying(to, amount)
     }
 
+    #[modifiers(only_flashloan_gateway)]
+    default fn repay_flashloan(&mut self, amount: Balance) -> Result<()> {
+        self._accrue_interest()?;
+        self._transfer_underlying_from(Self::env().caller(), Self::env().account_id(), amount)
+    }
+
     default fn set_controller(&mut self, new_controller: AccountId) -> Result<()> {
         self._assert_manager()?;
         let old = self._controller();
@@ -500,11 +692,27 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         Ok(())
     }
 
+    default fn set_minimum_initial_deposit(
+        &mut self,
+        new_minimum_initial_deposit: Balance,
+    ) -> Result<()> {
+        self._assert_manager()?;
+        let old = self._minimum_initial_deposit();
+        self._set_minimum_initial_deposit(new_minimum_initial_deposit)?;
+        self._emit_new_minimum_initial_deposit_event(old, new_minimum_initial_deposit);
+        Ok(())
+    }
+
     default fn add_reserves(&mut self, amount: Balance) -> Result<()> {
         self._accrue_interest()?;
         self._add_reserves(amount)
     }
 
+    default fn cover_deficit(&mut self, amount: Balance) -> Result<()> {
+        self._accrue_interest()?;
+        self._cover_deficit(amount)
+    }
+
     default fn reduce_reserves(&mut self, amount: Balance) -> Result<()> {
         self._assert_manager()?;
         self._accrue_interest()?;
@@ -516,11 +724,33 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         self._sweep_token(asset)
     }
 
+    default fn skim(&mut self) -> Result<()> {
+        self._assert_manager()?;
+        self._skim(Self::env().caller())?;
+        Ok(())
+    }
+
     default fn set_liquidation_threshold(&mut self, new_liquidation_threshold: u128) -> Result<()> {
         self._assert_manager()?;
         self._set_liquidation_threshold(new_liquidation_threshold)
     }
 
+    default fn set_socialize_bad_debt(&mut self, socialize: bool) -> Result<()> {
+        self._assert_manager()?;
+        self._set_socialize_bad_debt(socialize)
+    }
+
+    default fn set_max_stable_borrow_size_mantissa(
+        &mut self,
+        new_max_stable_borrow_size_mantissa: WrappedU256,
+    ) -> Result<()> {
+        self._assert_manager()?;
+        let old = self._max_stable_borrow_size_mantissa();
+        self._set_max_stable_borrow_size_mantissa(new_max_stable_borrow_size_mantissa)?;
+        self._emit_new_max_stable_borrow_size_event(old, new_max_stable_borrow_size_mantissa);
+        Ok(())
+    }
+
     default fn approve_delegate(&mut self, delegatee: AccountId, amount: Balance) -> Result<()> {
         self._approve_delegate(Self::env().caller(), delegatee, amount)
     }
@@ -610,6 +840,11 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         Ok(self.exchange_rate_stored())
     }
 
+    default fn exchange_rate_at(&self, at: Option<Timestamp>) -> Result<WrappedU256> {
+        let (_, interest) = self._get_projected_interest(at)?;
+        Ok(WrappedU256::from(self._projected_exchange_rate(&interest)))
+    }
+
     default fn get_cash_prior(&self) -> Balance {
         self._get_cash_prior()
     }
@@ -618,10 +853,30 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         self._total_borrows()
     }
 
+    default fn borrowers_count(&self) -> u32 {
+        self._borrowers_count()
+    }
+
+    default fn borrowers(&self, start: u32, limit: u32) -> Vec<AccountId> {
+        self._borrowers(start, limit)
+    }
+
+    default fn total_stable_borrows(&self) -> Balance {
+        self._total_stable_borrows()
+    }
+
+    default fn average_stable_borrow_rate(&self) -> WrappedU256 {
+        self._average_stable_borrow_rate()
+    }
+
     default fn total_reserves(&self) -> Balance {
         self._total_reserves()
     }
 
+    default fn deficit(&self) -> Balance {
+        self._deficit()
+    }
+
     default fn balance_of_underlying(&self, account: AccountId) -> Balance {
         self._balance_of_underlying(account)
     }
@@ -650,11 +905,57 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         self._borrow_balance_stored(account)
     }
 
+    default fn stable_borrow_balance_stored(&self, account: AccountId) -> Balance {
+        self._stable_borrow_balance_stored(account)
+    }
+
+    default fn stable_borrow_rate_of(&self, account: AccountId) -> WrappedU256 {
+        self._stable_borrow_rate_of(account)
+    }
+
     default fn borrow_balance_current(&mut self, account: AccountId) -> Result<Balance> {
         self._accrue_interest()?;
         Ok(self._borrow_balance_stored(account))
     }
 
+    default fn get_account_snapshot_at(
+        &self,
+        account: AccountId,
+        at: Option<Timestamp>,
+    ) -> Result<(Balance, Balance, U256)> {
+        let (at, interest) = self._get_projected_interest(at)?;
+        let borrow_balance = self._projected_borrow_balance(account, at, &interest);
+        let exchange_rate = self._projected_exchange_rate(&interest);
+        if self._using_reserve_as_collateral(account).unwrap_or(false) {
+            return Ok((self._balance_of(&account), borrow_balance, exchange_rate))
+        }
+        Ok((0, borrow_balance, exchange_rate))
+    }
+
+    default fn borrow_balance_at(
+        &self,
+        account: AccountId,
+        at: Option<Timestamp>,
+    ) -> Result<Balance> {
+        let (at, interest) = self._get_projected_interest(at)?;
+        Ok(self._projected_borrow_balance(account, at, &interest))
+    }
+
+    default fn total_borrows_at(&self, at: Option<Timestamp>) -> Result<Balance> {
+        let (_, interest) = self._get_projected_interest(at)?;
+        Ok(interest.total_borrows)
+    }
+
+    default fn total_reserves_at(&self, at: Option<Timestamp>) -> Result<Balance> {
+        let (_, interest) = self._get_projected_interest(at)?;
+        Ok(interest.total_reserves)
+    }
+
+    default fn borrow_index_at(&self, at: Option<Timestamp>) -> Result<WrappedU256> {
+        let (_, interest) = self._get_projected_interest(at)?;
+        Ok(WrappedU256::from(interest.borrow_index))
+    }
+
     default fn borrow_rate_per_msec(&self) -> WrappedU256 {
         let cash = self._get_cash_prior();
         let borrows = self._total_borrows();
@@ -662,6 +963,13 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         self._borrow_rate_per_msec(cash, borrows, reserves)
     }
 
+    default fn stable_borrow_rate_per_msec(&self) -> WrappedU256 {
+        let cash = self._get_cash_prior();
+        let borrows = self._total_borrows();
+        let reserves = self._total_reserves();
+        self._stable_borrow_rate_per_msec(cash, borrows, reserves)
+    }
+
     default fn supply_rate_per_msec(&self) -> WrappedU256 {
         let cash = self._get_cash_prior();
         let borrows = self._total_borrows();
@@ -678,6 +986,18 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         self._reserve_factor_mantissa()
     }
 
+    default fn minimum_initial_deposit(&self) -> Balance {
+        self._minimum_initial_deposit()
+    }
+
+    default fn socialize_bad_debt(&self) -> bool {
+        self._socialize_bad_debt()
+    }
+
+    default fn max_stable_borrow_size_mantissa(&self) -> WrappedU256 {
+        self._max_stable_borrow_size_mantissa()
+    }
+
     default fn liquidation_threshold(&self) -> u128 {
         self._liquidation_threshold()
     }
@@ -725,6 +1045,7 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         data.accrual_block_timestamp = at;
         data.borrow_index = out.borrow_index.into();
         data.total_borrows = out.total_borrows;
+        data.total_stable_borrows = out.total_stable_borrows;
         data.total_reserves = out.total_reserves;
         self._emit_accrue_interest_event(
             out.interest_accumulated,
@@ -746,15 +1067,73 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
             InterestRateModelRef::get_borrow_rate(&rate_model, cash, borrows, reserves);
         calculate_interest(&CalculateInterestInput {
             total_borrows: borrows,
+            total_stable_borrows: self._total_stable_borrows(),
             total_reserves: reserves,
             borrow_index: idx.into(),
             borrow_rate: borrow_rate.into(),
+            average_stable_borrow_rate: self._average_stable_borrow_rate().into(),
             old_block_timestamp: self._accrual_block_timestamp(),
             new_block_timestamp: at,
             reserve_factor_mantissa: self._reserve_factor_mantissa().into(),
         })
     }
 
+    default fn _get_projected_interest(
+        &self,
+        at: Option<Timestamp>,
+    ) -> Result<(Timestamp, CalculateInterestOutput)> {
+        let at = at.unwrap_or(Self::env().block_timestamp());
+        // interest can not be projected before the last accrual
+        if at < self._accrual_block_timestamp() {
+            return Err(Error::InvalidParameter)
+        }
+        Ok((at, self._get_interest_at(at)?))
+    }
+
+    default fn _projected_exchange_rate(&self, interest: &CalculateInterestOutput) -> U256 {
+        exchange_rate(
+            self.data::<PSP22Data>().supply,
+            self._get_cash_prior(),
+            interest.total_borrows + self._deficit(),
+            interest.total_reserves,
+            U256::from(self._initial_exchange_rate_mantissa()),
+        )
+    }
+
+    default fn _projected_borrow_balance(
+        &self,
+        account: AccountId,
+        at: Timestamp,
+        interest: &CalculateInterestOutput,
+    ) -> Balance {
+        let snapshot = self
+            .data::<Data>()
+            .account_borrows
+            .get(&account)
+            .unwrap_or_default();
+        let variable_balance = if snapshot.principal == 0 {
+            0
+        } else {
+            U256::from(snapshot.principal)
+                .mul(interest.borrow_index)
+                .div(U256::from(snapshot.interest_index))
+                .as_u128()
+        };
+
+        let stable_snapshot = self
+            .data::<Data>()
+            .account_stable_borrows
+            .get(&account)
+            .unwrap_or_default();
+        let stable_balance = stable_borrow_balance(
+            stable_snapshot.principal,
+            stable_snapshot.stable_rate.into(),
+            at.saturating_sub(stable_snapshot.last_updated_timestamp),
+        );
+
+        variable_balance + stable_balance
+    }
+
     default fn _transfer_tokens(
         &mut self,
         spender: AccountId,
@@ -797,6 +1176,7 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
             account_borrow_balance,
             exchange_rate,
             total_borrows: self._total_borrows(),
+            total_supply: self._total_supply(),
         };
 
         let controller = self
@@ -828,6 +1208,19 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
             self._transfer_from_to(src, dst, value, data)?;
         }
 
+        if self._update_account_market(src).is_err() || self._update_account_market(dst).is_err() {
+            return Err(PSP22Error::Custom(String::from(
+                "UpdateAccountMarketFailed",
+            )))
+        }
+        if self._update_liquidation_auction(src).is_err()
+            || self._update_liquidation_auction(dst).is_err()
+        {
+            return Err(PSP22Error::Custom(String::from(
+                "UpdateLiquidationAuctionFailed",
+            )))
+        }
+
         Ok(())
     }
 
@@ -836,11 +1229,30 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         let contract_addr = Self::env().account_id();
 
         let controller = self._controller().ok_or(Error::ControllerIsNotSet)?;
-        ControllerRef::mint_allowed_builder(&controller, contract_addr, minter, mint_amount)
-            .call_flags(ink_env::CallFlags::default().set_allow_reentry(true))
-            .try_invoke()
-            .unwrap()
-            .unwrap()?;
+        let (account_balance, account_borrow_balance, exchange_rate) =
+            self.get_account_snapshot(minter)?;
+        let pool_attribute = PoolAttributes {
+            pool: Some(contract_addr),
+            underlying: self._underlying(),
+            decimals: self.token_decimals(),
+            liquidation_threshold: self._liquidation_threshold(),
+            account_balance,
+            account_borrow_balance,
+            exchange_rate,
+            total_borrows: self._total_borrows(),
+            total_supply: self._total_supply(),
+        };
+        ControllerRef::mint_allowed_builder(
+            &controller,
+            contract_addr,
+            minter,
+            mint_amount,
+            Some(pool_attribute),
+        )
+        .call_flags(ink_env::CallFlags::default().set_allow_reentry(true))
+        .try_invoke()
+        .unwrap()
+        .unwrap()?;
 
         let current_block_timestamp = Self::env().block_timestamp();
         if self._accrual_block_timestamp() != current_block_timestamp {
@@ -850,19 +1262,39 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         let exchange_rate = self._exchange_rate_stored(); // NOTE: need exchange_rate calculation before transfer underlying
         let caller = Self::env().caller();
 
-        self._transfer_underlying_from(caller, contract_addr, mint_amount)?;
-        let minted_tokens = U256::from(mint_amount)
+        let mut minted_tokens = U256::from(mint_amount)
             .mul(exp_scale())
             .div(exchange_rate)
             .as_u128();
+        if minted_tokens == 0 {
+            return Err(Error::MintedTokensIsZero)
+        }
+        // The first mint into the pool locks dead shares, not to let the exchange rate be manipulated by rounding
+        let is_initial_deposit = self._total_supply() == 0;
+        if is_initial_deposit {
+            if mint_amount < self._minimum_initial_deposit() || minted_tokens <= DEAD_SHARES {
+                return Err(Error::InsufficientInitialDeposit)
+            }
+            minted_tokens -= DEAD_SHARES;
+        }
+
+        self._transfer_underlying_from(caller, contract_addr, mint_amount)?;
+        if is_initial_deposit {
+            self._mint_to(AccountId::from(DEAD_SHARES_HOLDER), DEAD_SHARES)?;
+        }
 
         // Check if it is first deposit.
+        // The deposit is not used as collateral if it conflicts with the isolated collateral of the minter.
         let lp_balance = self._balance_of(&minter);
-        if lp_balance == 0 {
+        if lp_balance == 0
+            && ControllerRef::use_reserve_as_collateral_allowed(&controller, contract_addr, minter)
+                .is_ok()
+        {
             self._set_use_reserve_as_collateral(minter, true);
         }
 
         self._mint_to(minter, minted_tokens)?;
+        self._update_account_market(minter)?;
         self._emit_mint_event(minter, mint_amount, minted_tokens);
 
         Ok(())
@@ -892,6 +1324,7 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
             account_borrow_balance,
             exchange_rate,
             total_borrows: self._total_borrows(),
+            total_supply: self._total_supply(),
         };
         ControllerRef::redeem_allowed(
             &controller,
@@ -915,6 +1348,7 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         }
 
         self._burn_from(redeemer, redeem_amount)?;
+        self._update_account_market(redeemer)?;
         self._transfer_underlying(redeemer, redeem_amount)?;
 
         self._emit_redeem_event(redeemer, redeem_amount);
@@ -927,6 +1361,7 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         borrower: AccountId,
         borrow_amount: Balance,
         release_underlying: bool,
+        rate_mode: BorrowRateMode,
     ) -> Result<()> {
         self._accrue_reward(borrower)?;
 
@@ -944,6 +1379,7 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
             account_borrow_balance,
             exchange_rate,
             total_borrows: self._total_borrows(),
+            total_supply: self._total_supply(),
             liquidation_threshold: self._liquidation_threshold(),
         };
 
@@ -967,16 +1403,31 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         let account_borrows_new = account_borrows_prev + borrow_amount;
         let total_borrows_new = self._total_borrows() + borrow_amount;
 
-        let borrow_index = self._borrow_index();
-
-        self.data::<Data>().account_borrows.insert(
-            &borrower,
-            &BorrowSnapshot {
-                principal: account_borrows_new,
-                interest_index: borrow_index,
-            },
-        );
+        match rate_mode {
+            BorrowRateMode::Variable => {
+                let borrow_index = self._borrow_index();
+                let variable_borrows_new =
+                    self._variable_borrow_balance_stored(borrower) + borrow_amount;
+                self.data::<Data>().account_borrows.insert(
+                    &borrower,
+                    &BorrowSnapshot {
+                        principal: variable_borrows_new,
+                        interest_index: borrow_index,
+                    },
+                );
+            }
+            BorrowRateMode::Stable => {
+                // the rate is fixed from the pool state before this borrow,
+                // so a single borrow cannot take most of the liquidity at it
+                self._assert_stable_borrow_size(borrow_amount)?;
+                let stable_rate = self.stable_borrow_rate_per_msec();
+                self._increase_stable_borrow(borrower, borrow_amount, stable_rate);
+            }
+        }
         self.data::<Data>().total_borrows = total_borrows_new;
+        self._add_borrower(borrower);
+        self._update_account_market(borrower)?;
+        self._update_isolated_debt(borrower, account_borrows_prev, account_borrows_new)?;
 
         if release_underlying {
             self._transfer_underlying(caller, borrow_amount)?;
@@ -1002,11 +1453,21 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         self._accrue_reward(payer)?;
         let contract_addr = Self::env().account_id();
 
+        let controller = self._controller().ok_or(Error::ControllerIsNotSet)?;
+        ControllerRef::repay_borrow_allowed(
+            &controller,
+            contract_addr,
+            payer,
+            borrower,
+            repay_amount,
+        )?;
+
         let current_block_timestamp = Self::env().block_timestamp();
         if self._accrual_block_timestamp() != current_block_timestamp {
             return Err(Error::AccrualBlockNumberIsNotFresh)
         };
 
+        let variable_borrow_prev = self._variable_borrow_balance_stored(borrower);
         let account_borrow_prev = self._borrow_balance_stored(borrower);
         let repay_amount_final = if repay_amount > account_borrow_prev {
             account_borrow_prev
@@ -1019,16 +1480,28 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         let account_borrows_new = account_borrow_prev - repay_amount_final;
         let total_borrows_new = self._total_borrows() - repay_amount_final;
 
+        // variable rate debt is repaid first, the rest goes to stable rate debt
+        let variable_repay_amount = repay_amount_final.min(variable_borrow_prev);
+        let stable_repay_amount = repay_amount_final - variable_repay_amount;
+
         let borrow_index = self._borrow_index();
 
         self.data::<Data>().account_borrows.insert(
             &borrower,
             &BorrowSnapshot {
-                principal: account_borrows_new,
+                principal: variable_borrow_prev - variable_repay_amount,
                 interest_index: borrow_index,
             },
         );
+        if stable_repay_amount > 0 {
+            self._decrease_stable_borrow(borrower, stable_repay_amount);
+        }
         self.data::<Data>().total_borrows = total_borrows_new;
+        if account_borrows_new == 0 {
+            self._remove_borrower(borrower);
+            self._update_account_market(borrower)?;
+        }
+        self._update_isolated_debt(borrower, account_borrow_prev, account_borrows_new)?;
 
         self._emit_repay_borrow_event(
             payer,
@@ -1041,12 +1514,190 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         Ok(repay_amount_final)
     }
 
+    default fn _swap_borrow_rate_mode(
+        &mut self,
+        borrower: AccountId,
+        rate_mode: BorrowRateMode,
+    ) -> Result<()> {
+        let current_block_timestamp = Self::env().block_timestamp();
+        if self._accrual_block_timestamp() != current_block_timestamp {
+            return Err(Error::AccrualBlockNumberIsNotFresh)
+        };
+
+        let variable_borrow = self._variable_borrow_balance_stored(borrower);
+        let stable_borrow = self._stable_borrow_balance_stored(borrower);
+        let borrow_index = self._borrow_index();
+        match rate_mode {
+            BorrowRateMode::Stable => {
+                if stable_borrow == 0 {
+                    return Err(Error::NoDebtOfSelectedType)
+                }
+                self._decrease_stable_borrow(borrower, stable_borrow);
+                self.data::<Data>().account_borrows.insert(
+                    &borrower,
+                    &BorrowSnapshot {
+                        principal: variable_borrow + stable_borrow,
+                        interest_index: borrow_index,
+                    },
+                );
+            }
+            BorrowRateMode::Variable => {
+                if variable_borrow == 0 {
+                    return Err(Error::NoDebtOfSelectedType)
+                }
+                self._assert_stable_borrow_size(variable_borrow)?;
+                let stable_rate = self.stable_borrow_rate_per_msec();
+                self.data::<Data>().account_borrows.insert(
+                    &borrower,
+                    &BorrowSnapshot {
+                        principal: 0,
+                        interest_index: borrow_index,
+                    },
+                );
+                self._increase_stable_borrow(borrower, variable_borrow, stable_rate);
+            }
+        }
+
+        self._emit_swap_borrow_rate_mode_event(borrower, rate_mode);
+        Ok(())
+    }
+
+    default fn _rebalance_stable_borrow_rate(&mut self, borrower: AccountId) -> Result<()> {
+        let current_block_timestamp = Self::env().block_timestamp();
+        if self._accrual_block_timestamp() != current_block_timestamp {
+            return Err(Error::AccrualBlockNumberIsNotFresh)
+        };
+
+        let stable_borrow = self._stable_borrow_balance_stored(borrower);
+        if stable_borrow == 0 {
+            return Err(Error::NoDebtOfSelectedType)
+        }
+
+        let cash = self._get_cash_prior();
+        let borrows = self._total_borrows();
+        let reserves = self._total_reserves();
+        let supply_rate =
+            self._supply_rate_per_msec(cash, borrows, reserves, self._reserve_factor_mantissa());
+        let current_stable_rate = self._stable_borrow_rate_per_msec(cash, borrows, reserves);
+        let user_stable_rate = U256::from(self._stable_borrow_rate_of(borrower));
+
+        // rebalance up when suppliers earn more than the borrower pays,
+        // or down when the borrower pays more than a new stable borrow would
+        if user_stable_rate >= U256::from(supply_rate)
+            && user_stable_rate <= U256::from(current_stable_rate)
+        {
+            return Err(Error::RebalanceStableBorrowRateConditionsNotMet)
+        }
+
+        self._decrease_stable_borrow(borrower, stable_borrow);
+        self._increase_stable_borrow(borrower, stable_borrow, current_stable_rate);
+
+        self._emit_rebalance_stable_borrow_rate_event(borrower, current_stable_rate);
+        Ok(())
+    }
+
+    default fn _increase_stable_borrow(
+        &mut self,
+        borrower: AccountId,
+        amount: Balance,
+        rate: WrappedU256,
+    ) {
+        let account_stable_borrow = self._stable_borrow_balance_stored(borrower);
+        let account_stable_rate_new = average_stable_rate_on_increase(
+            account_stable_borrow,
+            self._stable_borrow_rate_of(borrower).into(),
+            amount,
+            rate.into(),
+        );
+        let total_stable_borrows = self._total_stable_borrows();
+        let average_stable_borrow_rate_new = average_stable_rate_on_increase(
+            total_stable_borrows,
+            self._average_stable_borrow_rate().into(),
+            amount,
+            rate.into(),
+        );
+        let accrual_block_timestamp = self._accrual_block_timestamp();
+
+        let mut data = self.data::<Data>();
+        data.account_stable_borrows.insert(
+            &borrower,
+            &StableBorrowSnapshot {
+                principal: account_stable_borrow + amount,
+                stable_rate: account_stable_rate_new.into(),
+                last_updated_timestamp: accrual_block_timestamp,
+            },
+        );
+        data.total_stable_borrows = total_stable_borrows + amount;
+        data.average_stable_borrow_rate = average_stable_borrow_rate_new.into();
+    }
+
+    default fn _decrease_stable_borrow(&mut self, borrower: AccountId, amount: Balance) {
+        let account_stable_borrow = self._stable_borrow_balance_stored(borrower);
+        let account_stable_rate = self._stable_borrow_rate_of(borrower);
+        let account_stable_borrow_new = account_stable_borrow.saturating_sub(amount);
+        let total_stable_borrows = self._total_stable_borrows();
+        let average_stable_borrow_rate_new = average_stable_rate_on_decrease(
+            total_stable_borrows,
+            self._average_stable_borrow_rate().into(),
+            amount,
+            account_stable_rate.into(),
+        );
+        let accrual_block_timestamp = self._accrual_block_timestamp();
+
+        let mut data = self.data::<Data>();
+        data.account_stable_borrows.insert(
+            &borrower,
+            &StableBorrowSnapshot {
+                principal: account_stable_borrow_new,
+                stable_rate: if account_stable_borrow_new == 0 {
+                    WrappedU256::from(U256::zero())
+                } else {
+                    account_stable_rate
+                },
+                last_updated_timestamp: accrual_block_timestamp,
+            },
+        );
+        // the sum of the accounts can exceed the total by rounding, so this saturates
+        data.total_stable_borrows = total_stable_borrows.saturating_sub(amount);
+        data.average_stable_borrow_rate = average_stable_borrow_rate_new.into();
+    }
+
+    default fn _add_borrower(&mut self, borrower: AccountId) {
+        let mut data = self.data::<Data>();
+        if data.borrower_positions.get(&borrower).is_some() {
+            return
+        }
+        let position = data.borrowers_count;
+        data.borrowers.insert(&position, &borrower);
+        data.borrower_positions.insert(&borrower, &position);
+        data.borrowers_count = position + 1;
+    }
+
+    default fn _remove_borrower(&mut self, borrower: AccountId) {
+        let mut data = self.data::<Data>();
+        let position = match data.borrower_positions.get(&borrower) {
+            Some(position) => position,
+            None => return,
+        };
+        // the last account fills the position of the removed one
+        let last_position = data.borrowers_count - 1;
+        if position != last_position {
+            let last_borrower = data.borrowers.get(&last_position).unwrap();
+            data.borrowers.insert(&position, &last_borrower);
+            data.borrower_positions.insert(&last_borrower, &position);
+        }
+        data.borrowers.remove(&last_position);
+        data.borrower_positions.remove(&borrower);
+        data.borrowers_count = last_position;
+    }
+
     default fn _liquidate_borrow(
         &mut self,
         liquidator: AccountId,
         borrower: AccountId,
         repay_amount: Balance,
         collateral: AccountId,
+        receive_underlying: bool,
     ) -> Result<()> {
         self._accrue_reward(liquidator)?;
         self._accrue_reward(borrower)?;
@@ -1063,6 +1714,7 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
             account_borrow_balance,
             exchange_rate,
             total_borrows: self._total_borrows(),
+            total_supply: self._total_supply(),
             liquidation_threshold: self._liquidation_threshold(),
         };
 
@@ -1103,13 +1755,20 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
                 &controller,
                 contract_addr,
                 collateral,
+                borrower,
                 WrappedU256::from(self._exchange_rate_stored()),
                 actual_repay_amount,
                 pool_borrowed_attributes,
                 pool_collateral_attributes,
             )?;
 
-            self._seize(contract_addr, liquidator, borrower, seize_tokens)?;
+            self._seize(
+                contract_addr,
+                liquidator,
+                borrower,
+                seize_tokens,
+                receive_underlying,
+            )?;
 
             seize_tokens
         } else {
@@ -1117,6 +1776,7 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
                 &controller,
                 contract_addr,
                 collateral,
+                borrower,
                 PoolRef::exchange_rate_stored(&collateral),
                 actual_repay_amount,
                 pool_borrowed_attributes,
@@ -1133,7 +1793,13 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
             if seizer_controller != controller {
                 return Err(Error::from(ControllerError::ControllerMismatch))
             }
-            PoolRef::seize(&collateral, liquidator, borrower, seize_tokens)?;
+            PoolRef::seize(
+                &collateral,
+                liquidator,
+                borrower,
+                seize_tokens,
+                receive_underlying,
+            )?;
 
             seize_tokens
         };
@@ -1146,6 +1812,9 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
             seize_tokens,
         );
 
+        // the debt left after seizing the last collateral is never repaid
+        self._settle_bad_debt(borrower)?;
+
         Ok(())
     }
 
@@ -1155,6 +1824,7 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         liquidator: AccountId,
         borrower: AccountId,
         seize_tokens: Balance,
+        receive_underlying: bool,
     ) -> Result<()> {
         if liquidator == borrower {
             return Err(Error::LiquidateSeizeLiquidatorIsBorrower)
@@ -1183,20 +1853,185 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         let exchange_rate = Exp {
             mantissa: WrappedU256::from(self._exchange_rate_stored()),
         };
+        let protocol_seize_share = U256::from(
+            ControllerRef::market_protocol_seize_share_mantissa(&controller, contract_addr)
+                .unwrap_or_else(|| ControllerRef::protocol_seize_share_mantissa(&controller)),
+        );
         let (liquidator_seize_tokens, protocol_seize_amount, _) =
-            protocol_seize_amount(exchange_rate, seize_tokens, protocol_seize_share_mantissa());
+            protocol_seize_amount(exchange_rate.clone(), seize_tokens, protocol_seize_share);
         let total_reserves_new = self._total_reserves() + protocol_seize_amount;
+        // the tokens of the liquidator are redeemed at once if it receives the underlying
+        let liquidator_seize_amount = underlying_balance(exchange_rate, liquidator_seize_tokens);
+        if receive_underlying && self._get_cash_prior() < liquidator_seize_amount {
+            return Err(Error::SeizeCashNotAvailable)
+        }
 
         // EFFECTS & INTERACTIONS
         self.data::<Data>().total_reserves = total_reserves_new;
         self._burn_from(borrower, seize_tokens)?;
-        self._mint_to(liquidator, liquidator_seize_tokens)?;
+        self._update_account_market(borrower)?;
+        if receive_underlying {
+            self._transfer_underlying(liquidator, liquidator_seize_amount)?;
+        } else {
+            self._mint_to(liquidator, liquidator_seize_tokens)?;
+            self._update_account_market(liquidator)?;
+        }
 
         self._emit_reserves_added_event(contract_addr, protocol_seize_amount, total_reserves_new);
 
         Ok(())
     }
 
+    default fn _update_account_market(&mut self, account: AccountId) -> Result<()> {
+        let entered = self._balance_of(&account) != 0 || self._borrow_balance_stored(account) != 0;
+        let recorded = self
+            .data::<Data>()
+            .account_market_entered
+            .get(&account)
+            .unwrap_or(false);
+        // Controller is called only when the account opens or closes its position
+        if entered == recorded {
+            return Ok(())
+        }
+
+        let controller = self._controller().ok_or(Error::ControllerIsNotSet)?;
+        ControllerRef::update_account_market(&controller, account, entered)?;
+        if entered {
+            self.data::<Data>()
+                .account_market_entered
+                .insert(&account, &true);
+        } else {
+            self.data::<Data>().account_market_entered.remove(&account);
+        }
+        Ok(())
+    }
+
+    default fn _update_liquidation_auction(&mut self, account: AccountId) -> Result<()> {
+        let controller = self._controller().ok_or(Error::ControllerIsNotSet)?;
+        // only the started auction needs to be reset, keepers start it through the Controller
+        if ControllerRef::liquidation_auction_start(&controller, account).is_none() {
+            return Ok(())
+        }
+
+        let (account_balance, account_borrow_balance, exchange_rate) =
+            self.get_account_snapshot(account)?;
+        let pool_attribute = PoolAttributes {
+            pool: Some(Self::env().account_id()),
+            underlying: self._underlying(),
+            decimals: self.token_decimals(),
+            liquidation_threshold: self._liquidation_threshold(),
+            account_balance,
+            account_borrow_balance,
+            exchange_rate,
+            total_borrows: self._total_borrows(),
+            total_supply: self._total_supply(),
+        };
+        ControllerRef::update_liquidation_auction(&controller, account, Some(pool_attribute))?;
+        Ok(())
+    }
+
+    default fn _update_isolated_debt(
+        &mut self,
+        account: AccountId,
+        borrow_balance_prev: Balance,
+        borrow_balance_new: Balance,
+    ) -> Result<()> {
+        if borrow_balance_prev == borrow_balance_new {
+            return Ok(())
+        }
+        let controller = self._controller().ok_or(Error::ControllerIsNotSet)?;
+        ControllerRef::update_isolated_debt(
+            &controller,
+            account,
+            borrow_balance_prev,
+            borrow_balance_new,
+        )?;
+        Ok(())
+    }
+
+    default fn _settle_bad_debt(&mut self, borrower: AccountId) -> Result<Balance> {
+        let (account_balance, account_borrow_balance, exchange_rate) =
+            self.get_account_snapshot(borrower)?;
+        if account_borrow_balance == 0 {
+            return Ok(0)
+        }
+
+        let controller = self._controller().ok_or(Error::ControllerIsNotSet)?;
+        let pool_attribute = PoolAttributes {
+            pool: Some(Self::env().account_id()),
+            underlying: self._underlying(),
+            decimals: self.token_decimals(),
+            liquidation_threshold: self._liquidation_threshold(),
+            account_balance,
+            account_borrow_balance,
+            exchange_rate,
+            total_borrows: self._total_borrows(),
+            total_supply: self._total_supply(),
+        };
+        let account_data = ControllerRef::calculate_user_account_data(
+            &controller,
+            borrower,
+            Some(pool_attribute),
+        )?;
+        if !account_data.total_collateral_in_base_currency.is_zero() {
+            return Ok(0)
+        }
+
+        // write off the whole debt of the borrower
+        let bad_debt = account_borrow_balance;
+        self._write_off_bad_debt(borrower, bad_debt)?;
+        Ok(bad_debt)
+    }
+
+    default fn _write_off_bad_debt(
+        &mut self,
+        borrower: AccountId,
+        bad_debt: Balance,
+    ) -> Result<()> {
+        let account_borrow_prev = self._borrow_balance_stored(borrower);
+        let stable_borrow = self._stable_borrow_balance_stored(borrower);
+        self.data::<Data>().account_borrows.insert(
+            &borrower,
+            &BorrowSnapshot {
+                principal: 0,
+                interest_index: self._borrow_index(),
+            },
+        );
+        if stable_borrow > 0 {
+            self._decrease_stable_borrow(borrower, stable_borrow);
+        }
+        self.data::<Data>().total_borrows = self._total_borrows().saturating_sub(bad_debt);
+        self._remove_borrower(borrower);
+        self._update_account_market(borrower)?;
+        self._update_isolated_debt(borrower, account_borrow_prev, 0)?;
+        self._emit_bad_debt_detected_event(borrower, bad_debt);
+
+        let covered_amount = bad_debt.min(self._total_reserves());
+        if covered_amount > 0 {
+            let total_reserves_new = self._total_reserves() - covered_amount;
+            self.data::<Data>().total_reserves = total_reserves_new;
+            self._emit_bad_debt_covered_by_reserves_event(
+                borrower,
+                covered_amount,
+                total_reserves_new,
+            );
+        }
+
+        let uncovered_amount = bad_debt - covered_amount;
+        if uncovered_amount > 0 {
+            if self._socialize_bad_debt() {
+                // lowers the exchange rate, as the debt is no longer counted as an asset
+                self._emit_bad_debt_socialized_event(borrower, uncovered_amount);
+            } else {
+                let deficit_new = self._deficit() + uncovered_amount;
+                self.data::<Data>().deficit = deficit_new;
+                self._emit_deficit_recorded_event(borrower, uncovered_amount, deficit_new);
+            }
+        }
+
+        Ok(())
+    }
+
     // admin functions
     default fn _set_controller(&mut self, new_controller: AccountId) -> Result<()> {
         self.data::<Data>().controller = Some(new_controller);
@@ -1222,6 +2057,14 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         Ok(())
     }
 
+    default fn _set_minimum_initial_deposit(
+        &mut self,
+        new_minimum_initial_deposit: Balance,
+    ) -> Result<()> {
+        self.data::<Data>().minimum_initial_deposit = new_minimum_initial_deposit;
+        Ok(())
+    }
+
     default fn _set_interest_rate_model(
         &mut self,
         new_interest_rate_model: AccountId,
@@ -1253,6 +2096,25 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         Ok(())
     }
 
+    default fn _cover_deficit(&mut self, amount: Balance) -> Result<()> {
+        let current_block_timestamp = Self::env().block_timestamp();
+        if self._accrual_block_timestamp() != current_block_timestamp {
+            return Err(Error::AccrualBlockNumberIsNotFresh)
+        }
+
+        let covered_amount = amount.min(self._deficit());
+        if covered_amount == 0 {
+            return Ok(())
+        }
+        let deficit_new = self._deficit() - covered_amount;
+        self.data::<Data>().deficit = deficit_new;
+        let caller = Self::env().caller();
+        self._transfer_underlying_from(caller, Self::env().account_id(), covered_amount)?;
+
+        self._emit_deficit_covered_event(caller, covered_amount, deficit_new);
+        Ok(())
+    }
+
     default fn _reduce_reserves(&mut self, admin: AccountId, amount: Balance) -> Result<()> {
         let current_block_timestamp = Self::env().block_timestamp();
         if self._accrual_block_timestamp() != current_block_timestamp {
@@ -1286,6 +2148,19 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         Ok(())
     }
 
+    default fn _skim(&mut self, to: AccountId) -> Result<Balance> {
+        let underlying = self._underlying().ok_or(Error::UnderlyingIsNotSet)?;
+        // only the surplus from direct transfers, the tracked cash is kept in the pool
+        let surplus = self
+            ._underlying_balance_of_pool()
+            .saturating_sub(self._get_cash_prior());
+        if surplus > 0 {
+            PSP22Ref::transfer(&underlying, to, surplus, Vec::<u8>::new())
+                .map_err(to_psp22_error)?;
+        }
+        Ok(surplus)
+    }
+
     default fn _set_liquidation_threshold(
         &mut self,
         new_liquidation_threshold: u128,
@@ -1307,6 +2182,22 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         Ok(())
     }
 
+    default fn _set_socialize_bad_debt(&mut self, socialize: bool) -> Result<()> {
+        self.data::<Data>().socialize_bad_debt = socialize;
+        Ok(())
+    }
+
+    default fn _set_max_stable_borrow_size_mantissa(
+        &mut self,
+        new_max_stable_borrow_size_mantissa: WrappedU256,
+    ) -> Result<()> {
+        if U256::from(new_max_stable_borrow_size_mantissa).gt(&exp_scale()) {
+            return Err(Error::SetMaxStableBorrowSizeBoundsCheck)
+        }
+        self.data::<Data>().max_stable_borrow_size_mantissa = new_max_stable_borrow_size_mantissa;
+        Ok(())
+    }
+
     default fn _approve_delegate(
         &mut self,
         owner: AccountId,
@@ -1354,7 +2245,15 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         user: AccountId,
         use_as_collateral: bool,
     ) -> Result<()> {
-        if use_as_collateral || !self._using_reserve_as_collateral(user).unwrap_or_default() {
+        let using_as_collateral = self._using_reserve_as_collateral(user).unwrap_or_default();
+        if use_as_collateral == using_as_collateral {
+            return Ok(())
+        }
+
+        let controller = self._controller().ok_or(Error::ControllerIsNotSet)?;
+        let contract_addr = Self::env().account_id();
+        if use_as_collateral {
+            ControllerRef::use_reserve_as_collateral_allowed(&controller, contract_addr, user)?;
             return Ok(())
         }
 
@@ -1364,9 +2263,6 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
             return Err(Error::from(PSP22Error::InsufficientBalance))
         }
 
-        let contract_addr = Self::env().account_id();
-
-        let controller = self._controller().ok_or(Error::ControllerIsNotSet)?;
         let pool_attributes: PoolAttributes = PoolAttributes {
             pool: Some(contract_addr),
             underlying: self._underlying(),
@@ -1376,6 +2272,7 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
             account_borrow_balance,
             exchange_rate,
             total_borrows: self._total_borrows(),
+            total_supply: self._total_supply(),
         };
 
         ControllerRef::balance_decrease_allowed(
@@ -1409,7 +2306,7 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
 
     // utilities
     default fn _transfer_underlying_from(
-        &self,
+        &mut self,
         from: AccountId,
         to: AccountId,
         value: Balance,
@@ -1420,11 +2317,21 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
             .try_invoke()
             .unwrap()
             .unwrap()
-            .map_err(to_psp22_error)
+            .map_err(to_psp22_error)?;
+        if to == Self::env().account_id() {
+            let mut data = self.data::<Data>();
+            data.total_cash = data.total_cash.add(value);
+        }
+        Ok(())
     }
 
-    default fn _transfer_underlying(&self, to: AccountId, value: Balance) -> Result<()> {
+    default fn _transfer_underlying(&mut self, to: AccountId, value: Balance) -> Result<()> {
         let underlying = self._underlying().ok_or(Error::UnderlyingIsNotSet)?;
+        let mut data = self.data::<Data>();
+        data.total_cash = data
+            .total_cash
+            .checked_sub(value)
+            .ok_or(Error::InsufficientCash)?;
         PSP22Ref::transfer(&underlying, to, value, Vec::<u8>::new()).map_err(to_psp22_error)
     }
 
@@ -1495,6 +2402,10 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
     }
 
     default fn _get_cash_prior(&self) -> Balance {
+        self.data::<Data>().total_cash
+    }
+
+    default fn _underlying_balance_of_pool(&self) -> Balance {
         if let Some(underlying) = self._underlying() {
             let contract_addr = Self::env().account_id();
             return PSP22Ref::balance_of(&underlying, contract_addr)
@@ -1506,6 +2417,18 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         self.data::<Data>().total_borrows
     }
 
+    default fn _borrowers_count(&self) -> u32 {
+        self.data::<Data>().borrowers_count
+    }
+
+    default fn _borrowers(&self, start: u32, limit: u32) -> Vec<AccountId> {
+        let data = self.data::<Data>();
+        let end = start.saturating_add(limit).min(data.borrowers_count);
+        (start..end)
+            .filter_map(|position| data.borrowers.get(&position))
+            .collect()
+    }
+
     default fn _rate_model(&self) -> Option<AccountId> {
         self.data::<Data>().rate_model
     }
@@ -1523,6 +2446,24 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         WrappedU256::from(U256::zero())
     }
 
+    default fn _stable_borrow_rate_per_msec(
+        &self,
+        cash: Balance,
+        borrows: Balance,
+        reserves: Balance,
+    ) -> WrappedU256 {
+        if let Some(rate_model) = self._rate_model() {
+            return InterestRateModelRef::get_stable_borrow_rate(
+                &rate_model,
+                cash,
+                borrows,
+                reserves,
+            )
+        }
+
+        WrappedU256::from(U256::zero())
+    }
+
     default fn _supply_rate_per_msec(
         &self,
         cash: Balance,
@@ -1551,11 +2492,38 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         self.data::<Data>().total_reserves
     }
 
+    default fn _deficit(&self) -> Balance {
+        self.data::<Data>().deficit
+    }
+
+    default fn _socialize_bad_debt(&self) -> bool {
+        self.data::<Data>().socialize_bad_debt
+    }
+
+    default fn _max_stable_borrow_size_mantissa(&self) -> WrappedU256 {
+        self.data::<Data>().max_stable_borrow_size_mantissa
+    }
+
+    default fn _assert_stable_borrow_size(&self, amount: Balance) -> Result<()> {
+        let max_amount = max_stable_borrow_amount(
+            self._get_cash_prior(),
+            U256::from(self._max_stable_borrow_size_mantissa()),
+        );
+        if amount > max_amount {
+            return Err(Error::MaxStableBorrowSizeExceeded)
+        }
+        Ok(())
+    }
+
     default fn _borrow_index(&self) -> WrappedU256 {
         self.data::<Data>().borrow_index
     }
 
     default fn _borrow_balance_stored(&self, account: AccountId) -> Balance {
+        self._variable_borrow_balance_stored(account) + self._stable_borrow_balance_stored(account)
+    }
+
+    default fn _variable_borrow_balance_stored(&self, account: AccountId) -> Balance {
         let snapshot = self
             .data::<Data>()
             .account_borrows
@@ -1573,6 +2541,37 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
             .as_u128()
     }
 
+    default fn _stable_borrow_balance_stored(&self, account: AccountId) -> Balance {
+        let snapshot = self
+            .data::<Data>()
+            .account_stable_borrows
+            .get(&account)
+            .unwrap_or_default();
+
+        stable_borrow_balance(
+            snapshot.principal,
+            snapshot.stable_rate.into(),
+            self._accrual_block_timestamp()
+                .saturating_sub(snapshot.last_updated_timestamp),
+        )
+    }
+
+    default fn _stable_borrow_rate_of(&self, account: AccountId) -> WrappedU256 {
+        self.data::<Data>()
+            .account_stable_borrows
+            .get(&account)
+            .unwrap_or_default()
+            .stable_rate
+    }
+
+    default fn _total_stable_borrows(&self) -> Balance {
+        self.data::<Data>().total_stable_borrows
+    }
+
+    default fn _average_stable_borrow_rate(&self) -> WrappedU256 {
+        self.data::<Data>().average_stable_borrow_rate
+    }
+
     default fn _balance_of_underlying(&self, account: AccountId) -> Balance {
         let exchange_rate = Exp {
             mantissa: self._exchange_rate_stored().into(),
@@ -1589,11 +2588,15 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
         self.data::<Data>().reserve_factor_mantissa
     }
 
+    default fn _minimum_initial_deposit(&self) -> Balance {
+        self.data::<Data>().minimum_initial_deposit
+    }
+
     default fn _exchange_rate_stored(&self) -> U256 {
         exchange_rate(
             self.data::<PSP22Data>().supply,
             self._get_cash_prior(),
-            self._total_borrows(),
+            self._total_borrows() + self._deficit(),
             self._total_reserves(),
             U256::from(self._initial_exchange_rate_mantissa()),
         )
@@ -1673,6 +2676,34 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
     ) {
     }
 
+    default fn _emit_bad_debt_detected_event(&self, _borrower: AccountId, _amount: Balance) {}
+
+    default fn _emit_bad_debt_covered_by_reserves_event(
+        &self,
+        _borrower: AccountId,
+        _amount: Balance,
+        _total_reserves_new: Balance,
+    ) {
+    }
+
+    default fn _emit_bad_debt_socialized_event(&self, _borrower: AccountId, _amount: Balance) {}
+
+    default fn _emit_deficit_recorded_event(
+        &self,
+        _borrower: AccountId,
+        _amount: Balance,
+        _deficit: Balance,
+    ) {
+    }
+
+    default fn _emit_deficit_covered_event(
+        &self,
+        _benefactor: AccountId,
+        _amount: Balance,
+        _deficit: Balance,
+    ) {
+    }
+
     default fn _emit_new_controller_event(&self, _old: Option<AccountId>, _new: Option<AccountId>) {
     }
     default fn _emit_new_interest_rate_model_event(
@@ -1682,6 +2713,9 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
     ) {
     }
     default fn _emit_new_reserve_factor_event(&self, _old: WrappedU256, _new: WrappedU256) {}
+    default fn _emit_new_minimum_initial_deposit_event(&self, _old: Balance, _new: Balance) {}
+    default fn _emit_new_max_stable_borrow_size_event(&self, _old: WrappedU256, _new: WrappedU256) {
+    }
     default fn _emit_delegate_approval_event(
         &self,
         _owner: AccountId,
@@ -1693,6 +2727,18 @@ impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metada
     default fn _emit_reserve_used_as_collateral_enabled_event(&self, _user: AccountId) {}
     default fn _emit_reserve_used_as_collateral_disabled_event(&self, _user: AccountId) {}
     default fn _emit_manager_updated_event(&self, _old: AccountId, _new: AccountId) {}
+    default fn _emit_swap_borrow_rate_mode_event(
+        &self,
+        _borrower: AccountId,
+        _rate_mode: BorrowRateMode,
+    ) {
+    }
+    default fn _emit_rebalance_stable_borrow_rate_event(
+        &self,
+        _borrower: AccountId,
+        _stable_rate: WrappedU256,
+    ) {
+    }
 }
 
 pub fn to_psp22_error(e: PSP22Error) -> Error {
@@ -1713,6 +2759,7 @@ impl From<controller::Error> for PSP22Error {
             controller::Error::PriceError => convert("PriceError"),
             controller::Error::TooMuchRepay => convert("TooMuchRepay"),
             controller::Error::BorrowCapReached => convert("BorrowCapReached"),
+            controller::Error::SupplyCapReached => convert("SupplyCapReached"),
             controller::Error::InsufficientLiquidity => convert("InsufficientLiquidity"),
             controller::Error::InsufficientShortfall => convert("InsufficientShortfall"),
             controller::Error::CallerIsNotManager => convert("CallerIsNotManager"),
@@ -36,15 +36,31 @@ pub fn reserve_factor_max_mantissa() -> U256 {
     exp_scale()
 }
 
+/// Initial maximum size of a stable rate borrow relative to the available liquidity
+pub fn max_stable_borrow_size_mantissa() -> U256 {
+    exp_scale().mul(U256::from(25)).div(U256::from(100)) // 25%
+}
+
+/// Largest stable rate borrow the available liquidity allows
+pub fn max_stable_borrow_amount(cash: Balance, max_stable_borrow_size_mantissa: U256) -> Balance {
+    U256::from(cash)
+        .mul(max_stable_borrow_size_mantissa)
+        .div(exp_scale())
+        .as_u128()
+}
+
+/// Initial protocol seize share of the Controller
 pub fn protocol_seize_share_mantissa() -> U256 {
     exp_scale().mul(U256::from(28)).div(U256::from(10 * 100)) // 2.8%
 }
 
 pub struct CalculateInterestInput {
     pub total_borrows: Balance,
+    pub total_stable_borrows: Balance,
     pub total_reserves: Balance,
     pub borrow_index: U256,
     pub borrow_rate: U256,
+    pub average_stable_borrow_rate: U256,
     pub old_block_timestamp: Timestamp,
     pub new_block_timestamp: Timestamp,
     pub reserve_factor_mantissa: U256,
@@ -53,6 +69,7 @@ pub struct CalculateInterestInput {
 pub struct CalculateInterestOutput {
     pub borrow_index: U256,
     pub total_borrows: Balance,
+    pub total_stable_borrows: Balance,
     pub total_reserves: Balance,
     pub interest_accumulated: Balance,
 }
@@ -107,10 +124,25 @@ pub fn calculate_interest(input: &CalculateInterestInput) -> Result<CalculateInt
         U256::from(delta),
     );
 
-    let interest_accumulated =
-        compound_interest_factor.mul_scalar_truncate(U256::from(input.total_borrows));
+    // stable borrows accrue at the average stable rate, the rest follows the variable rate
+    let variable_borrows = input
+        .total_borrows
+        .saturating_sub(input.total_stable_borrows);
+    let variable_interest_accumulated =
+        compound_interest_factor.mul_scalar_truncate(U256::from(variable_borrows));
+    let stable_interest_accumulated = compound_interest(
+        &Exp {
+            mantissa: input.average_stable_borrow_rate.into(),
+        },
+        U256::from(delta),
+    )
+    .mul_scalar_truncate(U256::from(input.total_stable_borrows));
+    let interest_accumulated = variable_interest_accumulated.add(stable_interest_accumulated);
 
     let total_borrows_new = interest_accumulated.as_u128().add(input.total_borrows);
+    let total_stable_borrows_new = stable_interest_accumulated
+        .as_u128()
+        .add(input.total_stable_borrows);
     let total_reserves_new = Exp {
         mantissa: WrappedU256::from(input.reserve_factor_mantissa),
     }
@@ -121,10 +153,59 @@ pub fn calculate_interest(input: &CalculateInterestInput) -> Result<CalculateInt
         borrow_index: borrow_index_new,
         interest_accumulated: interest_accumulated.as_u128(),
         total_borrows: total_borrows_new,
+        total_stable_borrows: total_stable_borrows_new,
         total_reserves: total_reserves_new.as_u128(),
     })
 }
 
+/// Stable debt of an account, accrued at its own rate since `delta` milliseconds
+pub fn stable_borrow_balance(principal: Balance, stable_rate: U256, delta: Timestamp) -> Balance {
+    if principal == 0 {
+        return 0
+    }
+    compound_interest(
+        &Exp {
+            mantissa: stable_rate.into(),
+        },
+        U256::from(delta),
+    )
+    .mul_scalar_truncate_add_uint(U256::from(principal), U256::from(principal))
+    .as_u128()
+}
+
+/// Average stable rate after `amount` has been borrowed at `rate`
+pub fn average_stable_rate_on_increase(
+    total_stable_borrows: Balance,
+    average_rate: U256,
+    amount: Balance,
+    rate: U256,
+) -> U256 {
+    let total_new = U256::from(total_stable_borrows).add(U256::from(amount));
+    if total_new.is_zero() {
+        return U256::zero()
+    }
+    average_rate
+        .mul(U256::from(total_stable_borrows))
+        .add(rate.mul(U256::from(amount)))
+        .div(total_new)
+}
+
+/// Average stable rate after `amount` borrowed at `rate` has been repaid
+pub fn average_stable_rate_on_decrease(
+    total_stable_borrows: Balance,
+    average_rate: U256,
+    amount: Balance,
+    rate: U256,
+) -> U256 {
+    if total_stable_borrows <= amount {
+        return U256::zero()
+    }
+    average_rate
+        .mul(U256::from(total_stable_borrows))
+        .saturating_sub(rate.mul(U256::from(amount)))
+        .div(U256::from(total_stable_borrows - amount))
+}
+
 // returns liquidator_seize_tokens, protocol_seize_amount and protocol_seize_tokens
 pub fn protocol_seize_amount(
     exchange_rate: Exp,
@@ -178,6 +259,27 @@ pub fn pool_balance(exchange_rate: Exp, underlying_token_balance: Balance) -> Ba
     .as_u128()
 }
 
+/// Same as `underlying_balance`, rounding up instead of down
+pub fn underlying_balance_round_up(exchange_rate: Exp, pool_token_balance: Balance) -> Balance {
+    let product = U256::from(exchange_rate.mantissa).mul(U256::from(pool_token_balance));
+    product
+        .add(exp_scale())
+        .sub(U256::one())
+        .div(exp_scale())
+        .as_u128()
+}
+
+/// Same as `pool_balance`, rounding up instead of down
+pub fn pool_balance_round_up(exchange_rate: Exp, underlying_token_balance: Balance) -> Balance {
+    let rate = U256::from(exchange_rate.mantissa);
+    U256::from(underlying_token_balance)
+        .mul(exp_scale())
+        .add(rate)
+        .sub(U256::one())
+        .div(rate)
+        .as_u128()
+}
+
 #[cfg(test)]
 
 mod tests {
@@ -194,10 +296,12 @@ mod tests {
         let input = CalculateInterestInput {
             borrow_index: 0.into(),
             borrow_rate: U256::one().mul(U256::from(10)).pow(U256::from(18)),
+            average_stable_borrow_rate: U256::zero(),
             new_block_timestamp: Timestamp::default(),
             old_block_timestamp: Timestamp::default(),
             reserve_factor_mantissa: U256::zero(),
             total_borrows: Balance::default(),
+            total_stable_borrows: Balance::default(),
             total_reserves: Balance::default(),
         };
         let out = calculate_interest(&input);
@@ -264,8 +368,10 @@ mod tests {
                 new_block_timestamp: old_timestamp + 1000 * 60 * 60 * 24 * 30 * 12, // 1 year
                 borrow_index: 1.into(),
                 borrow_rate: mantissa().div(100000), // 0.001 %
+                average_stable_borrow_rate: U256::zero(),
                 reserve_factor_mantissa: mantissa().div(100), // 1 %
                 total_borrows: 10_000 * (10_u128.pow(18)),
+                total_stable_borrows: 0,
                 total_reserves: 10_000 * (10_u128.pow(18)),
             },
             CalculateInterestInput {
@@ -273,8 +379,10 @@ mod tests {
                 new_block_timestamp: old_timestamp + 1000 * 60 * 60, // 1 hour
                 borrow_index: 123123123.into(),
                 borrow_rate: mantissa().div(1000000),
+                average_stable_borrow_rate: U256::zero(),
                 reserve_factor_mantissa: mantissa().div(10),
                 total_borrows: 100_000 * (10_u128.pow(18)),
+                total_stable_borrows: 0,
                 total_reserves: 1_000_000 * (10_u128.pow(18)),
             },
             CalculateInterestInput {
@@ -282,8 +390,10 @@ mod tests {
                 new_block_timestamp: old_timestamp + 1000 * 60 * 60,
                 borrow_index: 123123123.into(),
                 borrow_rate: mantissa().div(123123),
+                average_stable_borrow_rate: U256::zero(),
                 reserve_factor_mantissa: mantissa().div(10).mul(2),
                 total_borrows: 123_456 * (10_u128.pow(18)),
+                total_stable_borrows: 0,
                 total_reserves: 789_012 * (10_u128.pow(18)),
             },
         ];
@@ -315,6 +425,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_calculate_interest_with_stable_borrows() {
+        let old_timestamp = BlockNumber::default();
+        let delta = 1000 * 60 * 60; // 1 hour
+        let input = CalculateInterestInput {
+            old_block_timestamp: old_timestamp,
+            new_block_timestamp: old_timestamp + delta,
+            borrow_index: mantissa(),
+            borrow_rate: mantissa().div(1000000),
+            average_stable_borrow_rate: mantissa().div(500000),
+            reserve_factor_mantissa: mantissa().div(10),
+            total_borrows: 100_000 * (10_u128.pow(18)),
+            total_stable_borrows: 40_000 * (10_u128.pow(18)),
+            total_reserves: 0,
+        };
+        let got = calculate_interest(&input).unwrap();
+
+        let stable_interest = got.total_stable_borrows - input.total_stable_borrows;
+        assert_eq!(
+            stable_interest,
+            stable_borrow_balance(
+                input.total_stable_borrows,
+                input.average_stable_borrow_rate,
+                delta
+            ) - input.total_stable_borrows
+        );
+        assert_eq!(
+            got.total_borrows,
+            input.total_borrows + got.interest_accumulated
+        );
+        assert!(got.interest_accumulated.gt(&stable_interest));
+
+        // borrow index only follows the variable rate
+        let variable_only = calculate_interest(&CalculateInterestInput {
+            total_stable_borrows: 0,
+            average_stable_borrow_rate: U256::zero(),
+            ..input
+        })
+        .unwrap();
+        assert_eq!(got.borrow_index, variable_only.borrow_index);
+    }
+
+    #[test]
+    fn test_average_stable_rate() {
+        let rate_a = mantissa().div(100);
+        let rate_b = mantissa().div(50);
+        let avg = average_stable_rate_on_increase(0, U256::zero(), 100, rate_a);
+        assert_eq!(avg, rate_a);
+        let avg = average_stable_rate_on_increase(100, avg, 300, rate_b);
+        assert_eq!(avg, rate_a.add(rate_b.mul(3)).div(4));
+        let avg = average_stable_rate_on_decrease(400, avg, 300, rate_b);
+        assert_eq!(avg, rate_a);
+        assert_eq!(
+            average_stable_rate_on_decrease(100, avg, 100, rate_a),
+            U256::zero()
+        );
+    }
+
     #[test]
     // protocol_seize_tokens = seizeTokens * protocolSeizeShare
     // liquidator_seize_tokens = seizeTokens - (seizeTokens * protocolSeizeShare)
@@ -340,6 +508,30 @@ mod tests {
         assert_eq!(protocol_seize_amount_got, protocol_seize_amount_want);
         assert_eq!(protocol_seize_tokens_got, protocol_seize_tokens);
     }
+    #[test]
+    fn test_max_stable_borrow_amount() {
+        assert_eq!(
+            max_stable_borrow_amount(10_000, max_stable_borrow_size_mantissa()),
+            2_500
+        );
+        assert_eq!(max_stable_borrow_amount(10_000, exp_scale()), 10_000);
+        assert_eq!(max_stable_borrow_amount(10_000, U256::zero()), 0);
+        assert_eq!(max_stable_borrow_amount(0, exp_scale()), 0);
+    }
+    #[test]
+    fn test_balance_conversions_rounding() {
+        // 1.5
+        let exchange_rate = Exp {
+            mantissa: WrappedU256::from(mantissa().mul(3).div(2)),
+        };
+        assert_eq!(underlying_balance(exchange_rate.clone(), 3), 4);
+        assert_eq!(underlying_balance_round_up(exchange_rate.clone(), 3), 5);
+        assert_eq!(underlying_balance_round_up(exchange_rate.clone(), 4), 6);
+        assert_eq!(pool_balance(exchange_rate.clone(), 4), 2);
+        assert_eq!(pool_balance_round_up(exchange_rate.clone(), 4), 3);
+        assert_eq!(pool_balance_round_up(exchange_rate, 6), 4);
+    }
+
     #[test]
     fn test_exchange_rate_in_case_total_supply_is_zero() {
         let initial = U256::one().mul(exp_scale());
@@ -359,24 +551,28 @@ mod tests {
             Case {
                 total_cash: with_dec(999987),
                 total_borrows: with_dec(199987),
+                total_stable_borrows: 0,
                 total_reserves: with_dec(299987),
                 total_supply: with_dec(1999987),
             },
             Case {
                 total_cash: with_dec(999983),
                 total_borrows: with_dec(199983),
+                total_stable_borrows: 0,
                 total_reserves: with_dec(299983),
                 total_supply: with_dec(1999983),
             },
             Case {
                 total_cash: with_dec(1999983),
                 total_borrows: with_dec(1199983),
+                total_stable_borrows: 0,
                 total_reserves: with_dec(1299983),
                 total_supply: with_dec(11999983),
             },
             Case {
                 total_cash: with_dec(1234567),
                 total_borrows: with_dec(234567),
+                total_stable_borrows: 0,
                 total_reserves: with_dec(34567),
                 total_supply: with_dec(11999983),
             },
@@ -0,0 +1,136 @@
+// Copyright 2023 Asynmatrix Pte. Ltd.
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+pub use crate::traits::price_aggregator::*;
+use crate::traits::price_oracle::PriceOracleRef;
+use ink::prelude::vec::Vec;
+use openbrush::{
+    contracts::ownable::*,
+    modifiers,
+    storage::Mapping,
+    traits::{
+        AccountId,
+        Storage,
+        Timestamp,
+    },
+};
+mod utils;
+pub use self::utils::{
+    aggregate,
+    is_fresh,
+    median,
+    source_config_is_valid,
+    Observation,
+};
+
+pub const STORAGE_KEY: u32 = openbrush::storage_unique_key!(Data);
+#[derive(Default, Debug)]
+#[openbrush::upgradeable_storage(STORAGE_KEY)]
+pub struct Data {
+    /// Sources of the price of the asset
+    pub source_configs: Mapping<AccountId, SourceConfig>,
+    /// Maximum age of a source price to be used, 0 if it never goes stale
+    pub heartbeats: Mapping<AccountId, Timestamp>,
+}
+
+pub trait Internal {
+    fn _get_aggregated_price(&self, asset: AccountId) -> Result<AggregatedPrice>;
+    fn _observe(&self, source: AccountId, asset: AccountId) -> Option<Observation>;
+    fn _source_config(&self, asset: AccountId) -> Option<SourceConfig>;
+    fn _set_source_config(&mut self, asset: AccountId, config: SourceConfig) -> Result<()>;
+    fn _remove_source_config(&mut self, asset: AccountId) -> Result<()>;
+    fn _get_heartbeat(&self, asset: AccountId) -> Timestamp;
+    fn _set_heartbeat(&mut self, asset: AccountId, heartbeat: Timestamp);
+
+    // event emission
+    fn _emit_source_config_updated_event(&self, asset: AccountId, config: SourceConfig);
+    fn _emit_source_config_removed_event(&self, asset: AccountId);
+}
+
+impl<T: Storage<Data> + Storage<ownable::Data>> PriceAggregator for T {
+    default fn get_aggregated_price(&self, asset: AccountId) -> Result<AggregatedPrice> {
+        self._get_aggregated_price(asset)
+    }
+    default fn source_config(&self, asset: AccountId) -> Option<SourceConfig> {
+        self._source_config(asset)
+    }
+    #[modifiers(only_owner)]
+    default fn set_source_config(&mut self, asset: AccountId, config: SourceConfig) -> Result<()> {
+        if !source_config_is_valid(&config) {
+            return Err(Error::InvalidSourceConfig)
+        }
+        self._set_source_config(asset, config.clone())?;
+        self._emit_source_config_updated_event(asset, config);
+        Ok(())
+    }
+    #[modifiers(only_owner)]
+    default fn remove_source_config(&mut self, asset: AccountId) -> Result<()> {
+        if self._source_config(asset).is_none() {
+            return Err(Error::SourceConfigIsNotSet)
+        }
+        self._remove_source_config(asset)?;
+        self._emit_source_config_removed_event(asset);
+        Ok(())
+    }
+}
+
+impl<T: Storage<Data> + Storage<ownable::Data>> Internal for T {
+    default fn _get_aggregated_price(&self, asset: AccountId) -> Result<AggregatedPrice> {
+        let config = self
+            ._source_config(asset)
+            .ok_or(Error::SourceConfigIsNotSet)?;
+        let heartbeat = self._get_heartbeat(asset);
+        let now = Self::env().block_timestamp();
+        let observations: Vec<Observation> = config
+            .sources
+            .iter()
+            .filter_map(|source| self._observe(*source, asset))
+            .filter(|o| o.price != 0 && is_fresh(o, now, heartbeat))
+            .collect();
+        aggregate(&observations, config.min_sources, config.max_deviation)
+    }
+    default fn _observe(&self, source: AccountId, asset: AccountId) -> Option<Observation> {
+        // NOTE: a source failing to answer is treated as missing instead of failing the aggregation
+        let (price, updated_at) = PriceOracleRef::get_price_data_builder(&source, asset)
+            .try_invoke()
+            .ok()?
+            .ok()??;
+        let heartbeat = PriceOracleRef::get_heartbeat_builder(&source, asset)
+            .try_invoke()
+            .ok()?
+            .ok()?;
+        Some(Observation {
+            source,
+            price,
+            updated_at,
+            heartbeat,
+        })
+    }
+    default fn _source_config(&self, asset: AccountId) -> Option<SourceConfig> {
+        self.data::<Data>().source_configs.get(&asset)
+    }
+    default fn _set_source_config(&mut self, asset: AccountId, config: SourceConfig) -> Result<()> {
+        self.data::<Data>().source_configs.insert(&asset, &config);
+        Ok(())
+    }
+    default fn _remove_source_config(&mut self, asset: AccountId) -> Result<()> {
+        self.data::<Data>().source_configs.remove(&asset);
+        Ok(())
+    }
+    default fn _get_heartbeat(&self, asset: AccountId) -> Timestamp {
+        self.data::<Data>()
+            .heartbeats
+            .get(&asset)
+            .unwrap_or_default()
+    }
+    default fn _set_heartbeat(&mut self, asset: AccountId, heartbeat: Timestamp) {
+        self.data::<Data>().heartbeats.insert(&asset, &heartbeat);
+    }
+
+    default fn _emit_source_config_updated_event(&self, _asset: AccountId, _config: SourceConfig) {}
+    default fn _emit_source_config_removed_event(&self, _asset: AccountId) {}
+}
@@ -0,0 +1,305 @@
+use crate::impls::percent_math::{
+    deviation_within,
+    percentage_factor,
+};
+pub use crate::traits::price_aggregator::*;
+use ink::prelude::vec::Vec;
+use openbrush::traits::{
+    AccountId,
+    Timestamp,
+};
+use primitive_types::U256;
+
+/// Price reported by a source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Observation {
+    pub source: AccountId,
+    pub price: u128,
+    pub updated_at: Timestamp,
+    /// Maximum age of the price set in the source, 0 if it never goes stale
+    pub heartbeat: Timestamp,
+}
+
+pub fn median(prices: &[u128]) -> Option<u128> {
+    if prices.is_empty() {
+        return None
+    }
+    let mut sorted = prices.to_vec();
+    sorted.sort_unstable();
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 1 {
+        return Some(sorted[mid])
+    }
+    let (lower, upper) = (sorted[mid - 1], sorted[mid]);
+    Some(lower / 2 + upper / 2 + (lower % 2 + upper % 2) / 2)
+}
+
+/// Whether the observation is within both the heartbeat of the source and the given one
+pub fn is_fresh(observation: &Observation, now: Timestamp, heartbeat: Timestamp) -> bool {
+    let age = now.saturating_sub(observation.updated_at);
+    (heartbeat == 0 || age <= heartbeat)
+        && (observation.heartbeat == 0 || age <= observation.heartbeat)
+}
+
+pub fn source_config_is_valid(config: &SourceConfig) -> bool {
+    if config.min_sources == 0 || config.min_sources as usize > config.sources.len() {
+        return false
+    }
+    if U256::from(config.max_deviation) > percentage_factor() {
+        return false
+    }
+    config
+        .sources
+        .iter()
+        .enumerate()
+        .all(|(i, source)| !config.sources[..i].contains(source))
+}
+
+/// Takes the median of the observations, drops the ones deviating too far from it
+/// and takes the median of the rest, as long as enough sources remain.
+pub fn aggregate(
+    observations: &[Observation],
+    min_sources: u32,
+    max_deviation: u128,
+) -> Result<AggregatedPrice> {
+    if observations.len() < min_sources as usize {
+        return Err(Error::InsufficientSources)
+    }
+    let prices: Vec<u128> = observations.iter().map(|o| o.price).collect();
+    let reference = median(&prices).ok_or(Error::InsufficientSources)?;
+    let used: Vec<&Observation> = observations
+        .iter()
+        .filter(|o| deviation_within(o.price, reference, max_deviation))
+        .collect();
+    if used.is_empty() || used.len() < min_sources as usize {
+        return Err(Error::InsufficientSources)
+    }
+
+    let prices: Vec<u128> = used.iter().map(|o| o.price).collect();
+    Ok(AggregatedPrice {
+        price: median(&prices).ok_or(Error::InsufficientSources)?,
+        updated_at: used.iter().map(|o| o.updated_at).min().unwrap_or_default(),
+        sources: used.iter().map(|o| o.source).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn obs(id: u8, price: u128, updated_at: Timestamp) -> Observation {
+        Observation {
+            source: AccountId::from([id; 32]),
+            price,
+            updated_at,
+            heartbeat: 0,
+        }
+    }
+
+    #[test]
+    fn test_median() {
+        struct Case<'a> {
+            prices: &'a [u128],
+            want: Option<u128>,
+        }
+        let cases: &[Case] = &[
+            Case {
+                prices: &[],
+                want: None,
+            },
+            Case {
+                prices: &[7],
+                want: Some(7),
+            },
+            Case {
+                prices: &[30, 10, 20],
+                want: Some(20),
+            },
+            Case {
+                prices: &[40, 10, 30, 20],
+                want: Some(25),
+            },
+            Case {
+                prices: &[u128::MAX, u128::MAX],
+                want: Some(u128::MAX),
+            },
+        ];
+        for case in cases {
+            assert_eq!(median(case.prices), case.want);
+        }
+    }
+
+    #[test]
+    fn test_source_config_is_valid() {
+        let a = AccountId::from([0x01; 32]);
+        let b = AccountId::from([0x02; 32]);
+        struct Case {
+            config: SourceConfig,
+            want: bool,
+        }
+        let cases: &[Case] = &[
+            Case {
+                config: SourceConfig {
+                    sources: [a, b].to_vec(),
+                    min_sources: 2,
+                    max_deviation: 500,
+                },
+                want: true,
+            },
+            Case {
+                config: SourceConfig {
+                    sources: [a, b].to_vec(),
+                    min_sources: 0,
+                    max_deviation: 500,
+                },
+                want: false,
+            },
+            Case {
+                config: SourceConfig {
+                    sources: [a, b].to_vec(),
+                    min_sources: 3,
+                    max_deviation: 500,
+                },
+                want: false,
+            },
+            Case {
+                config: SourceConfig {
+                    sources: [a, a].to_vec(),
+                    min_sources: 1,
+                    max_deviation: 500,
+                },
+                want: false,
+            },
+            Case {
+                config: SourceConfig {
+                    sources: [a].to_vec(),
+                    min_sources: 1,
+                    max_deviation: 10001,
+                },
+                want: false,
+            },
+        ];
+        for case in cases {
+            assert_eq!(source_config_is_valid(&case.config), case.want);
+        }
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let observations = [obs(1, 100, 10), obs(2, 102, 20), obs(3, 150, 30)];
+
+        let result = aggregate(&observations, 2, 500).unwrap();
+        assert_eq!(result.price, 101);
+        assert_eq!(result.updated_at, 10);
+        assert_eq!(
+            result.sources,
+            [AccountId::from([1; 32]), AccountId::from([2; 32])].to_vec()
+        );
+
+        let result = aggregate(&observations, 3, 10000).unwrap();
+        assert_eq!(result.price, 102);
+        assert_eq!(result.sources.len(), 3);
+
+        assert_eq!(
+            aggregate(&observations, 3, 500).unwrap_err(),
+            Error::InsufficientSources
+        );
+        assert_eq!(
+            aggregate(&observations[..1], 2, 500).unwrap_err(),
+            Error::InsufficientSources
+        );
+        assert_eq!(
+            aggregate(&[], 1, 500).unwrap_err(),
+            Error::InsufficientSources
+        );
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        struct Case {
+            updated_at: Timestamp,
+            source_heartbeat: Timestamp,
+            heartbeat: Timestamp,
+            want: bool,
+        }
+        let now = 100;
+        let cases: &[Case] = &[
+            Case {
+                updated_at: 0,
+                source_heartbeat: 0,
+                heartbeat: 0,
+                want: true,
+            },
+            Case {
+                updated_at: 40,
+                source_heartbeat: 0,
+                heartbeat: 60,
+                want: true,
+            },
+            Case {
+                updated_at: 39,
+                source_heartbeat: 0,
+                heartbeat: 60,
+                want: false,
+            },
+            Case {
+                updated_at: 39,
+                source_heartbeat: 60,
+                heartbeat: 0,
+                want: false,
+            },
+            Case {
+                updated_at: 60,
+                source_heartbeat: 30,
+                heartbeat: 60,
+                want: false,
+            },
+        ];
+        for case in cases {
+            let observation = Observation {
+                heartbeat: case.source_heartbeat,
+                ..obs(1, 100, case.updated_at)
+            };
+            assert_eq!(is_fresh(&observation, now, case.heartbeat), case.want);
+        }
+    }
+
+    #[test]
+    fn test_aggregate_without_stale_sources() {
+        let now = 100;
+        let observations = [
+            Observation {
+                heartbeat: 30,
+                ..obs(1, 100, 50)
+            },
+            Observation {
+                heartbeat: 60,
+                ..obs(2, 101, 50)
+            },
+            Observation {
+                heartbeat: 0,
+                ..obs(3, 102, 10)
+            },
+        ];
+        assert_eq!(aggregate(&observations, 2, 500).unwrap().price, 101);
+
+        let fresh: Vec<Observation> = observations
+            .iter()
+            .filter(|o| is_fresh(o, now, 0))
+            .cloned()
+            .collect();
+        assert_eq!(fresh.len(), 2);
+        assert_eq!(aggregate(&fresh, 2, 500).unwrap().price, 101);
+
+        let fresh: Vec<Observation> = observations
+            .iter()
+            .filter(|o| is_fresh(o, now, 60))
+            .cloned()
+            .collect();
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(
+            aggregate(&fresh, 2, 500).unwrap_err(),
+            Error::InsufficientSources
+        );
+    }
+}
@@ -5,8 +5,11 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use crate::traits::pool::PoolRef;
 pub use crate::traits::price_oracle::*;
+use crate::{
+    impls::percent_math::deviation_within,
+    traits::pool::PoolRef,
+};
 use openbrush::{
     contracts::ownable::*,
     modifier_definition,
@@ -15,6 +18,7 @@ use openbrush::{
     traits::{
         AccountId,
         Storage,
+        Timestamp,
     },
 };
 
@@ -26,6 +30,16 @@ pub struct Data {
     pub fixed_prices: Mapping<AccountId, u128>,
     /// Authorized sybils for updating Price
     pub sybils: Mapping<AccountId, bool>,
+    /// When the price of the asset was last set
+    pub updated_at: Mapping<AccountId, Timestamp>,
+    /// Maximum age of the price of the asset, 0 if it never goes stale
+    pub heartbeats: Mapping<AccountId, Timestamp>,
+    /// Maximum deviations of the price updates of the asset
+    pub deviation_bounds: Mapping<AccountId, DeviationBounds>,
+    /// Price of the asset at the start of the current window and when it started
+    pub window_anchors: Mapping<AccountId, (u128, Timestamp)>,
+    /// Price update of the asset out of the bounds, waiting for the owner to confirm it
+    pub pending_prices: Mapping<AccountId, u128>,
 }
 
 pub const PRICE_PRECISION: u128 = 10_u128.pow(18);
@@ -49,10 +63,45 @@ where
 pub trait Internal {
     fn _get_price(&self, asset: AccountId) -> Option<u128>;
     fn _get_underlying_price(&self, pool: AccountId) -> Option<u128>;
+    fn _get_price_data(&self, asset: AccountId) -> Option<(u128, Timestamp)>;
+    fn _get_heartbeat(&self, asset: AccountId) -> Timestamp;
     fn _set_fixed_price(&mut self, asset: AccountId, price: u128) -> Result<()>;
+    fn _set_heartbeat(&mut self, asset: AccountId, heartbeat: Timestamp) -> Result<()>;
     fn _authorize_sybil(&mut self, sybil: AccountId) -> Result<()>;
     fn _unauthorize_sybil(&mut self, sybil: AccountId) -> Result<()>;
     fn _is_sybil_authorized(&self, sybil: AccountId) -> bool;
+    fn _deviation_bounds(&self, asset: AccountId) -> Option<DeviationBounds>;
+    fn _set_deviation_bounds(
+        &mut self,
+        asset: AccountId,
+        bounds: Option<DeviationBounds>,
+    ) -> Result<()>;
+    fn _window_anchor(
+        &self,
+        asset: AccountId,
+        window: Timestamp,
+        fallback: u128,
+    ) -> (u128, Timestamp);
+    fn _update_within_bounds(
+        &self,
+        asset: AccountId,
+        value: u128,
+        bounds: &DeviationBounds,
+    ) -> bool;
+    fn _pending_price(&self, asset: AccountId) -> Option<u128>;
+    fn _set_pending_price(&mut self, asset: AccountId, value: u128) -> Result<()>;
+    fn _remove_pending_price(&mut self, asset: AccountId) -> Result<()>;
+
+    // event emission
+    fn _emit_deviation_bounds_updated_event(
+        &self,
+        asset: AccountId,
+        bounds: Option<DeviationBounds>,
+    );
+    fn _emit_price_update_rejected_event(&self, asset: AccountId, price: u128);
+    fn _emit_price_update_pending_event(&self, asset: AccountId, price: u128);
+    fn _emit_pending_price_confirmed_event(&self, asset: AccountId, price: u128);
+    fn _emit_pending_price_discarded_event(&self, asset: AccountId, price: u128);
 }
 
 impl<T: Storage<Data> + Storage<ownable::Data>> PriceOracle for T {
@@ -62,9 +111,35 @@ impl<T: Storage<Data> + Storage<ownable::Data>> PriceOracle for T {
     default fn get_underlying_price(&self, pool: AccountId) -> Option<u128> {
         self._get_underlying_price(pool)
     }
+    default fn get_price_data(&self, asset: AccountId) -> Option<(u128, Timestamp)> {
+        self._get_price_data(asset)
+    }
+    default fn get_heartbeat(&self, asset: AccountId) -> Timestamp {
+        self._get_heartbeat(asset)
+    }
     #[modifiers(only_authorized_sybil)]
     default fn set_fixed_price(&mut self, asset: AccountId, value: u128) -> Result<()> {
-        self._set_fixed_price(asset, value)
+        let bounds = match self._deviation_bounds(asset) {
+            Some(bounds) => bounds,
+            None => return self._set_fixed_price(asset, value),
+        };
+        if self._pending_price(asset).is_none() && self._update_within_bounds(asset, value, &bounds)
+        {
+            return self._set_fixed_price(asset, value)
+        }
+
+        // NOTE: the update out of the bounds does not revert so that its event is kept
+        if bounds.pending_on_breach {
+            self._set_pending_price(asset, value)?;
+            self._emit_price_update_pending_event(asset, value);
+        } else {
+            self._emit_price_update_rejected_event(asset, value);
+        }
+        Ok(())
+    }
+    #[modifiers(only_owner)]
+    default fn set_heartbeat(&mut self, asset: AccountId, heartbeat: Timestamp) -> Result<()> {
+        self._set_heartbeat(asset, heartbeat)
     }
     default fn authorize_sybil(&mut self, sybil: AccountId) -> Result<()> {
         self._authorize_sybil(sybil)
@@ -77,8 +152,62 @@ impl<T: Storage<Data> + Storage<ownable::Data>> PriceOracle for T {
     }
 }
 
+impl<T: Storage<Data> + Storage<ownable::Data>> PriceCircuitBreaker for T {
+    default fn deviation_bounds(&self, asset: AccountId) -> Option<DeviationBounds> {
+        self._deviation_bounds(asset)
+    }
+    #[modifiers(only_owner)]
+    default fn set_deviation_bounds(
+        &mut self,
+        asset: AccountId,
+        bounds: Option<DeviationBounds>,
+    ) -> Result<()> {
+        if let Some(bounds) = &bounds {
+            if bounds.max_update_deviation == 0 || bounds.max_window_deviation == 0 {
+                return Err(Error::InvalidDeviationBounds)
+            }
+        }
+        self._set_deviation_bounds(asset, bounds.clone())?;
+        self._emit_deviation_bounds_updated_event(asset, bounds);
+        Ok(())
+    }
+    default fn pending_price(&self, asset: AccountId) -> Option<u128> {
+        self._pending_price(asset)
+    }
+    #[modifiers(only_owner)]
+    default fn confirm_pending_price(&mut self, asset: AccountId, price: u128) -> Result<()> {
+        let pending = self
+            ._pending_price(asset)
+            .ok_or(Error::PendingPriceIsNotSet)?;
+        if pending != price {
+            return Err(Error::PendingPriceMismatch)
+        }
+        self._remove_pending_price(asset)?;
+        self._set_fixed_price(asset, price)?;
+        // the confirmed price starts a new window
+        self.data::<Data>()
+            .window_anchors
+            .insert(&asset, &(price, Self::env().block_timestamp()));
+        self._emit_pending_price_confirmed_event(asset, price);
+        Ok(())
+    }
+    #[modifiers(only_owner)]
+    default fn discard_pending_price(&mut self, asset: AccountId) -> Result<()> {
+        let price = self
+            ._pending_price(asset)
+            .ok_or(Error::PendingPriceIsNotSet)?;
+        self._remove_pending_price(asset)?;
+        self._emit_pending_price_discarded_event(asset, price);
+        Ok(())
+    }
+}
+
 impl<T: Storage<Data> + Storage<ownable::Data>> Internal for T {
     default fn _get_price(&self, asset: AccountId) -> Option<u128> {
+        // NOTE: the price is unusable while an update waits for confirmation
+        if self._pending_price(asset).is_some() {
+            return None
+        }
         self.data::<Data>().fixed_prices.get(&asset)
     }
     default fn _get_underlying_price(&self, pool: AccountId) -> Option<u128> {
@@ -87,8 +216,34 @@ impl<T: Storage<Data> + Storage<ownable::Data>> Internal for T {
         }
         None
     }
+    default fn _get_price_data(&self, asset: AccountId) -> Option<(u128, Timestamp)> {
+        let price = self._get_price(asset)?;
+        let updated_at = self
+            .data::<Data>()
+            .updated_at
+            .get(&asset)
+            .unwrap_or_default();
+        Some((price, updated_at))
+    }
+    default fn _get_heartbeat(&self, asset: AccountId) -> Timestamp {
+        self.data::<Data>()
+            .heartbeats
+            .get(&asset)
+            .unwrap_or_default()
+    }
     default fn _set_fixed_price(&mut self, asset: AccountId, value: u128) -> Result<()> {
+        if let Some(bounds) = self._deviation_bounds(asset) {
+            let anchor = self._window_anchor(asset, bounds.window, value);
+            self.data::<Data>().window_anchors.insert(&asset, &anchor);
+        }
         self.data::<Data>().fixed_prices.insert(&asset, &value);
+        self.data::<Data>()
+            .updated_at
+            .insert(&asset, &Self::env().block_timestamp());
+        Ok(())
+    }
+    default fn _set_heartbeat(&mut self, asset: AccountId, heartbeat: Timestamp) -> Result<()> {
+        self.data::<Data>().heartbeats.insert(&asset, &heartbeat);
         Ok(())
     }
     default fn _authorize_sybil(&mut self, sybil: AccountId) -> Result<()> {
@@ -106,4 +261,79 @@ impl<T: Storage<Data> + Storage<ownable::Data>> Internal for T {
         }
         self.data::<Data>().sybils.get(&sybil).unwrap_or(false)
     }
+    default fn _deviation_bounds(&self, asset: AccountId) -> Option<DeviationBounds> {
+        self.data::<Data>().deviation_bounds.get(&asset)
+    }
+    default fn _set_deviation_bounds(
+        &mut self,
+        asset: AccountId,
+        bounds: Option<DeviationBounds>,
+    ) -> Result<()> {
+        let mut data = self.data::<Data>();
+        if let Some(bounds) = bounds {
+            data.deviation_bounds.insert(&asset, &bounds);
+        } else {
+            data.deviation_bounds.remove(&asset);
+        }
+        data.window_anchors.remove(&asset);
+        Ok(())
+    }
+    default fn _window_anchor(
+        &self,
+        asset: AccountId,
+        window: Timestamp,
+        fallback: u128,
+    ) -> (u128, Timestamp) {
+        let now = Self::env().block_timestamp();
+        if let Some((price, started_at)) = self.data::<Data>().window_anchors.get(&asset) {
+            if now.saturating_sub(started_at) <= window {
+                return (price, started_at)
+            }
+        }
+        let current = self
+            .data::<Data>()
+            .fixed_prices
+            .get(&asset)
+            .filter(|price| *price != 0);
+        (current.unwrap_or(fallback), now)
+    }
+    default fn _update_within_bounds(
+        &self,
+        asset: AccountId,
+        value: u128,
+        bounds: &DeviationBounds,
+    ) -> bool {
+        if value == 0 {
+            return false
+        }
+        let current = match self.data::<Data>().fixed_prices.get(&asset) {
+            Some(price) if price != 0 => price,
+            _ => return true,
+        };
+        let (reference, _) = self._window_anchor(asset, bounds.window, current);
+        deviation_within(value, current, bounds.max_update_deviation)
+            && deviation_within(value, reference, bounds.max_window_deviation)
+    }
+    default fn _pending_price(&self, asset: AccountId) -> Option<u128> {
+        self.data::<Data>().pending_prices.get(&asset)
+    }
+    default fn _set_pending_price(&mut self, asset: AccountId, value: u128) -> Result<()> {
+        self.data::<Data>().pending_prices.insert(&asset, &value);
+        Ok(())
+    }
+    default fn _remove_pending_price(&mut self, asset: AccountId) -> Result<()> {
+        self.data::<Data>().pending_prices.remove(&asset);
+        Ok(())
+    }
+
+    default fn _emit_deviation_bounds_updated_event(
+        &self,
+        _asset: AccountId,
+        _bounds: Option<DeviationBounds>,
+    ) {
+    }
+    default fn _emit_price_update_rejected_event(&self, _asset: AccountId, _price: u128) {}
+    default fn _emit_price_update_pending_event(&self, _asset: AccountId, _price: u128) {}
+    default fn _emit_pending_price_confirmed_event(&self, _asset: AccountId, _price: u128) {}
+    default fn _emit_pending_price_discarded_event(&self, _asset: AccountId, _price: u128) {}
 }
@@ -0,0 +1,516 @@
+// Copyright 2023 Asynmatrix Pte. Ltd.
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::{
+    dex_pair::{
+        accumulate,
+        spot_price,
+    },
+    price_oracle::PRICE_PRECISION,
+};
+pub use crate::traits::twap_oracle::*;
+use crate::traits::{
+    dex_pair::DexPairRef,
+    price_oracle::PriceOracleRef,
+};
+use core::ops::{
+    Div,
+    Mul,
+};
+use ink::prelude::vec::Vec;
+use openbrush::{
+    contracts::ownable::*,
+    modifiers,
+    storage::{
+        Mapping,
+        TypeGuard,
+    },
+    traits::{
+        AccountId,
+        Storage,
+        Timestamp,
+    },
+};
+use primitive_types::U256;
+
+/// Size of the ring buffer of the observations of each asset
+pub const OBSERVATION_CAPACITY: u32 = 24;
+
+pub const STORAGE_KEY: u32 = openbrush::storage_unique_key!(Data);
+#[derive(Default, Debug)]
+#[openbrush::upgradeable_storage(STORAGE_KEY)]
+pub struct Data {
+    /// Source of the price of the asset
+    pub twap_configs: Mapping<AccountId, TwapConfig>,
+    /// Whether the asset is token_0 of its pair
+    pub token_0_assets: Mapping<AccountId, bool>,
+    /// Ring buffer of the observations of the asset
+    pub observations: Mapping<(AccountId, u32), TwapObservation, ObservationsKey>,
+    /// Next index in the ring buffer of the asset and the number of observations in it
+    pub observation_heads: Mapping<AccountId, (u32, u32)>,
+    /// Maximum age of the latest observation of the asset, 0 if it never goes stale
+    pub heartbeats: Mapping<AccountId, Timestamp>,
+}
+
+pub struct ObservationsKey;
+
+impl<'a> TypeGuard<'a> for ObservationsKey {
+    type Type = &'a (&'a AccountId, &'a u32);
+}
+
+pub fn twap_config_is_valid(config: &TwapConfig) -> bool {
+    if config.window == 0 || config.period == 0 {
+        return false
+    }
+    if config.min_observations == 0 || config.min_observations > OBSERVATION_CAPACITY {
+        return false
+    }
+    // the buffer must cover the window and the required observations must fit in it
+    let buffer_span = config
+        .period
+        .saturating_mul(OBSERVATION_CAPACITY as Timestamp);
+    let required_span = config
+        .period
+        .saturating_mul((config.min_observations - 1) as Timestamp);
+    buffer_span >= config.window && required_span <= config.window
+}
+
+/// Average of the prices held between the observations, None if no time has elapsed
+pub fn time_weighted_average(start: &TwapObservation, end: &TwapObservation) -> Option<u128> {
+    let elapsed = end.timestamp.checked_sub(start.timestamp)?;
+    if elapsed == 0 {
+        return None
+    }
+    let (diff, _) =
+        U256::from(end.price_cumulative).overflowing_sub(U256::from(start.price_cumulative));
+    let average = diff.div(U256::from(elapsed));
+    if average > U256::from(u128::MAX) {
+        return None
+    }
+    Some(average.as_u128())
+}
+
+/// Price of one whole unit of the asset from the raw ratio of the reserves,
+/// converted with the price of the quote token if given. None on overflow.
+pub fn normalize_price(
+    price: u128,
+    asset_decimals: u8,
+    quote_decimals: u8,
+    quote_price: Option<u128>,
+) -> Option<u128> {
+    let asset_unit = U256::from(10).checked_pow(U256::from(asset_decimals))?;
+    let quote_unit = U256::from(10).checked_pow(U256::from(quote_decimals))?;
+    let mut numerator = U256::from(price).checked_mul(asset_unit)?;
+    let mut denominator = quote_unit;
+    if let Some(quote_price) = quote_price {
+        numerator = numerator.checked_mul(U256::from(quote_price))?;
+        denominator = denominator.mul(U256::from(PRICE_PRECISION));
+    }
+    let normalized = numerator.div(denominator);
+    if normalized > U256::from(u128::MAX) {
+        return None
+    }
+    Some(normalized.as_u128())
+}
+
+pub trait Internal {
+    fn _consult(&self, asset: AccountId) -> Result<u128>;
+    fn _update(&mut self, asset: AccountId) -> Result<()>;
+    fn _get_price_data(&self, asset: AccountId) -> Option<(u128, Timestamp)>;
+    fn _current_observation(&self, asset: AccountId, pair: AccountId) -> TwapObservation;
+    fn _quote_token(&self, asset: AccountId, pair: AccountId) -> AccountId;
+    fn _observations(&self, asset: AccountId) -> Vec<TwapObservation>;
+    fn _latest_observation(&self, asset: AccountId) -> Option<TwapObservation>;
+    fn _push_observation(&mut self, asset: AccountId, observation: TwapObservation);
+    fn _twap_config(&self, asset: AccountId) -> Option<TwapConfig>;
+    fn _set_twap_config(
+        &mut self,
+        asset: AccountId,
+        config: TwapConfig,
+        is_token_0: bool,
+    ) -> Result<()>;
+    fn _get_heartbeat(&self, asset: AccountId) -> Timestamp;
+    fn _set_heartbeat(&mut self, asset: AccountId, heartbeat: Timestamp);
+
+    // event emission
+    fn _emit_twap_config_updated_event(&self, asset: AccountId, config: TwapConfig);
+    fn _emit_observation_recorded_event(&self, asset: AccountId, observation: TwapObservation);
+}
+
+impl<T: Storage<Data> + Storage<ownable::Data>> TwapOracle for T {
+    default fn consult(&self, asset: AccountId) -> Result<u128> {
+        self._consult(asset)
+    }
+    default fn update(&mut self, asset: AccountId) -> Result<()> {
+        self._update(asset)
+    }
+    default fn observations(&self, asset: AccountId) -> Vec<TwapObservation> {
+        self._observations(asset)
+    }
+    default fn twap_config(&self, asset: AccountId) -> Option<TwapConfig> {
+        self._twap_config(asset)
+    }
+    #[modifiers(only_owner)]
+    default fn set_twap_config(&mut self, asset: AccountId, config: TwapConfig) -> Result<()> {
+        if !twap_config_is_valid(&config) {
+            return Err(Error::InvalidTwapConfig)
+        }
+        let is_token_0 = if DexPairRef::token_0(&config.pair) == asset {
+            true
+        } else if DexPairRef::token_1(&config.pair) == asset {
+            false
+        } else {
+            return Err(Error::PairDoesNotContainAsset)
+        };
+        self._set_twap_config(asset, config.clone(), is_token_0)?;
+        self._emit_twap_config_updated_event(asset, config);
+        Ok(())
+    }
+}
+
+impl<T: Storage<Data> + Storage<ownable::Data>> Internal for T {
+    default fn _consult(&self, asset: AccountId) -> Result<u128> {
+        let config = self._twap_config(asset).ok_or(Error::TwapConfigIsNotSet)?;
+        let now = Self::env().block_timestamp();
+        let observations: Vec<TwapObservation> = self
+            ._observations(asset)
+            .into_iter()
+            .filter(|o| now.saturating_sub(o.timestamp) <= config.window)
+            .collect();
+        if observations.len() < config.min_observations as usize {
+            return Err(Error::InsufficientObservations)
+        }
+        let start = observations
+            .first()
+            .ok_or(Error::InsufficientObservations)?;
+        let end = self._current_observation(asset, config.pair);
+        let average = time_weighted_average(start, &end).ok_or(Error::InsufficientObservations)?;
+        let quote_price = match config.quote_oracle {
+            Some(oracle) => {
+                let quote = self._quote_token(asset, config.pair);
+                Some(
+                    PriceOracleRef::get_price(&oracle, quote)
+                        .filter(|price| *price != 0)
+                        .ok_or(Error::QuotePriceIsNotAvailable)?,
+                )
+            }
+            None => None,
+        };
+        normalize_price(
+            average,
+            config.asset_decimals,
+            config.quote_decimals,
+            quote_price,
+        )
+        .ok_or(Error::PriceOverflow)
+    }
+    default fn _update(&mut self, asset: AccountId) -> Result<()> {
+        let config = self._twap_config(asset).ok_or(Error::TwapConfigIsNotSet)?;
+        if let Some(latest) = self._latest_observation(asset) {
+            let now = Self::env().block_timestamp();
+            if now.saturating_sub(latest.timestamp) < config.period {
+                return Err(Error::ObservationIsTooRecent)
+            }
+        }
+        let observation = self._current_observation(asset, config.pair);
+        self._push_observation(asset, observation.clone());
+        self._emit_observation_recorded_event(asset, observation);
+        Ok(())
+    }
+    default fn _get_price_data(&self, asset: AccountId) -> Option<(u128, Timestamp)> {
+        let price = self._consult(asset).ok()?;
+        let latest = self._latest_observation(asset)?;
+        Some((price, latest.timestamp))
+    }
+    default fn _current_observation(&self, asset: AccountId, pair: AccountId) -> TwapObservation {
+        let is_token_0 = self
+            .data::<Data>()
+            .token_0_assets
+            .get(&asset)
+            .unwrap_or(true);
+        let (reserve_0, reserve_1, block_timestamp_last) = DexPairRef::get_reserves(&pair);
+        let (mut price_cumulative, price): (U256, _) = if is_token_0 {
+            (
+                DexPairRef::price_0_cumulative_last(&pair).into(),
+                spot_price(reserve_0, reserve_1),
+            )
+        } else {
+            (
+                DexPairRef::price_1_cumulative_last(&pair).into(),
+                spot_price(reserve_1, reserve_0),
+            )
+        };
+
+        // NOTE: the pair accumulates only on its updates, so the current price is added up to now
+        let now = Self::env().block_timestamp();
+        if let Some(price) = price {
+            price_cumulative = accumulate(
+                price_cumulative,
+                price,
+                now.saturating_sub(block_timestamp_last),
+            );
+        }
+        TwapObservation {
+            timestamp: now,
+            price_cumulative: price_cumulative.into(),
+        }
+    }
+    default fn _quote_token(&self, asset: AccountId, pair: AccountId) -> AccountId {
+        let is_token_0 = self
+            .data::<Data>()
+            .token_0_assets
+            .get(&asset)
+            .unwrap_or(true);
+        if is_token_0 {
+            DexPairRef::token_1(&pair)
+        } else {
+            DexPairRef::token_0(&pair)
+        }
+    }
+    default fn _observations(&self, asset: AccountId) -> Vec<TwapObservation> {
+        let data = self.data::<Data>();
+        let (next, count) = data.observation_heads.get(&asset).unwrap_or_default();
+        (0..count)
+            .filter_map(|i| {
+                let index = (next + OBSERVATION_CAPACITY - count + i) % OBSERVATION_CAPACITY;
+                data.observations.get(&(&asset, &index))
+            })
+            .collect()
+    }
+    default fn _latest_observation(&self, asset: AccountId) -> Option<TwapObservation> {
+        let data = self.data::<Data>();
+        let (next, count) = data.observation_heads.get(&asset).unwrap_or_default();
+        if count == 0 {
+            return None
+        }
+        let index = (next + OBSERVATION_CAPACITY - 1) % OBSERVATION_CAPACITY;
+        data.observations.get(&(&asset, &index))
+    }
+    default fn _push_observation(&mut self, asset: AccountId, observation: TwapObservation) {
+        let mut data = self.data::<Data>();
+        let (next, count) = data.observation_heads.get(&asset).unwrap_or_default();
+        data.observations.insert(&(&asset, &next), &observation);
+        data.observation_heads.insert(
+            &asset,
+            &(
+                (next + 1) % OBSERVATION_CAPACITY,
+                (count + 1).min(OBSERVATION_CAPACITY),
+            ),
+        );
+    }
+    default fn _twap_config(&self, asset: AccountId) -> Option<TwapConfig> {
+        self.data::<Data>().twap_configs.get(&asset)
+    }
+    default fn _set_twap_config(
+        &mut self,
+        asset: AccountId,
+        config: TwapConfig,
+        is_token_0: bool,
+    ) -> Result<()> {
+        let mut data = self.data::<Data>();
+        data.twap_configs.insert(&asset, &config);
+        data.token_0_assets.insert(&asset, &is_token_0);
+        // observations of another pair cannot be averaged with the new ones
+        data.observation_heads.remove(&asset);
+        Ok(())
+    }
+    default fn _get_heartbeat(&self, asset: AccountId) -> Timestamp {
+        self.data::<Data>()
+            .heartbeats
+            .get(&asset)
+            .unwrap_or_default()
+    }
+    default fn _set_heartbeat(&mut self, asset: AccountId, heartbeat: Timestamp) {
+        self.data::<Data>().heartbeats.insert(&asset, &heartbeat);
+    }
+
+    default fn _emit_twap_config_updated_event(&self, _asset: AccountId, _config: TwapConfig) {}
+    default fn _emit_observation_recorded_event(
+        &self,
+        _asset: AccountId,
+        _observation: TwapObservation,
+    ) {
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observation(timestamp: Timestamp, price_cumulative: U256) -> TwapObservation {
+        TwapObservation {
+            timestamp,
+            price_cumulative: price_cumulative.into(),
+        }
+    }
+
+    #[test]
+    fn test_twap_config_is_valid() {
+        struct Case {
+            config: TwapConfig,
+            want: bool,
+        }
+        let pair = AccountId::from([0x01; 32]);
+        let cases: &[Case] = &[
+            Case {
+                config: TwapConfig {
+                    pair,
+                    window: 3600,
+                    period: 300,
+                    min_observations: 3,
+                    ..Default::default()
+                },
+                want: true,
+            },
+            Case {
+                config: TwapConfig {
+                    pair,
+                    window: 0,
+                    period: 300,
+                    min_observations: 3,
+                    ..Default::default()
+                },
+                want: false,
+            },
+            Case {
+                config: TwapConfig {
+                    pair,
+                    window: 3600,
+                    period: 300,
+                    min_observations: 0,
+                    ..Default::default()
+                },
+                want: false,
+            },
+            Case {
+                config: TwapConfig {
+                    pair,
+                    window: 3600,
+                    period: 100,
+                    min_observations: 3,
+                    ..Default::default()
+                },
+                want: false,
+            },
+            Case {
+                config: TwapConfig {
+                    pair,
+                    window: 3600,
+                    period: 1200,
+                    min_observations: 5,
+                    ..Default::default()
+                },
+                want: false,
+            },
+        ];
+        for case in cases {
+            assert_eq!(twap_config_is_valid(&case.config), case.want);
+        }
+    }
+
+    #[test]
+    fn test_time_weighted_average() {
+        struct Case {
+            start: TwapObservation,
+            end: TwapObservation,
+            want: Option<u128>,
+        }
+        let cases: &[Case] = &[
+            Case {
+                start: observation(100, U256::from(1000)),
+                end: observation(110, U256::from(1500)),
+                want: Some(50),
+            },
+            Case {
+                start: observation(100, U256::MAX - U256::from(99)),
+                end: observation(110, U256::from(400)),
+                want: Some(50),
+            },
+            Case {
+                start: observation(100, U256::from(1000)),
+                end: observation(100, U256::from(1000)),
+                want: None,
+            },
+            Case {
+                start: observation(100, U256::zero()),
+                end: observation(101, U256::from(u128::MAX) + U256::one()),
+                want: None,
+            },
+        ];
+        for case in cases {
+            assert_eq!(time_weighted_average(&case.start, &case.end), case.want);
+        }
+    }
+
+    #[test]
+    fn test_normalize_price() {
+        struct Case {
+            price: u128,
+            asset_decimals: u8,
+            quote_decimals: u8,
+            quote_price: Option<u128>,
+            want: Option<u128>,
+        }
+        let cases: &[Case] = &[
+            // 1,000 of a 6 decimals asset against 2,000 of a 18 decimals quote
+            Case {
+                price: 2 * 10_u128.pow(30),
+                asset_decimals: 6,
+                quote_decimals: 18,
+                quote_price: None,
+                want: Some(2 * PRICE_PRECISION),
+            },
+            // 1,000 of a 18 decimals asset against 2,000 of a 6 decimals quote
+            Case {
+                price: 2 * 10_u128.pow(6),
+                asset_decimals: 18,
+                quote_decimals: 6,
+                quote_price: None,
+                want: Some(2 * PRICE_PRECISION),
+            },
+            Case {
+                price: 2 * PRICE_PRECISION,
+                asset_decimals: 18,
+                quote_decimals: 18,
+                quote_price: None,
+                want: Some(2 * PRICE_PRECISION),
+            },
+            Case {
+                price: 2 * 10_u128.pow(30),
+                asset_decimals: 6,
+                quote_decimals: 18,
+                quote_price: Some(PRICE_PRECISION / 2),
+                want: Some(PRICE_PRECISION),
+            },
+            Case {
+                price: u128::MAX,
+                asset_decimals: 18,
+                quote_decimals: 6,
+                quote_price: None,
+                want: None,
+            },
+            Case {
+                price: 1,
+                asset_decimals: 80,
+                quote_decimals: 18,
+                quote_price: None,
+                want: None,
+            },
+        ];
+        for case in cases {
+            assert_eq!(
+                normalize_price(
+                    case.price,
+                    case.asset_decimals,
+                    case.quote_decimals,
+                    case.quote_price
+                ),
+                case.want
+            );
+        }
+    }
+}
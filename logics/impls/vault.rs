@@ -0,0 +1,319 @@
+// Copyright 2023 Asynmatrix Pte. Ltd.
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+pub use crate::traits::vault::*;
+use crate::traits::{
+    controller::{
+        ControllerRef,
+        Error as ControllerError,
+        PoolAttributes,
+        ACTION_MINT,
+        ACTION_REDEEM,
+    },
+    pool::{
+        Error,
+        Pool,
+        Result,
+    },
+};
+use openbrush::{
+    contracts::psp22::{
+        self,
+        extensions::metadata::PSP22Metadata,
+        Internal as PSP22Internal,
+        PSP22Error,
+    },
+    traits::{
+        AccountId,
+        Balance,
+        Storage,
+    },
+};
+
+use super::{
+    exp_no_err::Exp,
+    pool::{
+        utils::{
+            pool_balance,
+            pool_balance_round_up,
+            underlying_balance,
+            underlying_balance_round_up,
+        },
+        Data,
+        Internal as PoolInternal,
+    },
+};
+
+/// Rounding direction of the conversions between assets and shares
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+pub trait Internal {
+    fn _total_assets(&self) -> Balance;
+    fn _convert_to_shares(&self, assets: Balance, rounding: Rounding) -> Balance;
+    fn _convert_to_assets(&self, shares: Balance, rounding: Rounding) -> Balance;
+    fn _max_deposit(&self, receiver: AccountId) -> Balance;
+    fn _max_withdraw(&self, owner: AccountId) -> Balance;
+    fn _max_redeem(&self, owner: AccountId) -> Balance;
+    fn _redeem_paused(&self, owner: AccountId) -> bool;
+    fn _withdraw(
+        &mut self,
+        caller: AccountId,
+        receiver: AccountId,
+        owner: AccountId,
+        assets: Balance,
+        shares: Balance,
+    ) -> Result<()>;
+}
+
+impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metadata::Data>> Vault
+    for T
+{
+    default fn asset(&self) -> Option<AccountId> {
+        self._underlying()
+    }
+
+    default fn total_assets(&self) -> Balance {
+        self._total_assets()
+    }
+
+    default fn convert_to_shares(&self, assets: Balance) -> Balance {
+        self._convert_to_shares(assets, Rounding::Down)
+    }
+
+    default fn convert_to_assets(&self, shares: Balance) -> Balance {
+        self._convert_to_assets(shares, Rounding::Down)
+    }
+
+    default fn max_deposit(&self, receiver: AccountId) -> Balance {
+        self._max_deposit(receiver)
+    }
+
+    default fn max_mint(&self, receiver: AccountId) -> Balance {
+        let max_deposit = self._max_deposit(receiver);
+        if max_deposit == Balance::MAX {
+            return Balance::MAX
+        }
+        self._convert_to_shares(max_deposit, Rounding::Down)
+    }
+
+    default fn max_withdraw(&self, owner: AccountId) -> Balance {
+        self._max_withdraw(owner)
+    }
+
+    default fn max_redeem(&self, owner: AccountId) -> Balance {
+        self._max_redeem(owner)
+    }
+
+    default fn preview_deposit(&self, assets: Balance) -> Balance {
+        self._convert_to_shares(assets, Rounding::Down)
+    }
+
+    default fn preview_mint(&self, shares: Balance) -> Balance {
+        self._convert_to_assets(shares, Rounding::Up)
+    }
+
+    default fn preview_withdraw(&self, assets: Balance) -> Balance {
+        self._convert_to_shares(assets, Rounding::Up)
+    }
+
+    default fn preview_redeem(&self, shares: Balance) -> Balance {
+        self._convert_to_assets(shares, Rounding::Down)
+    }
+
+    default fn deposit(&mut self, assets: Balance, receiver: AccountId) -> Result<Balance> {
+        self._accrue_interest()?;
+        // the receiver does not get the dead shares locked by the first deposit
+        let balance_before = self._balance_of(&receiver);
+        self._mint(receiver, assets)?;
+        Ok(self._balance_of(&receiver) - balance_before)
+    }
+
+    default fn withdraw(
+        &mut self,
+        assets: Balance,
+        receiver: AccountId,
+        owner: AccountId,
+    ) -> Result<Balance> {
+        self._accrue_interest()?;
+        let shares = self._convert_to_shares(assets, Rounding::Up);
+        self._withdraw(Self::env().caller(), receiver, owner, assets, shares)?;
+        self._update_liquidation_auction(owner)?;
+        Ok(shares)
+    }
+}
+
+impl<T: Storage<Data> + Storage<psp22::Data> + Storage<psp22::extensions::metadata::Data>> Internal
+    for T
+{
+    default fn _total_assets(&self) -> Balance {
+        (self._get_cash_prior() + self._total_borrows() + self._deficit())
+            .saturating_sub(self._total_reserves())
+    }
+
+    default fn _convert_to_shares(&self, assets: Balance, rounding: Rounding) -> Balance {
+        let exchange_rate = Exp {
+            mantissa: self._exchange_rate_stored().into(),
+        };
+        match rounding {
+            Rounding::Down => pool_balance(exchange_rate, assets),
+            Rounding::Up => pool_balance_round_up(exchange_rate, assets),
+        }
+    }
+
+    default fn _convert_to_assets(&self, shares: Balance, rounding: Rounding) -> Balance {
+        let exchange_rate = Exp {
+            mantissa: self._exchange_rate_stored().into(),
+        };
+        match rounding {
+            Rounding::Down => underlying_balance(exchange_rate, shares),
+            Rounding::Up => underlying_balance_round_up(exchange_rate, shares),
+        }
+    }
+
+    default fn _max_deposit(&self, _receiver: AccountId) -> Balance {
+        let controller = match self._controller() {
+            Some(controller) => controller,
+            None => return 0,
+        };
+        if ControllerRef::is_shutdown(&controller) {
+            return 0
+        }
+        let pool = Self::env().account_id();
+        if !ControllerRef::is_listed(&controller, pool)
+            || ControllerRef::action_paused(&controller, pool, ACTION_MINT)
+        {
+            return 0
+        }
+        if ControllerRef::market_frozen(&controller, pool) {
+            return 0
+        }
+        match ControllerRef::supply_cap(&controller, pool) {
+            Some(0) | None => Balance::MAX,
+            Some(supply_cap) => {
+                let total_supplied = self._convert_to_assets(self._total_supply(), Rounding::Up);
+                supply_cap.saturating_sub(total_supplied)
+            }
+        }
+    }
+
+    default fn _max_withdraw(&self, owner: AccountId) -> Balance {
+        if self._redeem_paused(owner) {
+            return 0
+        }
+        let assets = self._convert_to_assets(self._balance_of(&owner), Rounding::Down);
+        assets.min(self._get_cash_prior())
+    }
+
+    default fn _max_redeem(&self, owner: AccountId) -> Balance {
+        if self._redeem_paused(owner) {
+            return 0
+        }
+        let max_shares = self._convert_to_shares(self._get_cash_prior(), Rounding::Down);
+        self._balance_of(&owner).min(max_shares)
+    }
+
+    default fn _redeem_paused(&self, owner: AccountId) -> bool {
+        let controller = match self._controller() {
+            Some(controller) => controller,
+            None => return true,
+        };
+        let pool = Self::env().account_id();
+        if !ControllerRef::is_listed(&controller, pool)
+            || ControllerRef::action_paused(&controller, pool, ACTION_REDEEM)
+        {
+            return true
+        }
+        if !ControllerRef::is_shutdown(&controller) {
+            return false
+        }
+        // once shut down, only the accounts without borrows can take their collateral back
+        let account_balance = if self._using_reserve_as_collateral(owner).unwrap_or(false) {
+            self._balance_of(&owner)
+        } else {
+            0
+        };
+        let pool_attribute = PoolAttributes {
+            pool: Some(pool),
+            underlying: self._underlying(),
+            decimals: self.token_decimals(),
+            liquidation_threshold: self._liquidation_threshold(),
+            account_balance,
+            account_borrow_balance: self._borrow_balance_stored(owner),
+            exchange_rate: self._exchange_rate_stored(),
+            total_borrows: self._total_borrows(),
+            total_supply: self._total_supply(),
+        };
+        matches!(
+            ControllerRef::redeem_allowed(&controller, pool, owner, 0, Some(pool_attribute)),
+            Err(ControllerError::ShutdownDebtOutstanding)
+        )
+    }
+
+    default fn _withdraw(
+        &mut self,
+        caller: AccountId,
+        receiver: AccountId,
+        owner: AccountId,
+        assets: Balance,
+        shares: Balance,
+    ) -> Result<()> {
+        self._accrue_reward(owner)?;
+        if caller != owner {
+            let allowance = self._allowance(&owner, &caller);
+            if allowance < shares {
+                return Err(Error::from(PSP22Error::InsufficientAllowance))
+            }
+            self._approve_from_to(owner, caller, allowance - shares)?;
+        }
+
+        let controller = self._controller().ok_or(Error::ControllerIsNotSet)?;
+        let (account_balance, account_borrow_balance, exchange_rate) =
+            self.get_account_snapshot(owner)?;
+        let contract_addr = Self::env().account_id();
+        let pool_attribute = PoolAttributes {
+            pool: Some(contract_addr),
+            underlying: self._underlying(),
+            decimals: self.token_decimals(),
+            liquidation_threshold: self._liquidation_threshold(),
+            account_balance,
+            account_borrow_balance,
+            exchange_rate,
+            total_borrows: self._total_borrows(),
+            total_supply: self._total_supply(),
+        };
+        ControllerRef::redeem_allowed(
+            &controller,
+            contract_addr,
+            owner,
+            shares,
+            Some(pool_attribute),
+        )?;
+
+        let current_block_timestamp = Self::env().block_timestamp();
+        if self._accrual_block_timestamp() != current_block_timestamp {
+            return Err(Error::AccrualBlockNumberIsNotFresh)
+        }
+        if self._get_cash_prior() < assets {
+            return Err(Error::RedeemTransferOutNotPossible)
+        }
+
+        if self._balance_of(&owner) == shares {
+            self._set_use_reserve_as_collateral(owner, false);
+        }
+
+        self._burn_from(owner, shares)?;
+        self._update_account_market(owner)?;
+        self._transfer_underlying(receiver, assets)?;
+
+        self._emit_redeem_event(owner, assets);
+
+        Ok(())
+    }
+}
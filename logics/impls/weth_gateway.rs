@@ -7,6 +7,7 @@
 
 pub use crate::traits::weth_gateway::*;
 use crate::traits::{
+    controller::ControllerRef,
     pool::PoolRef,
     types::WrappedU256,
     weth::*,
@@ -51,6 +52,7 @@ pub trait Internal {
     ) -> Result<()>;
     fn _emergency_ether_transfer(&mut self, to: AccountId, amount: Balance) -> Result<()>;
     fn _safe_transfer_eth(&self, to: AccountId, value: Balance) -> Result<()>;
+    fn _assert_not_shutdown(&self) -> Result<()>;
     fn _emit_deposit_eth_event_(&self, pool: AccountId, from: AccountId, value: Balance);
     fn _emit_withdraw_eth_event_(&self, pool: AccountId, to: AccountId, value: Balance);
     fn _emit_borrow_eth_event_(&self, pool: AccountId, to: AccountId, value: Balance);
@@ -61,6 +63,7 @@ pub trait Internal {
 
 impl<T: Storage<Data> + Storage<ownable::Data>> Internal for T {
     default fn _deposit_eth(&mut self) -> Result<()> {
+        self._assert_not_shutdown()?;
         let deposit_value = Self::env().transferred_value();
         let caller = Self::env().caller();
         let weth = self._weth_address();
@@ -143,6 +146,7 @@ impl<T: Storage<Data> + Storage<ownable::Data>> Internal for T {
     }
 
     default fn _borrow_eth(&mut self, amount: Balance) -> Result<()> {
+        self._assert_not_shutdown()?;
         let caller = Self::env().caller();
         let weth = self._weth_address();
         let pool = self._pool_address();
@@ -175,6 +179,15 @@ impl<T: Storage<Data> + Storage<ownable::Data>> Internal for T {
         Ok(())
     }
 
+    default fn _assert_not_shutdown(&self) -> Result<()> {
+        if let Some(controller) = PoolRef::controller(&self._pool_address()) {
+            if ControllerRef::is_shutdown(&controller) {
+                return Err(Error::ProtocolIsShutdown)
+            }
+        }
+        Ok(())
+    }
+
     default fn _weth_address(&self) -> AccountId {
         self.data::<Data>().weth
     }
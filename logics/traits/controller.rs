@@ -9,6 +9,7 @@ use ink::prelude::vec::Vec;
 use openbrush::traits::{
     AccountId,
     Balance,
+    Timestamp,
 };
 use primitive_types::U256;
 use scale::{
@@ -21,13 +22,41 @@ use super::types::WrappedU256;
 #[openbrush::wrapper]
 pub type ControllerRef = dyn Controller;
 
+/// Actions of the pools, each one a bit of the pause masks
+pub const ACTION_MINT: u8 = 1 << 0;
+pub const ACTION_REDEEM: u8 = 1 << 1;
+pub const ACTION_BORROW: u8 = 1 << 2;
+pub const ACTION_REPAY: u8 = 1 << 3;
+pub const ACTION_LIQUIDATE: u8 = 1 << 4;
+pub const ACTION_SEIZE: u8 = 1 << 5;
+pub const ACTION_TRANSFER: u8 = 1 << 6;
+pub const ACTION_FLASHLOAN: u8 = 1 << 7;
+
+/// Names of the actions, used in the pause events
+pub const ACTION_NAMES: [(u8, &str); 8] = [
+    (ACTION_MINT, "Mint"),
+    (ACTION_REDEEM, "Redeem"),
+    (ACTION_BORROW, "Borrow"),
+    (ACTION_REPAY, "Repay"),
+    (ACTION_LIQUIDATE, "Liquidate"),
+    (ACTION_SEIZE, "Seize"),
+    (ACTION_TRANSFER, "Transfer"),
+    (ACTION_FLASHLOAN, "Flashloan"),
+];
+
 /// Trait defines the interface for the controller of a lending protocol.
 /// It contains a set of functions that are responsible for validating and calculating various actions related to lending, such as minting, borrowing, and liquidation.
 #[openbrush::trait_definition]
 pub trait Controller {
     /// Checks if the account should be allowed to mint tokens in the given market
     #[ink(message)]
-    fn mint_allowed(&self, pool: AccountId, minter: AccountId, mint_amount: Balance) -> Result<()>;
+    fn mint_allowed(
+        &self,
+        pool: AccountId,
+        minter: AccountId,
+        mint_amount: Balance,
+        pool_attribute: Option<PoolAttributes>,
+    ) -> Result<()>;
 
     /// Checks if the account should be allowed to redeem tokens in the given market
     #[ink(message)]
@@ -49,10 +78,21 @@ pub trait Controller {
         pool_attribute: Option<PoolAttributes>,
     ) -> Result<()>;
 
-    /// Checks if the liquidation should be allowed to occur
+    /// Checks if the account should be allowed to repay a borrow in the given market
     #[ink(message)]
-    fn liquidate_borrow_allowed(
+    fn repay_borrow_allowed(
         &self,
+        pool: AccountId,
+        payer: AccountId,
+        borrower: AccountId,
+        repay_amount: Balance,
+    ) -> Result<()>;
+
+    /// Checks if the liquidation should be allowed to occur.
+    /// Starts the liquidation auction of the borrower in shortfall.
+    #[ink(message)]
+    fn liquidate_borrow_allowed(
+        &mut self,
         pool_borrowed: AccountId,
         pool_collateral: AccountId,
         liquidator: AccountId,
@@ -83,12 +123,17 @@ pub trait Controller {
         pool_attribute: Option<PoolAttributes>,
     ) -> Result<()>;
 
+    /// Checks if the account should be allowed to use its deposit in the given market as collateral
+    #[ink(message)]
+    fn use_reserve_as_collateral_allowed(&self, pool: AccountId, account: AccountId) -> Result<()>;
+
     /// Checks if the account should be allowed to transfer tokens in the given market
     #[ink(message)]
     fn liquidate_calculate_seize_tokens(
         &self,
         pool_borrowed: AccountId,
         pool_collateral: AccountId,
+        borrower: AccountId,
         exchange_rate_mantissa: WrappedU256,
         repay_amount: Balance,
         pool_borrowed_attributes: Option<PoolAttributesForSeizeCalculation>,
@@ -125,6 +170,14 @@ pub trait Controller {
         new_collateral_factor_mantissa: WrappedU256,
     ) -> Result<()>;
 
+    /// Pauses or unpauses the actions of the mask in the pool
+    #[ink(message)]
+    fn set_actions_paused(&mut self, pool: AccountId, actions: u8, paused: bool) -> Result<()>;
+
+    /// Pauses or unpauses the actions of the mask in all pools
+    #[ink(message)]
+    fn set_global_actions_paused(&mut self, actions: u8, paused: bool) -> Result<()>;
+
     /// Update the pause status of mint action in the pool
     #[ink(message)]
     fn set_mint_guardian_paused(&mut self, pool: AccountId, paused: bool) -> Result<()>;
@@ -133,11 +186,15 @@ pub trait Controller {
     #[ink(message)]
     fn set_borrow_guardian_paused(&mut self, pool: AccountId, paused: bool) -> Result<()>;
 
-    /// Update the pause status of seize action in the pool
+    /// Freezes the pool: blocks new supply and borrows, while repay, redeem, liquidation and transfers keep working
+    #[ink(message)]
+    fn set_market_frozen(&mut self, pool: AccountId, frozen: bool) -> Result<()>;
+
+    /// Update the pause status of seize action in all pools
     #[ink(message)]
     fn set_seize_guardian_paused(&mut self, paused: bool) -> Result<()>;
 
-    /// Update the transfer status of seize action in the pool
+    /// Update the pause status of transfer action in all pools
     #[ink(message)]
     fn set_transfer_guardian_paused(&mut self, paused: bool) -> Result<()>;
 
@@ -152,11 +209,102 @@ pub trait Controller {
         new_liquidation_incentive_mantissa: WrappedU256,
     ) -> Result<()>;
 
+    /// Sets the liquidation incentive applied when seizing the collateral of the given pool.
+    /// None falls back to the controller-wide liquidation incentive.
+    #[ink(message)]
+    fn set_market_liquidation_incentive_mantissa(
+        &mut self,
+        pool: AccountId,
+        new_liquidation_incentive_mantissa: Option<WrappedU256>,
+    ) -> Result<()>;
+
+    /// Sets the share of the seized collateral added to the reserves of the pools
+    /// without a protocol seize share of their own
+    #[ink(message)]
+    fn set_protocol_seize_share_mantissa(
+        &mut self,
+        new_protocol_seize_share_mantissa: WrappedU256,
+    ) -> Result<()>;
+
+    /// Sets the share of the seized collateral the given pool adds to its reserves.
+    /// None falls back to the controller-wide protocol seize share.
+    #[ink(message)]
+    fn set_market_protocol_seize_share_mantissa(
+        &mut self,
+        pool: AccountId,
+        new_protocol_seize_share_mantissa: Option<WrappedU256>,
+    ) -> Result<()>;
+
+    /// Sets the liquidation auction, in which the liquidation incentive rises linearly
+    /// from the start incentive to the one of the collateral market over the duration.
+    /// 0 duration disables the auction.
+    #[ink(message)]
+    fn set_liquidation_auction(
+        &mut self,
+        duration: Timestamp,
+        start_incentive_mantissa: WrappedU256,
+    ) -> Result<()>;
+
+    /// Sets the health factor below which a borrow can be fully liquidated
+    #[ink(message)]
+    fn set_critical_health_factor(&mut self, new_critical_health_factor: WrappedU256)
+        -> Result<()>;
+
+    /// Sets the value in base currency below which a debt or collateral position can be fully liquidated
+    #[ink(message)]
+    fn set_liquidation_dust_threshold(&mut self, new_dust_threshold: Balance) -> Result<()>;
+
     /// Set the given borrow caps for the given pool.
     /// Borrowing that brings total borrows to or above borrow cap will revert.
     #[ink(message)]
     fn set_borrow_cap(&mut self, pool: AccountId, new_cap: Balance) -> Result<()>;
 
+    /// Set the given supply caps for the given pool.
+    /// Supplying that brings total supplies above supply cap will revert.
+    #[ink(message)]
+    fn set_supply_cap(&mut self, pool: AccountId, new_cap: Balance) -> Result<()>;
+
+    /// Sets the parameters of the e-mode category
+    #[ink(message)]
+    fn set_emode_category(&mut self, id: u8, category: EModeCategory) -> Result<()>;
+
+    /// Sets the e-mode category the pool belongs to, 0 removes it from any category
+    #[ink(message)]
+    fn set_market_emode_category(&mut self, pool: AccountId, id: u8) -> Result<()>;
+
+    /// Sets whether the pool is isolated and the debt ceiling (in base currency) on the total debt of all the accounts using it as collateral.
+    /// Accounts using an isolated asset as collateral cannot use any other asset as collateral. An isolated pool requires a non-zero ceiling.
+    #[ink(message)]
+    fn set_isolation_mode(
+        &mut self,
+        pool: AccountId,
+        isolated: bool,
+        debt_ceiling: Balance,
+    ) -> Result<()>;
+
+    /// Sets whether the pool can be borrowed by the accounts using an isolated asset as collateral
+    #[ink(message)]
+    fn set_borrowable_in_isolation(&mut self, pool: AccountId, borrowable: bool) -> Result<()>;
+
+    /// Deprecates the pool: pauses mint and borrow, and ramps its collateral factor down to zero over `ramp_duration`.
+    /// Its borrows can be repaid by liquidation up to the whole balance, without shortfall once the ramp ends.
+    #[ink(message)]
+    fn deprecate_market(&mut self, pool: AccountId, ramp_duration: Timestamp) -> Result<()>;
+
+    /// Removes the deprecated pool once nothing is supplied to or borrowed from it
+    #[ink(message)]
+    fn remove_market(&mut self, pool: AccountId) -> Result<()>;
+
+    /// Shuts the protocol down for settlement, freezing the prices within their heartbeat at their current values
+    ///
+    /// Only repaying and, once all of its debt is repaid, redeeming stay available to an account. This cannot be undone.
+    #[ink(message)]
+    fn shutdown(&mut self) -> Result<()>;
+
+    /// Overrides the frozen price of the underlying of the pool while the protocol is shut down
+    #[ink(message)]
+    fn set_settlement_price(&mut self, pool: AccountId, price: u128) -> Result<()>;
+
     /// Set Manager
     #[ink(message)]
     fn set_manager(&mut self, manager: AccountId) -> Result<()>;
@@ -165,11 +313,44 @@ pub trait Controller {
     #[ink(message)]
     fn accept_manager(&mut self) -> Result<()>;
 
+    /// Opts the caller in to the e-mode category, 0 opts out
+    ///
+    /// All assets borrowed by the caller must belong to the category
+    #[ink(message)]
+    fn set_user_emode(&mut self, id: u8) -> Result<()>;
+
+    /// Starts the liquidation auction of the account if it has shortfall, or resets it if not.
+    /// Anyone can call this, the pool of `pool_attributes` must be the caller.
+    #[ink(message)]
+    fn update_liquidation_auction(
+        &mut self,
+        account: AccountId,
+        pool_attributes: Option<PoolAttributes>,
+    ) -> Result<()>;
+
+    /// Records whether the account has a balance or debt in the calling pool (call from Pool)
+    #[ink(message)]
+    fn update_account_market(&mut self, account: AccountId, entered: bool) -> Result<()>;
+
+    /// Records the change of the borrow balance of the account in the calling pool
+    /// against the debt of its isolated collateral, if any (call from Pool)
+    #[ink(message)]
+    fn update_isolated_debt(
+        &mut self,
+        account: AccountId,
+        borrow_balance_prev: Balance,
+        borrow_balance_new: Balance,
+    ) -> Result<()>;
+
     // view function
     /// Returns the list of all markets that are currently supported
     #[ink(message)]
     fn markets(&self) -> Vec<AccountId>;
 
+    /// Returns the markets where the account has a balance or debt
+    #[ink(message)]
+    fn account_markets(&self, account: AccountId) -> Vec<AccountId>;
+
     #[ink(message)]
     fn flashloan_gateway(&self) -> Option<AccountId>;
 
@@ -185,6 +366,18 @@ pub trait Controller {
     #[ink(message)]
     fn collateral_factor_mantissa(&self, pool: AccountId) -> Option<WrappedU256>;
 
+    /// Returns the mask of the actions paused in a given pool
+    #[ink(message)]
+    fn paused_actions(&self, pool: AccountId) -> u8;
+
+    /// Returns the mask of the actions paused in all pools
+    #[ink(message)]
+    fn global_paused_actions(&self) -> u8;
+
+    /// Returns whether an action is paused in a given pool, by its own mask or the global one
+    #[ink(message)]
+    fn action_paused(&self, pool: AccountId, action: u8) -> bool;
+
     /// Returns the current mint pause status for a given pool
     #[ink(message)]
     fn mint_guardian_paused(&self, pool: AccountId) -> Option<bool>;
@@ -193,6 +386,10 @@ pub trait Controller {
     #[ink(message)]
     fn borrow_guardian_paused(&self, pool: AccountId) -> Option<bool>;
 
+    /// Returns whether a given pool is frozen
+    #[ink(message)]
+    fn market_frozen(&self, pool: AccountId) -> bool;
+
     /// Returns the current seize pause status
     #[ink(message)]
     fn seize_guardian_paused(&self) -> bool;
@@ -213,10 +410,86 @@ pub trait Controller {
     #[ink(message)]
     fn liquidation_incentive_mantissa(&self) -> WrappedU256;
 
+    /// Returns the liquidation incentive of a given pool if it overrides the controller-wide one
+    #[ink(message)]
+    fn market_liquidation_incentive_mantissa(&self, pool: AccountId) -> Option<WrappedU256>;
+
+    /// Returns the protocol seize share
+    #[ink(message)]
+    fn protocol_seize_share_mantissa(&self) -> WrappedU256;
+
+    /// Returns the protocol seize share of a given pool if it overrides the controller-wide one
+    #[ink(message)]
+    fn market_protocol_seize_share_mantissa(&self, pool: AccountId) -> Option<WrappedU256>;
+
+    /// Returns the duration of the liquidation auction
+    #[ink(message)]
+    fn liquidation_auction_duration(&self) -> Timestamp;
+
+    /// Returns the liquidation incentive at the start of the liquidation auction
+    #[ink(message)]
+    fn liquidation_auction_start_incentive_mantissa(&self) -> WrappedU256;
+
+    /// Returns when the liquidation auction of the account started
+    #[ink(message)]
+    fn liquidation_auction_start(&self, account: AccountId) -> Option<Timestamp>;
+
+    /// Returns the health factor below which a borrow can be fully liquidated
+    #[ink(message)]
+    fn critical_health_factor(&self) -> WrappedU256;
+
+    /// Returns the liquidation dust threshold
+    #[ink(message)]
+    fn liquidation_dust_threshold(&self) -> Balance;
+
     /// Returns the borrow cap for a given pool
     #[ink(message)]
     fn borrow_cap(&self, pool: AccountId) -> Option<Balance>;
 
+    /// Returns the supply cap for a given pool
+    #[ink(message)]
+    fn supply_cap(&self, pool: AccountId) -> Option<Balance>;
+
+    /// Returns the parameters of the e-mode category
+    #[ink(message)]
+    fn emode_category(&self, id: u8) -> Option<EModeCategory>;
+
+    /// Returns the e-mode category of a given pool
+    #[ink(message)]
+    fn market_emode_category(&self, pool: AccountId) -> u8;
+
+    /// Returns the e-mode category chosen by a given account
+    #[ink(message)]
+    fn user_emode(&self, account: AccountId) -> u8;
+
+    /// Returns whether a given pool is isolated
+    #[ink(message)]
+    fn isolated(&self, pool: AccountId) -> bool;
+
+    /// Returns the debt ceiling of a given isolated pool
+    #[ink(message)]
+    fn debt_ceiling(&self, pool: AccountId) -> Balance;
+
+    /// Returns the total debt (in base currency) of the accounts using a given isolated pool as collateral
+    #[ink(message)]
+    fn isolated_debt(&self, pool: AccountId) -> Balance;
+
+    /// Returns whether a given pool can be borrowed in isolation mode
+    #[ink(message)]
+    fn borrowable_in_isolation(&self, pool: AccountId) -> bool;
+
+    /// Returns the deprecation of a given pool, None if it is not deprecated
+    #[ink(message)]
+    fn market_deprecation(&self, pool: AccountId) -> Option<MarketDeprecation>;
+
+    /// Returns whether the protocol is shut down
+    #[ink(message)]
+    fn is_shutdown(&self) -> bool;
+
+    /// Returns the frozen price of the underlying of a given pool, None if the protocol is not shut down
+    #[ink(message)]
+    fn settlement_price(&self, pool: AccountId) -> Option<u128>;
+
     /// Returns the account id of the manager account
     #[ink(message)]
     fn manager(&self) -> Option<AccountId>;
@@ -278,6 +551,7 @@ pub struct PoolAttributes {
     pub account_borrow_balance: Balance,
     pub exchange_rate: U256,
     pub total_borrows: Balance,
+    pub total_supply: Balance,
 }
 
 /// Structure for having information for Seize about the Pool
@@ -290,6 +564,36 @@ pub struct PoolAttributesForSeizeCalculation {
     pub decimals: u8,
 }
 
+/// Risk parameters for the correlated assets of an e-mode category
+///
+/// Applied instead of the parameters of the market to the accounts opting in to the category
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, Default)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct EModeCategory {
+    /// Collateral Factor (Decimals: 18)
+    pub collateral_factor_mantissa: WrappedU256,
+    /// Liquidation Threshold (Decimals: 4)
+    pub liquidation_threshold: u128,
+    /// Liquidation Incentive (Decimals: 18)
+    pub liquidation_incentive_mantissa: WrappedU256,
+    /// Price oracle used for the assets instead of the one of the controller
+    pub price_source: Option<AccountId>,
+}
+
+/// Deprecation of a market, whose collateral factor ramps down to zero from `started_at`
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, Default)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct MarketDeprecation {
+    pub started_at: Timestamp,
+    pub ramp_duration: Timestamp,
+}
+
 /// Structure to hold status information of a user
 ///
 /// Used to retrieve the status of all users in the Protocol pool and to make the calculated results available for use and reference.
@@ -326,12 +630,16 @@ pub enum Error {
     BorrowIsPaused,
     SeizeIsPaused,
     TransferIsPaused,
+    RedeemIsPaused,
+    RepayIsPaused,
+    LiquidateIsPaused,
     MarketNotListed,
     MarketAlreadyListed,
     ControllerMismatch,
     PriceError,
     TooMuchRepay,
     BorrowCapReached,
+    SupplyCapReached,
     InsufficientLiquidity,
     InsufficientShortfall,
     CallerIsNotManager,
@@ -345,6 +653,26 @@ pub enum Error {
     BalanceDecreaseNotAllowed,
     MarketCountReachedToMaximum,
     PoolError,
+    InvalidEModeCategory,
+    EModeCategoryNotFound,
+    InconsistentEModeCategory,
+    InconsistentIsolationMode,
+    NotBorrowableInIsolation,
+    DebtCeilingReached,
+    InvalidDebtCeiling,
+    InvalidCriticalHealthFactor,
+    InvalidLiquidationIncentive,
+    InvalidProtocolSeizeShare,
+    CallerIsNotPool,
+    MarketDeprecated,
+    MarketFrozen,
+    MarketAlreadyDeprecated,
+    MarketNotDeprecated,
+    MarketNotEmpty,
+    ProtocolIsShutdown,
+    ProtocolIsNotShutdown,
+    ShutdownDebtOutstanding,
+    StalePrice,
 }
 
 pub type Result<T> = core::result::Result<T, Error>;
@@ -0,0 +1,52 @@
+// Copyright 2023 Asynmatrix Pte. Ltd.
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::types::WrappedU256;
+use openbrush::traits::{
+    AccountId,
+    Balance,
+    Timestamp,
+};
+use scale::{
+    Decode,
+    Encode,
+};
+
+#[openbrush::wrapper]
+pub type DexPairRef = dyn DexPair;
+
+/// Trait defines the functions of a DEX pair read by the TwapOracle.
+/// The pair accumulates its spot prices weighted by the time they were held, as Uniswap V2 does.
+#[openbrush::trait_definition]
+pub trait DexPair {
+    #[ink(message)]
+    fn token_0(&self) -> AccountId;
+
+    #[ink(message)]
+    fn token_1(&self) -> AccountId;
+
+    /// Returns the reserves of the tokens and when they were last updated.
+    #[ink(message)]
+    fn get_reserves(&self) -> (Balance, Balance, Timestamp);
+
+    /// Returns the prices of token_0 in token_1 summed over time, as of the last update.
+    #[ink(message)]
+    fn price_0_cumulative_last(&self) -> WrappedU256;
+
+    /// Returns the prices of token_1 in token_0 summed over time, as of the last update.
+    #[ink(message)]
+    fn price_1_cumulative_last(&self) -> WrappedU256;
+}
+
+/// Custom error definitions for DexPair
+#[derive(Debug, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Error {
+    InsufficientLiquidity,
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
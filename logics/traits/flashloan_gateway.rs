@@ -55,6 +55,8 @@ pub enum Error {
     DuplicatedFlashloanAssets,
     MarketNotListed,
     ControllerIsNotSet,
+    FlashloanIsPaused,
+    ProtocolIsShutdown,
     PSP22(PSP22Error),
     Pool(PoolError),
 }
@@ -19,6 +19,15 @@ pub trait InterestRateModel {
     #[ink(message)]
     fn get_borrow_rate(&self, cash: Balance, borrows: Balance, reserves: Balance) -> WrappedU256;
 
+    /// Calculates the stable borrow interest rate per milliseconds offered to new stable borrows
+    #[ink(message)]
+    fn get_stable_borrow_rate(
+        &self,
+        cash: Balance,
+        borrows: Balance,
+        reserves: Balance,
+    ) -> WrappedU256;
+
     /// Calculates the current supply interest rate per milliseconds
     #[ink(message)]
     fn get_supply_rate(
@@ -134,6 +134,7 @@ pub enum Error {
     ControllerIsNotSet,
     MarketNotListed,
     WETHIsNotSet,
+    ProtocolIsShutdown,
     Controller(ControllerError),
     Pool(PoolError),
     PSP22(PSP22Error),
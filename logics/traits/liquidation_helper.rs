@@ -0,0 +1,81 @@
+// Copyright 2023 Asynmatrix Pte. Ltd.
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use ink::prelude::vec::Vec;
+use openbrush::{
+    contracts::psp22::PSP22Error,
+    traits::{
+        AccountId,
+        Balance,
+    },
+};
+use scale::{
+    Decode,
+    Encode,
+};
+
+pub use super::pool::Error as PoolError;
+
+#[openbrush::wrapper]
+pub type LiquidationHelperRef = dyn LiquidationHelper;
+
+/// Trait defines the interface for the Liquidation Helper
+#[openbrush::trait_definition]
+pub trait LiquidationHelper {
+    /// Get Controller AccountId
+    #[ink(message)]
+    fn controller(&self) -> AccountId;
+
+    /// Liquidates the borrows of the given pool in order, on behalf of the sender.
+    /// The sender approves the sum of the repay amounts in the underlying of the pool to this contract,
+    /// and receives the seized collateral and the unused underlying back.
+    /// If `skip_failures` is set, a failed entry is reverted alone and does not abort the batch.
+    #[ink(message)]
+    fn liquidate_borrows(
+        &mut self,
+        pool: AccountId,
+        liquidations: Vec<LiquidationParams>,
+        receive_underlying: bool,
+        skip_failures: bool,
+    ) -> Result<Vec<Result<()>>>;
+}
+
+/// Entry of the batch liquidation
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct LiquidationParams {
+    pub borrower: AccountId,
+    pub repay_amount: Balance,
+    pub collateral: AccountId,
+}
+
+/// Custom error definitions for Liquidation Helper
+#[derive(Debug, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Error {
+    MarketNotListed,
+    UnderlyingIsNotSet,
+    LiquidatorIsBorrower,
+    LiquidationTrapped,
+    ProtocolIsShutdown,
+    Pool(PoolError),
+    PSP22(PSP22Error),
+}
+
+impl From<PoolError> for Error {
+    fn from(error: PoolError) -> Self {
+        Error::Pool(error)
+    }
+}
+
+impl From<PSP22Error> for Error {
+    fn from(error: PSP22Error) -> Self {
+        Error::PSP22(error)
+    }
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
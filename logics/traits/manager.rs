@@ -6,7 +6,10 @@
 // except according to those terms.
 
 use super::{
-    controller::Error as ControllerError,
+    controller::{
+        EModeCategory,
+        Error as ControllerError,
+    },
     pool::Error as PoolError,
 };
 use openbrush::{
@@ -17,6 +20,7 @@ use openbrush::{
     traits::{
         AccountId,
         Balance,
+        Timestamp,
     },
 };
 use scale::{
@@ -77,6 +81,18 @@ pub trait Manager {
     #[ink(message)]
     fn set_borrow_guardian_paused(&mut self, pool: AccountId, paused: bool) -> Result<()>;
 
+    /// Pauses or unpauses the actions of the mask in the pool (call Controller)
+    #[ink(message)]
+    fn set_actions_paused(&mut self, pool: AccountId, actions: u8, paused: bool) -> Result<()>;
+
+    /// Pauses or unpauses the actions of the mask in all pools (call Controller)
+    #[ink(message)]
+    fn set_global_actions_paused(&mut self, actions: u8, paused: bool) -> Result<()>;
+
+    /// Update the frozen status of the pool, blocking new supply and borrows only (call Controller)
+    #[ink(message)]
+    fn set_market_frozen(&mut self, pool: AccountId, frozen: bool) -> Result<()>;
+
     /// Sets the closeFactor used when liquidating borrows (call Controller)
     #[ink(message)]
     fn set_close_factor_mantissa(&mut self, new_close_factor_mantissa: WrappedU256) -> Result<()>;
@@ -88,10 +104,83 @@ pub trait Manager {
         new_liquidation_incentive_mantissa: WrappedU256,
     ) -> Result<()>;
 
+    /// Sets the liquidation incentive of the pool, None falls back to the global one (call Controller)
+    #[ink(message)]
+    fn set_market_liquidation_incentive_mantissa(
+        &mut self,
+        pool: AccountId,
+        new_liquidation_incentive_mantissa: Option<WrappedU256>,
+    ) -> Result<()>;
+
+    /// Sets the protocol seize share (call Controller)
+    #[ink(message)]
+    fn set_protocol_seize_share_mantissa(
+        &mut self,
+        new_protocol_seize_share_mantissa: WrappedU256,
+    ) -> Result<()>;
+
+    /// Sets the protocol seize share of the pool, None falls back to the global one (call Controller)
+    #[ink(message)]
+    fn set_market_protocol_seize_share_mantissa(
+        &mut self,
+        pool: AccountId,
+        new_protocol_seize_share_mantissa: Option<WrappedU256>,
+    ) -> Result<()>;
+
+    /// Deprecates the pool, ramping its collateral factor down to zero over `ramp_duration` (call Controller)
+    #[ink(message)]
+    fn deprecate_market(&mut self, pool: AccountId, ramp_duration: Timestamp) -> Result<()>;
+
+    /// Removes the deprecated pool with nothing supplied or borrowed (call Controller)
+    #[ink(message)]
+    fn remove_market(&mut self, pool: AccountId) -> Result<()>;
+
+    /// Sets the duration and the starting Liquidation Incentive of the liquidation auction, 0 duration disables it (call Controller)
+    #[ink(message)]
+    fn set_liquidation_auction(
+        &mut self,
+        duration: Timestamp,
+        start_incentive_mantissa: WrappedU256,
+    ) -> Result<()>;
+
+    /// Sets the health factor below which a borrow can be fully liquidated (call Controller)
+    #[ink(message)]
+    fn set_critical_health_factor(&mut self, new_critical_health_factor: WrappedU256)
+        -> Result<()>;
+
+    /// Sets the liquidation dust threshold in base currency (call Controller)
+    #[ink(message)]
+    fn set_liquidation_dust_threshold(&mut self, new_dust_threshold: Balance) -> Result<()>;
+
+    /// Sets the parameters of the e-mode category (call Controller)
+    #[ink(message)]
+    fn set_emode_category(&mut self, id: u8, category: EModeCategory) -> Result<()>;
+
+    /// Assigns the pool to the e-mode category, 0 removes it from any category (call Controller)
+    #[ink(message)]
+    fn set_market_emode_category(&mut self, pool: AccountId, id: u8) -> Result<()>;
+
+    /// Sets whether the pool is isolated and its debt ceiling (call Controller)
+    #[ink(message)]
+    fn set_isolation_mode(
+        &mut self,
+        pool: AccountId,
+        isolated: bool,
+        debt_ceiling: Balance,
+    ) -> Result<()>;
+
+    /// Sets whether the pool can be borrowed in isolation mode (call Controller)
+    #[ink(message)]
+    fn set_borrowable_in_isolation(&mut self, pool: AccountId, borrowable: bool) -> Result<()>;
+
     /// Set the given borrow caps for the given pool (call Controller)
     #[ink(message)]
     fn set_borrow_cap(&mut self, pool: AccountId, new_cap: Balance) -> Result<()>;
 
+    /// Set the given supply caps for the given pool (call Controller)
+    #[ink(message)]
+    fn set_supply_cap(&mut self, pool: AccountId, new_cap: Balance) -> Result<()>;
+
     /// accrues interest and sets a new reserve factor for the protocol using _set_reserve_factor_mantissa (call Pool)
     #[ink(message)]
     fn set_reserve_factor_mantissa(
@@ -100,6 +189,26 @@ pub trait Manager {
         new_reserve_factor_mantissa: WrappedU256,
     ) -> Result<()>;
 
+    /// Sets the minimum underlying amount of the first mint into the pool (call Pool)
+    #[ink(message)]
+    fn set_minimum_initial_deposit(
+        &mut self,
+        pool: AccountId,
+        new_minimum_initial_deposit: Balance,
+    ) -> Result<()>;
+
+    /// Sets whether the bad debt not covered by the reserves is socialized to the suppliers (call Pool)
+    #[ink(message)]
+    fn set_socialize_bad_debt(&mut self, pool: AccountId, socialize: bool) -> Result<()>;
+
+    /// Sets the maximum size of a stable rate borrow relative to the available liquidity (call Pool)
+    #[ink(message)]
+    fn set_max_stable_borrow_size_mantissa(
+        &mut self,
+        pool: AccountId,
+        new_max_stable_borrow_size_mantissa: WrappedU256,
+    ) -> Result<()>;
+
     /// Accrues interest and add reserves by transferring from admin (call Pool)
     #[ink(message)]
     fn add_reserves(&mut self, pool: AccountId, amount: Balance) -> Result<()>;
@@ -112,6 +221,10 @@ pub trait Manager {
     #[ink(message)]
     fn sweep_token(&mut self, pool: AccountId, asset: AccountId) -> Result<()>;
 
+    /// A public function to skim underlying transferred directly to the pool. (call Pool)
+    #[ink(message)]
+    fn skim(&mut self, pool: AccountId) -> Result<()>;
+
     /// A public function to set Liquidation Threshold of pool. (call Pool)
     #[ink(message)]
     fn set_liquidation_threshold(
@@ -136,6 +249,14 @@ pub trait Manager {
     #[ink(message)]
     fn set_transfer_guardian_paused(&mut self, paused: bool) -> Result<()>;
 
+    /// Shuts the protocol down for settlement, which cannot be undone (call Controller)
+    #[ink(message)]
+    fn shutdown(&mut self) -> Result<()>;
+
+    /// Overrides the frozen price of the pool's underlying while shut down (call Controller)
+    #[ink(message)]
+    fn set_settlement_price(&mut self, pool: AccountId, price: u128) -> Result<()>;
+
     /// A public function to Set Controller Manager
     #[ink(message)]
     fn set_controller_manager(&mut self, manager: AccountId) -> Result<()>;
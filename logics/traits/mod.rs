@@ -6,14 +6,19 @@
 // except according to those terms.
 
 pub mod controller;
+pub mod dex_pair;
 pub mod flashloan_gateway;
 pub mod flashloan_receiver;
 pub mod incentives_controller;
 pub mod interest_rate_model;
 pub mod leverager;
+pub mod liquidation_helper;
 pub mod manager;
 pub mod pool;
+pub mod price_aggregator;
 pub mod price_oracle;
+pub mod twap_oracle;
 pub mod types;
+pub mod vault;
 pub mod weth;
 pub mod weth_gateway;
@@ -5,7 +5,10 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use ink::LangError;
+use ink::{
+    prelude::vec::Vec,
+    LangError,
+};
 use openbrush::{
     contracts::{
         psp22::PSP22Error,
@@ -68,6 +71,14 @@ pub trait Pool: PSP22 + PSP22Metadata {
     #[ink(message)]
     fn borrow_for_flashloan(&mut self, borrower: AccountId, borrow_amount: Balance) -> Result<()>;
 
+    /// Sender borrows assets from the protocol to their own address with the given rate mode
+    #[ink(message)]
+    fn borrow_with_rate_mode(
+        &mut self,
+        borrow_amount: Balance,
+        rate_mode: BorrowRateMode,
+    ) -> Result<()>;
+
     /// Sender repays their own borrow
     #[ink(message)]
     fn repay_borrow(&mut self, repay_amount: Balance) -> Result<()>;
@@ -80,28 +91,49 @@ pub trait Pool: PSP22 + PSP22Metadata {
     #[ink(message)]
     fn repay_borrow_behalf(&mut self, borrower: AccountId, repay_amount: Balance) -> Result<()>;
 
+    /// Sender moves all of their debt in the given rate mode to the other rate mode
+    #[ink(message)]
+    fn swap_borrow_rate_mode(&mut self, rate_mode: BorrowRateMode) -> Result<()>;
+
+    /// Resets the stable rate of the user to the current stable rate
+    #[ink(message)]
+    fn rebalance_stable_borrow_rate(&mut self, user: AccountId) -> Result<()>;
+
     /// The sender liquidates the borrowers collateral.
+    /// If `receive_underlying` is set, the sender receives the seized collateral in underlying.
     #[ink(message)]
     fn liquidate_borrow(
         &mut self,
         borrower: AccountId,
         repay_amount: Balance,
         collateral: AccountId,
+        receive_underlying: bool,
     ) -> Result<()>;
 
     /// Transfers collateral tokens (this market) to the liquidator.
+    /// If `receive_underlying` is set, the tokens are redeemed and the underlying is transferred instead.
     #[ink(message)]
     fn seize(
         &mut self,
         liquidator: AccountId,
         borrower: AccountId,
         seize_tokens: Balance,
+        receive_underlying: bool,
     ) -> Result<()>;
 
+    /// Writes off the debt of the borrower left with no collateral, covering it with the reserves first.
+    /// The rest is socialized to the suppliers or recorded as the deficit of the pool.
+    #[ink(message)]
+    fn settle_bad_debt(&mut self, borrower: AccountId) -> Result<()>;
+
     /// Transfer Underlying Token.
     #[ink(message)]
     fn transfer_underlying(&mut self, to: AccountId, amount: Balance) -> Result<()>;
 
+    /// Returns Underlying Token of a flashloan by transferring from the sender.
+    #[ink(message)]
+    fn repay_flashloan(&mut self, amount: Balance) -> Result<()>;
+
     // admin functions
     /// Sets a new controller for the market
     #[ink(message)]
@@ -118,6 +150,21 @@ pub trait Pool: PSP22 + PSP22Metadata {
     #[ink(message)]
     fn set_interest_rate_model(&mut self, new_interest_rate_model: AccountId) -> Result<()>;
 
+    /// Sets the minimum underlying amount of the first mint into the pool
+    #[ink(message)]
+    fn set_minimum_initial_deposit(&mut self, new_minimum_initial_deposit: Balance) -> Result<()>;
+
+    /// Sets whether the bad debt not covered by the reserves is socialized to the suppliers instead of recorded as deficit
+    #[ink(message)]
+    fn set_socialize_bad_debt(&mut self, socialize: bool) -> Result<()>;
+
+    /// Sets the maximum size of a stable rate borrow relative to the available liquidity
+    #[ink(message)]
+    fn set_max_stable_borrow_size_mantissa(
+        &mut self,
+        new_max_stable_borrow_size_mantissa: WrappedU256,
+    ) -> Result<()>;
+
     /// Set Liquidation Threshold
     #[ink(message)]
     fn set_liquidation_threshold(&mut self, new_liquidation_threshold: u128) -> Result<()>;
@@ -126,6 +173,10 @@ pub trait Pool: PSP22 + PSP22Metadata {
     #[ink(message)]
     fn add_reserves(&mut self, amount: Balance) -> Result<()>;
 
+    /// The sender covers the deficit of the pool.
+    #[ink(message)]
+    fn cover_deficit(&mut self, amount: Balance) -> Result<()>;
+
     /// Accrues interest and reduces reserves by transferring to admin
     #[ink(message)]
     fn reduce_reserves(&mut self, amount: Balance) -> Result<()>;
@@ -134,6 +185,10 @@ pub trait Pool: PSP22 + PSP22Metadata {
     #[ink(message)]
     fn sweep_token(&mut self, asset: AccountId) -> Result<()>;
 
+    /// Sends underlying transferred directly to this contract, over the tracked cash, to admin
+    #[ink(message)]
+    fn skim(&mut self) -> Result<()>;
+
     /// Delegates borrowing power to a user on the specific debt token
     #[ink(message)]
     fn approve_delegate(&mut self, delegatee: AccountId, amount: Balance) -> Result<()>;
@@ -184,9 +239,26 @@ pub trait Pool: PSP22 + PSP22Metadata {
     /// Total borrows in pool
     #[ink(message)]
     fn total_borrows(&self) -> Balance;
+    /// Number of accounts with debt in pool
+    #[ink(message)]
+    fn borrowers_count(&self) -> u32;
+    /// Accounts with debt in pool, `limit` entries from `start`.
+    /// The last account is moved to the position of the one repaying all its debt, so positions are only stable within a block:
+    /// a scan over several blocks can skip or repeat accounts.
+    #[ink(message)]
+    fn borrowers(&self, start: u32, limit: u32) -> Vec<AccountId>;
+    /// Total stable rate borrows in pool, included in total borrows
+    #[ink(message)]
+    fn total_stable_borrows(&self) -> Balance;
+    /// Average interest rate per milliseconds of the stable rate borrows
+    #[ink(message)]
+    fn average_stable_borrow_rate(&self) -> WrappedU256;
     /// Total reserves in pool
     #[ink(message)]
     fn total_reserves(&self) -> Balance;
+    /// Bad debt not covered by reserves, yet to be covered
+    #[ink(message)]
+    fn deficit(&self) -> Balance;
     /// Get the underlying balance of the account
     #[ink(message)]
     fn balance_of_underlying(&self, account: AccountId) -> Balance;
@@ -196,15 +268,43 @@ pub trait Pool: PSP22 + PSP22Metadata {
     /// Get user's borrow without interest
     #[ink(message)]
     fn borrow_balance_stored(&self, account: AccountId) -> Balance;
+    /// Get user's stable rate borrow without interest
+    #[ink(message)]
+    fn stable_borrow_balance_stored(&self, account: AccountId) -> Balance;
+    /// Stable interest rate per milliseconds fixed for the user
+    #[ink(message)]
+    fn stable_borrow_rate_of(&self, account: AccountId) -> WrappedU256;
     /// Get user's borrow with interest
     #[ink(message)]
     fn borrow_balance_current(&mut self, account: AccountId) -> Result<Balance>;
+    /// Get collateral detail of an account with interest projected to `at` (default: current block) without accruing it
+    #[ink(message)]
+    fn get_account_snapshot_at(
+        &self,
+        account: AccountId,
+        at: Option<Timestamp>,
+    ) -> Result<(Balance, Balance, U256)>;
+    /// Get user's borrow with interest projected to `at` (default: current block)
+    #[ink(message)]
+    fn borrow_balance_at(&self, account: AccountId, at: Option<Timestamp>) -> Result<Balance>;
+    /// Total borrows with interest projected to `at` (default: current block)
+    #[ink(message)]
+    fn total_borrows_at(&self, at: Option<Timestamp>) -> Result<Balance>;
+    /// Total reserves with interest projected to `at` (default: current block)
+    #[ink(message)]
+    fn total_reserves_at(&self, at: Option<Timestamp>) -> Result<Balance>;
+    /// Borrow index projected to `at` (default: current block)
+    #[ink(message)]
+    fn borrow_index_at(&self, at: Option<Timestamp>) -> Result<WrappedU256>;
     /// Get last block stamp of interest calculation process execution
     #[ink(message)]
     fn get_accrual_block_timestamp(&self) -> Timestamp;
     /// Calculates the current borrow interest rate per milliseconds
     #[ink(message)]
     fn borrow_rate_per_msec(&self) -> WrappedU256;
+    /// Calculates the current stable borrow interest rate per milliseconds
+    #[ink(message)]
+    fn stable_borrow_rate_per_msec(&self) -> WrappedU256;
     /// Calculates the current supply interest rate per milliseconds
     #[ink(message)]
     fn supply_rate_per_msec(&self) -> WrappedU256;
@@ -214,12 +314,24 @@ pub trait Pool: PSP22 + PSP22Metadata {
     /// Calculate the current exchange rate
     #[ink(message)]
     fn exchange_rate_current(&mut self) -> Result<WrappedU256>;
+    /// Exchange rate with interest projected to `at` (default: current block)
+    #[ink(message)]
+    fn exchange_rate_at(&self, at: Option<Timestamp>) -> Result<WrappedU256>;
     /// Get Initial Exchange rate.
     #[ink(message)]
     fn initial_exchange_rate_mantissa(&self) -> WrappedU256;
     /// Maximum fraction of interest that can be set aside for reserves
     #[ink(message)]
     fn reserve_factor_mantissa(&self) -> WrappedU256;
+    /// Minimum underlying amount of the first mint into the pool
+    #[ink(message)]
+    fn minimum_initial_deposit(&self) -> Balance;
+    /// Whether the bad debt not covered by reserves is socialized to the suppliers
+    #[ink(message)]
+    fn socialize_bad_debt(&self) -> bool;
+    /// Maximum size of a stable rate borrow relative to the available liquidity
+    #[ink(message)]
+    fn max_stable_borrow_size_mantissa(&self) -> WrappedU256;
     /// Get Liquidation Threshold for
     #[ink(message)]
     fn liquidation_threshold(&self) -> u128;
@@ -257,6 +369,17 @@ pub struct PoolStatus {
     pub exchange_rate: U256,
 }
 
+/// Interest rate mode of a borrow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum BorrowRateMode {
+    /// Follows the borrow index of the pool
+    #[default]
+    Variable,
+    /// Fixed when borrowed, until swapped or rebalanced
+    Stable,
+}
+
 /// Custom error definitions for Pool
 #[derive(Debug, PartialEq, Eq, Encode, Decode)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -265,6 +388,7 @@ pub enum Error {
     InvalidParameter,
     BorrowCashNotAvailable,
     RedeemTransferOutNotPossible,
+    SeizeCashNotAvailable,
     LiquidateLiquidatorIsBorrower,
     LiquidateCloseAmountIsZero,
     AccrualBlockNumberIsNotFresh,
@@ -289,6 +413,13 @@ pub enum Error {
     AccrueRewardFailed,
     InvalidLiquidationThreshold,
     ReserveIsNotEnabledAsCollateral,
+    NoDebtOfSelectedType,
+    RebalanceStableBorrowRateConditionsNotMet,
+    MaxStableBorrowSizeExceeded,
+    SetMaxStableBorrowSizeBoundsCheck,
+    InsufficientCash,
+    MintedTokensIsZero,
+    InsufficientInitialDeposit,
     Controller(ControllerError),
     PSP22(PSP22Error),
     Lang(LangError),
@@ -0,0 +1,86 @@
+// Copyright 2023 Asynmatrix Pte. Ltd.
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use ink::prelude::vec::Vec;
+use openbrush::{
+    contracts::ownable::*,
+    traits::{
+        AccountId,
+        Timestamp,
+    },
+};
+use scale::{
+    Decode,
+    Encode,
+};
+
+#[openbrush::wrapper]
+pub type PriceAggregatorRef = dyn PriceAggregator;
+
+/// Trait defines the functions to manage the sources of a PriceAggregator.
+/// A PriceAggregator reads several PriceOracles per asset and serves the median of their prices.
+#[openbrush::trait_definition]
+pub trait PriceAggregator {
+    /// Returns the median price for the given asset, when it was updated and the sources used.
+    #[ink(message)]
+    fn get_aggregated_price(&self, asset: AccountId) -> Result<AggregatedPrice>;
+
+    /// Returns the source configuration for the given asset, if any.
+    #[ink(message)]
+    fn source_config(&self, asset: AccountId) -> Option<SourceConfig>;
+
+    /// Sets the source oracles, the quorum and the outlier threshold for the given asset.
+    #[ink(message)]
+    fn set_source_config(&mut self, asset: AccountId, config: SourceConfig) -> Result<()>;
+
+    /// Removes the source configuration for the given asset.
+    #[ink(message)]
+    fn remove_source_config(&mut self, asset: AccountId) -> Result<()>;
+}
+
+/// Sources of the price of an asset
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, Default)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct SourceConfig {
+    /// PriceOracles to read the price from
+    pub sources: Vec<AccountId>,
+    /// Minimum number of fresh sources agreeing with the median
+    pub min_sources: u32,
+    /// Maximum deviation from the median in basis points, beyond which a source is dropped
+    pub max_deviation: u128,
+}
+
+/// Median price and the sources it was taken from
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct AggregatedPrice {
+    pub price: u128,
+    /// Oldest update time among the sources used
+    pub updated_at: Timestamp,
+    pub sources: Vec<AccountId>,
+}
+
+/// Custom error definitions for PriceAggregator
+#[derive(Debug, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Error {
+    SourceConfigIsNotSet,
+    InvalidSourceConfig,
+    InsufficientSources,
+    Ownable(OwnableError),
+}
+
+impl From<OwnableError> for Error {
+    fn from(error: OwnableError) -> Self {
+        Error::Ownable(error)
+    }
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
@@ -8,7 +8,10 @@
 use openbrush::{
     contracts::ownable::*,
     modifiers,
-    traits::AccountId,
+    traits::{
+        AccountId,
+        Timestamp,
+    },
 };
 use scale::{
     Decode,
@@ -30,10 +33,22 @@ pub trait PriceOracle {
     #[ink(message)]
     fn get_underlying_price(&self, pool: AccountId) -> Option<u128>;
 
+    /// Returns the current price for the given asset and when it was updated, if available.
+    #[ink(message)]
+    fn get_price_data(&self, asset: AccountId) -> Option<(u128, Timestamp)>;
+
+    /// Returns the maximum age of the price for the given asset, 0 if it never goes stale.
+    #[ink(message)]
+    fn get_heartbeat(&self, asset: AccountId) -> Timestamp;
+
     /// Sets a fixed price for the given asset.
     #[ink(message)]
     fn set_fixed_price(&mut self, asset: AccountId, value: u128) -> Result<()>;
 
+    /// Sets the maximum age of the price for the given asset.
+    #[ink(message)]
+    fn set_heartbeat(&mut self, asset: AccountId, heartbeat: Timestamp) -> Result<()>;
+
     /// Authorize Sybil to update the price.
     #[ink(message)]
     #[modifiers(only_owner)]
@@ -48,11 +63,63 @@ pub trait PriceOracle {
     fn is_sybil_authorized(&self, sybil: AccountId) -> bool;
 }
 
+#[openbrush::wrapper]
+pub type PriceCircuitBreakerRef = dyn PriceCircuitBreaker;
+
+/// Trait defines the bounds on the price updates of a PriceOracle.
+/// An update out of the bounds is rejected, or kept pending until the owner confirms it.
+#[openbrush::trait_definition]
+pub trait PriceCircuitBreaker {
+    /// Returns the deviation bounds for the given asset, if any.
+    #[ink(message)]
+    fn deviation_bounds(&self, asset: AccountId) -> Option<DeviationBounds>;
+
+    /// Sets the deviation bounds for the given asset, None to remove them.
+    #[ink(message)]
+    fn set_deviation_bounds(
+        &mut self,
+        asset: AccountId,
+        bounds: Option<DeviationBounds>,
+    ) -> Result<()>;
+
+    /// Returns the price waiting for confirmation for the given asset, if any.
+    #[ink(message)]
+    fn pending_price(&self, asset: AccountId) -> Option<u128>;
+
+    /// Applies the price waiting for confirmation for the given asset if it is the expected one.
+    /// The expected price keeps a later update from being confirmed without review.
+    #[ink(message)]
+    fn confirm_pending_price(&mut self, asset: AccountId, price: u128) -> Result<()>;
+
+    /// Discards the price waiting for confirmation for the given asset.
+    #[ink(message)]
+    fn discard_pending_price(&mut self, asset: AccountId) -> Result<()>;
+}
+
+/// Maximum deviations of the price updates of an asset
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, Default)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct DeviationBounds {
+    /// Maximum deviation from the current price in basis points
+    pub max_update_deviation: u128,
+    /// Maximum deviation from the price at the start of the window in basis points
+    pub max_window_deviation: u128,
+    pub window: Timestamp,
+    /// Keep an update out of the bounds pending owner confirmation instead of rejecting it
+    pub pending_on_breach: bool,
+}
+
 /// Custom error definitions for PriceOracle
 #[derive(Debug, PartialEq, Eq, Encode, Decode)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum Error {
     CallerIsNotAuthorized,
+    InvalidDeviationBounds,
+    PendingPriceIsNotSet,
+    PendingPriceMismatch,
     Ownable(OwnableError),
 }
 
@@ -0,0 +1,108 @@
+// Copyright 2023 Asynmatrix Pte. Ltd.
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::types::WrappedU256;
+use ink::prelude::vec::Vec;
+use openbrush::{
+    contracts::ownable::*,
+    traits::{
+        AccountId,
+        Timestamp,
+    },
+};
+use scale::{
+    Decode,
+    Encode,
+};
+
+#[openbrush::wrapper]
+pub type TwapOracleRef = dyn TwapOracle;
+
+/// Trait defines the functions of a TwapOracle.
+/// A TwapOracle records the price accumulators of a DexPair per asset
+/// and serves the time-weighted average price over a window,
+/// normalised by the decimals of the tokens and priced in the base currency.
+#[openbrush::trait_definition]
+pub trait TwapOracle {
+    /// Returns the time-weighted average price of one whole unit of the given asset over its window.
+    /// The price is in the quote token of the pair, converted into the base currency by the quote oracle if set.
+    #[ink(message)]
+    fn consult(&self, asset: AccountId) -> Result<u128>;
+
+    /// Records the current price accumulator of the pair of the given asset.
+    #[ink(message)]
+    fn update(&mut self, asset: AccountId) -> Result<()>;
+
+    /// Returns the recorded observations of the given asset, from the oldest.
+    #[ink(message)]
+    fn observations(&self, asset: AccountId) -> Vec<TwapObservation>;
+
+    /// Returns the TWAP configuration of the given asset, if any.
+    #[ink(message)]
+    fn twap_config(&self, asset: AccountId) -> Option<TwapConfig>;
+
+    /// Sets the pair, the window and the observation requirements for the given asset.
+    /// The observations recorded so far are discarded.
+    #[ink(message)]
+    fn set_twap_config(&mut self, asset: AccountId, config: TwapConfig) -> Result<()>;
+}
+
+/// Source of the time-weighted average price of an asset
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, Default)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct TwapConfig {
+    /// DexPair quoting the asset
+    pub pair: AccountId,
+    /// Period the price is averaged over
+    pub window: Timestamp,
+    /// Minimum time between two observations
+    pub period: Timestamp,
+    /// Minimum number of observations within the window to answer
+    pub min_observations: u32,
+    /// Decimals of the asset
+    pub asset_decimals: u8,
+    /// Decimals of the other token of the pair
+    pub quote_decimals: u8,
+    /// PriceOracle of the other token of the pair, None if it is the base currency
+    pub quote_oracle: Option<AccountId>,
+}
+
+/// Price accumulator of a pair at a point in time
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, Default)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct TwapObservation {
+    pub timestamp: Timestamp,
+    pub price_cumulative: WrappedU256,
+}
+
+/// Custom error definitions for TwapOracle
+#[derive(Debug, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Error {
+    TwapConfigIsNotSet,
+    InvalidTwapConfig,
+    PairDoesNotContainAsset,
+    ObservationIsTooRecent,
+    InsufficientObservations,
+    QuotePriceIsNotAvailable,
+    PriceOverflow,
+    Ownable(OwnableError),
+}
+
+impl From<OwnableError> for Error {
+    fn from(error: OwnableError) -> Self {
+        Error::Ownable(error)
+    }
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
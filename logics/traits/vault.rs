@@ -0,0 +1,93 @@
+// Copyright 2023 Asynmatrix Pte. Ltd.
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use openbrush::traits::{
+    AccountId,
+    Balance,
+};
+
+use super::pool::Result;
+
+#[openbrush::wrapper]
+pub type VaultRef = dyn Vault;
+
+/// Tokenized vault interface (ERC-4626 style) of the pool
+///
+/// Assets are the underlying token and shares are the pool token.
+/// Conversions use the stored exchange rate, so call `accrue_interest` beforehand for up-to-date values.
+#[openbrush::trait_definition]
+pub trait Vault {
+    /// AccountId of the underlying asset
+    #[ink(message)]
+    fn asset(&self) -> Option<AccountId>;
+
+    /// Total underlying managed by the pool (cash + borrows - reserves)
+    #[ink(message)]
+    fn total_assets(&self) -> Balance;
+
+    /// Shares exchanged for the given assets, rounding down
+    #[ink(message)]
+    fn convert_to_shares(&self, assets: Balance) -> Balance;
+
+    /// Assets exchanged for the given shares, rounding down
+    #[ink(message)]
+    fn convert_to_assets(&self, shares: Balance) -> Balance;
+
+    /// Maximum assets that can be deposited for the receiver, `Balance::MAX` if unlimited
+    /// and 0 while minting is paused or the protocol is shut down
+    #[ink(message)]
+    fn max_deposit(&self, receiver: AccountId) -> Balance;
+
+    /// Maximum shares that can be minted for the receiver, rounding down
+    #[ink(message)]
+    fn max_mint(&self, receiver: AccountId) -> Balance;
+
+    /// Maximum assets that the owner can withdraw, rounding down, 0 while redeeming is paused
+    /// or the protocol is shut down with debt of the owner outstanding
+    ///
+    /// NOTE: Liquidity check of the controller is not reflected.
+    #[ink(message)]
+    fn max_withdraw(&self, owner: AccountId) -> Balance;
+
+    /// Maximum shares that the owner can redeem, rounding down, 0 while redeeming is paused
+    /// or the protocol is shut down with debt of the owner outstanding
+    ///
+    /// NOTE: Liquidity check of the controller is not reflected.
+    #[ink(message)]
+    fn max_redeem(&self, owner: AccountId) -> Balance;
+
+    /// Shares minted by depositing the given assets, rounding down
+    #[ink(message)]
+    fn preview_deposit(&self, assets: Balance) -> Balance;
+
+    /// Assets needed to mint the given shares, rounding up
+    #[ink(message)]
+    fn preview_mint(&self, shares: Balance) -> Balance;
+
+    /// Shares burned by withdrawing the given assets, rounding up
+    #[ink(message)]
+    fn preview_withdraw(&self, assets: Balance) -> Balance;
+
+    /// Assets received by redeeming the given shares, rounding down
+    #[ink(message)]
+    fn preview_redeem(&self, shares: Balance) -> Balance;
+
+    /// Sender deposits assets and the receiver gets the shares. Returns minted shares.
+    #[ink(message)]
+    fn deposit(&mut self, assets: Balance, receiver: AccountId) -> Result<Balance>;
+
+    /// Burns shares of the owner and sends the assets to the receiver. Returns burned shares.
+    ///
+    /// Sender needs PSP22 allowance of the owner for the shares if they are not the owner.
+    #[ink(message)]
+    fn withdraw(
+        &mut self,
+        assets: Balance,
+        receiver: AccountId,
+        owner: AccountId,
+    ) -> Result<Balance>;
+}
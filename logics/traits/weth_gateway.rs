@@ -74,6 +74,7 @@ pub enum Error {
     SafeETHTransferFailed,
     InsufficientPayback,
     WethIsNotSet,
+    ProtocolIsShutdown,
     Pool(PoolError),
     PSP22(PSP22Error),
 }
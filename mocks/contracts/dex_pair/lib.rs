@@ -0,0 +1,52 @@
+// Copyright 2023 Asynmatrix Pte. Ltd.
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+#![feature(min_specialization)]
+
+#[openbrush::contract]
+pub mod contract {
+    use logics::{
+        impls::dex_pair::*,
+        traits::types::WrappedU256,
+    };
+    use primitive_types::U256;
+
+    use openbrush::traits::Storage;
+
+    #[ink(storage)]
+    #[derive(Storage)]
+    pub struct DexPairContract {
+        #[storage_field]
+        data: Data,
+    }
+
+    impl DexPair for DexPairContract {}
+    impl Internal for DexPairContract {}
+
+    impl DexPairContract {
+        #[ink(constructor)]
+        pub fn new(token_0: AccountId, token_1: AccountId) -> Self {
+            Self {
+                data: Data {
+                    token_0,
+                    token_1,
+                    reserve_0: 0,
+                    reserve_1: 0,
+                    block_timestamp_last: 0,
+                    price_0_cumulative_last: WrappedU256::from(U256::zero()),
+                    price_1_cumulative_last: WrappedU256::from(U256::zero()),
+                },
+            }
+        }
+
+        #[ink(message)]
+        pub fn set_reserves(&mut self, reserve_0: Balance, reserve_1: Balance) -> Result<()> {
+            self._update(reserve_0, reserve_1)
+        }
+    }
+}
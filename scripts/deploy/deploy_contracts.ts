@@ -8,6 +8,7 @@ import {
   deployFlashLoanGateway,
   deployLens,
   deployLeverager,
+  deployLiquidationHelper,
   deployPriceOracle,
   deployWETHGateway,
 } from './../helper/deploy_helper'
@@ -73,6 +74,12 @@ export const deployContracts = async ({
     args: [signer.address],
   })
 
+  const liquidationHelper = await deployLiquidationHelper({
+    api,
+    signer,
+    args: [controller.address],
+  })
+
   return {
     lens,
     faucet,
@@ -83,5 +90,6 @@ export const deployContracts = async ({
     wethGateway,
     flashloanGateway,
     leverager,
+    liquidationHelper,
   }
 }
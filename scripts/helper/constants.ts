@@ -8,6 +8,7 @@ export const ROLE = {
   TOKEN_ADMIN: 937842313,
   BORROW_CAP_GUARDIAN: 181502825,
   PAUSE_GUARDIAN: 1332676982,
+  EMERGENCY_GUARDIAN: 2634797933,
 } as const
 
 export const ZERO_ADDRESS = encodeAddress(
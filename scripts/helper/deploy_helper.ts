@@ -5,31 +5,39 @@ import { LastArrayElement } from 'type-fest'
 
 import Controller_factory from '../../types/constructors/controller'
 import DefaultInterestRateModel_factory from '../../types/constructors/default_interest_rate_model'
+import DexPair_factory from '../../types/constructors/dex_pair'
 import Faucet_factory from '../../types/constructors/faucet'
 import FlashloanGateway_factory from '../../types/constructors/flashloan_gateway'
 import FlashloanReceiver_factory from '../../types/constructors/flashloan_receiver'
 import IncentivesController_factory from '../../types/constructors/incentives_controller'
 import Lens_factory from '../../types/constructors/lens'
 import Leverager_factory from '../../types/constructors/leverager'
+import LiquidationHelper_factory from '../../types/constructors/liquidation_helper'
 import Manager_factory from '../../types/constructors/manager'
 import Pool_factory from '../../types/constructors/pool'
+import PriceAggregator_factory from '../../types/constructors/price_aggregator'
 import PriceOracle_factory from '../../types/constructors/price_oracle'
 import PSP22Token_factory from '../../types/constructors/psp22_token'
+import TwapOracle_factory from '../../types/constructors/twap_oracle'
 import WETH_factory from '../../types/constructors/weth'
 import WETHGateway_factory from '../../types/constructors/weth_gateway'
 
 import Controller from '../../types/contracts/controller'
 import DefaultInterestRateModel from '../../types/contracts/default_interest_rate_model'
+import DexPair from '../../types/contracts/dex_pair'
 import Faucet from '../../types/contracts/faucet'
 import FlashloanGateway from '../../types/contracts/flashloan_gateway'
 import FlashloanReceiver from '../../types/contracts/flashloan_receiver'
 import IncentivesController from '../../types/contracts/incentives_controller'
 import Lens from '../../types/contracts/lens'
 import Leverager from '../../types/contracts/leverager'
+import LiquidationHelper from '../../types/contracts/liquidation_helper'
 import Manager from '../../types/contracts/manager'
 import Pool from '../../types/contracts/pool'
+import PriceAggregator from '../../types/contracts/price_aggregator'
 import PriceOracle from '../../types/contracts/price_oracle'
 import PSP22Token from '../../types/contracts/psp22_token'
+import TwapOracle from '../../types/contracts/twap_oracle'
 import WETH from '../../types/contracts/weth'
 import WETHGateway from '../../types/contracts/weth_gateway'
 
@@ -94,6 +102,45 @@ export const deployPriceOracle = async ({
   return result
 }
 
+export const deployPriceAggregator = async ({
+  api,
+  signer,
+  args,
+  option = defaultOption(api),
+}: FactoryArgs<PriceAggregator_factory['new']>): Promise<PriceAggregator> => {
+  const factory = new PriceAggregator_factory(api, signer)
+  const contract = await factory.new(...args, option)
+  const result = new PriceAggregator(contract.address, signer, api)
+  await afterDeployment(result.name, contract)
+  return result
+}
+
+export const deployTwapOracle = async ({
+  api,
+  signer,
+  args,
+  option = defaultOption(api),
+}: FactoryArgs<TwapOracle_factory['new']>): Promise<TwapOracle> => {
+  const factory = new TwapOracle_factory(api, signer)
+  const contract = await factory.new(...args, option)
+  const result = new TwapOracle(contract.address, signer, api)
+  await afterDeployment(result.name, contract)
+  return result
+}
+
+export const deployDexPair = async ({
+  api,
+  signer,
+  args,
+  option = defaultOption(api),
+}: FactoryArgs<DexPair_factory['new']>): Promise<DexPair> => {
+  const factory = new DexPair_factory(api, signer)
+  const contract = await factory.new(...args, option)
+  const result = new DexPair(contract.address, signer, api)
+  await afterDeployment(result.name, contract)
+  return result
+}
+
 export const deployFaucet = async ({
   api,
   signer,
@@ -293,3 +340,19 @@ export const deployLeverager = async ({
   await afterDeployment(result.name, contract)
   return result
 }
+
+export const deployLiquidationHelper = async ({
+  api,
+  signer,
+  args,
+  option = defaultOption(api),
+}: FactoryArgs<
+  LiquidationHelper_factory['new']
+>): Promise<LiquidationHelper> => {
+  const factory = new LiquidationHelper_factory(api, signer)
+  const contract = await factory.new(...args, option)
+
+  const result = new LiquidationHelper(contract.address, signer, api)
+  await afterDeployment(result.name, contract)
+  return result
+}
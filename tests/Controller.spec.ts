@@ -11,6 +11,7 @@ import {
   deployPriceOracle,
 } from '../scripts/helper/deploy_helper'
 import { getGasLimit } from '../scripts/helper/utils'
+import { RATE_MODELS } from '../scripts/interest_rates'
 import Controller from '../types/contracts/controller'
 import PriceOracle from '../types/contracts/price_oracle'
 import {
@@ -18,8 +19,16 @@ import {
   TEST_METADATAS,
   preparePoolWithMockToken,
   preparePoolsWithPreparedTokens,
+  seedPool,
 } from './testContractHelper'
-import { mantissa, shouldNotRevert, toDec18, toDec6 } from './testHelpers'
+import {
+  DEAD_SHARES,
+  mantissa,
+  shouldNotRevert,
+  sleep,
+  toDec18,
+  toDec6,
+} from './testHelpers'
 
 const MAX_CALL_WEIGHT = new BN(100_000_000_000).isub(BN_ONE).mul(BN_TEN)
 const PROOFSIZE = new BN(2_000_000)
@@ -141,6 +150,7 @@ describe('Controller spec', () => {
         poolAddr,
         ZERO_ADDRESS,
         0,
+        null,
       )
       expect(value.ok.err).toBe('MintIsPaused')
     })
@@ -269,6 +279,88 @@ describe('Controller spec', () => {
       )
       expect(value.ok.err).toBe('InsufficientLiquidity')
     })
+    it('check isolation mode', async () => {
+      const {
+        controller,
+        pools: { dai, usdc, usdt },
+        users,
+      } = await setupWithPools()
+      const [user] = users
+      await controller.tx.setIsolationMode(dai.pool.address, true, 100)
+      const amount = toDec18(1_000)
+      await seedPool(dai, user, amount)
+      await seedPool(usdc, user, amount)
+      expect(
+        (await dai.pool.query.usingReserveAsCollateral(user.address)).value.ok,
+      ).toBe(true)
+      // the deposit is not used as collateral along with the isolated one
+      expect(
+        (await usdc.pool.query.usingReserveAsCollateral(user.address)).value
+          .ok,
+      ).toBe(false)
+      const { value: value1 } = await usdc.pool
+        .withSigner(user)
+        .query.setUseReserveAsCollateral(true)
+      expect(value1.ok.err).toStrictEqual({
+        controller: 'InconsistentIsolationMode',
+      })
+
+      const { value: value2 } = await controller.query.borrowAllowed(
+        usdt.pool.address,
+        user.address,
+        1,
+        null,
+      )
+      expect(value2.ok.err).toBe('NotBorrowableInIsolation')
+
+      await controller.tx.setBorrowableInIsolation(usdt.pool.address, true)
+      const { value: value3 } = await controller.query.borrowAllowed(
+        usdt.pool.address,
+        user.address,
+        toDec18(1),
+        null,
+      )
+      expect(value3.ok.err).toBe('DebtCeilingReached')
+    })
+    it('check the debt ceiling against the debt of all the accounts', async () => {
+      const {
+        deployer,
+        controller,
+        pools: { dai, usdt },
+        users,
+        gasLimit,
+      } = await setupWithPools()
+      const [user1, user2] = users
+      const isolatedDebt = async () =>
+        (
+          await controller.query.isolatedDebt(dai.pool.address)
+        ).value.ok.toString()
+      await controller.tx.setIsolationMode(dai.pool.address, true, toDec6(150))
+      await controller.tx.setBorrowableInIsolation(usdt.pool.address, true)
+      await seedPool(usdt, deployer, toDec6(1_000))
+      for (const user of users) await seedPool(dai, user, toDec18(1_000))
+
+      await usdt.pool.withSigner(user1).tx.borrow(toDec6(100), { gasLimit })
+      expect(await isolatedDebt()).toBe(toDec6(100).toString())
+
+      // within the ceiling alone, but not with the debt of the other account
+      const { value } = await usdt.pool
+        .withSigner(user2)
+        .query.borrow(toDec6(100))
+      expect(value.ok.err).toStrictEqual({ controller: 'DebtCeilingReached' })
+      await usdt.pool.withSigner(user2).tx.borrow(toDec6(50), { gasLimit })
+      expect(await isolatedDebt()).toBe(toDec6(150).toString())
+
+      // repaying the whole borrow releases its debt
+      await usdt.token.withSigner(user1).tx.mint(user1.address, toDec6(1))
+      await usdt.token
+        .withSigner(user1)
+        .tx.approve(usdt.pool.address, toDec6(101))
+      await usdt.pool
+        .withSigner(user1)
+        .tx.repayBorrow(toDec6(101), { gasLimit })
+      expect(await isolatedDebt()).toBe(toDec6(50).toString())
+    })
   })
 
   describe('.liquidate_borrow_allowed', () => {
@@ -351,6 +443,79 @@ describe('Controller spec', () => {
       )
       expect(value.ok.err).toBe('TooMuchRepay')
     })
+    it('restarts the auction after the borrower recovers', async () => {
+      const {
+        deployer,
+        controller,
+        pools: { dai, usdc },
+        users: [borrower],
+        gasLimit,
+      } = await setupWithPools()
+      const collateralFactor = ONE_ETHER.mul(new BN(75)).div(new BN(100))
+      await controller.tx.setLiquidationAuction(60 * 60 * 1000, [ONE_ETHER])
+
+      // Prepares
+      await usdc.token.tx.mint(deployer.address, toDec6(10_000))
+      await usdc.token.tx.approve(usdc.pool.address, toDec6(10_000))
+      await usdc.pool.tx.mint(toDec6(10_000), { gasLimit })
+      await dai.token.tx.mint(borrower.address, toDec18(20_000))
+      await dai.token
+        .withSigner(borrower)
+        .tx.approve(dai.pool.address, toDec18(20_000))
+      await dai.pool.withSigner(borrower).tx.mint(toDec18(20_000), { gasLimit })
+      await usdc.pool
+        .withSigner(borrower)
+        .tx.borrow(toDec6(10_000), { gasLimit })
+      const auctionStart = async () => {
+        const { value } = await controller.query.liquidationAuctionStart(
+          borrower.address,
+        )
+        return value.ok
+      }
+
+      // unhealthy: the liquidation check starts the auction
+      await controller.tx.setCollateralFactorMantissa(dai.pool.address, [
+        new BN(1),
+      ])
+      await controller.tx.liquidateBorrowAllowed(
+        usdc.pool.address,
+        dai.pool.address,
+        deployer.address,
+        borrower.address,
+        0,
+        null,
+      )
+      const firstStart = await auctionStart()
+      expect(firstStart).not.toBe(null)
+
+      // healthy: redeeming resets the auction
+      await controller.tx.setCollateralFactorMantissa(dai.pool.address, [
+        collateralFactor,
+      ])
+      await dai.pool
+        .withSigner(borrower)
+        .tx.redeemUnderlying(toDec18(1), { gasLimit })
+      expect(await auctionStart()).toBe(null)
+
+      // unhealthy again: the auction starts over instead of resuming
+      await sleep(2000)
+      await controller.tx.setCollateralFactorMantissa(dai.pool.address, [
+        new BN(1),
+      ])
+      await controller.tx.liquidateBorrowAllowed(
+        usdc.pool.address,
+        dai.pool.address,
+        deployer.address,
+        borrower.address,
+        0,
+        null,
+      )
+      const secondStart = await auctionStart()
+      expect(secondStart).not.toBe(null)
+      expect(
+        new BN(secondStart.toString()).gt(new BN(firstStart.toString())),
+      ).toBe(true)
+    })
   })
 
   describe('.seize_allowed', () => {
@@ -421,6 +586,7 @@ describe('Controller spec', () => {
         sym.token.address,
         toParam(ONE_ETHER.mul(new BN(90)).div(new BN(100))),
       )
+      await seedPool(sym, deployer)
     }
     //// use protocol
     for await (const { sym, value, user } of [
@@ -612,6 +778,7 @@ describe('Controller spec', () => {
         sym.token.address,
         [ONE_ETHER.mul(new BN(90)).div(new BN(100))],
       )
+      await seedPool(sym, deployer)
     }
 
     const getAccountAssets = async (address: string) =>
@@ -728,6 +895,7 @@ describe('Controller spec', () => {
             sym.token.address,
             [ONE_ETHER.mul(new BN(90)).div(new BN(100))],
           )
+          await seedPool(sym, deployer)
         }
         //// use protocol
         for await (const { sym, value, user } of [
@@ -829,6 +997,7 @@ describe('Controller spec', () => {
             sym.token.address,
             [ONE_ETHER.mul(new BN(90)).div(new BN(100))],
           )
+          await seedPool(sym, deployer)
         }
 
         //// use protocol
@@ -1053,6 +1222,103 @@ describe('Controller spec', () => {
         )
         expect(1).toEqual(1)
       })
+
+      it('with interest not yet accrued by the pool', async () => {
+        const { api, deployer, controller, priceOracle, users, gasLimit } =
+          await setup()
+        const daiRateModel = RATE_MODELS.dai
+        const rateModel = await deployDefaultInterestRateModel({
+          api,
+          signer: deployer,
+          args: [
+            [daiRateModel.baseRatePerYear()],
+            [daiRateModel.multiplierPerYearSlope1()],
+            [daiRateModel.multiplierPerYearSlope2()],
+            [daiRateModel.kink()],
+          ],
+        })
+        const incentivesController = await deployIncentivesController({
+          api,
+          signer: deployer,
+          args: [],
+        })
+        const { dai, usdt } = await preparePoolsWithPreparedTokens({
+          api,
+          controller,
+          rateModel,
+          signer: deployer,
+          incentivesController,
+          manager: deployer.address,
+        })
+        const user = users[0]
+        for (const sym of [dai, usdt]) {
+          await priceOracle.tx.setFixedPrice(sym.token.address, ONE_ETHER)
+          await controller.tx.supportMarketWithCollateralFactorMantissa(
+            sym.pool.address,
+            sym.token.address,
+            [ONE_ETHER.mul(new BN(90)).div(new BN(100))],
+          )
+          await seedPool(sym, deployer)
+        }
+        await dai.token
+          .withSigner(deployer)
+          .tx.mint(deployer.address, toDec18(100_000))
+        await dai.token
+          .withSigner(deployer)
+          .tx.approve(dai.pool.address, toDec18(100_000))
+        await dai.pool
+          .withSigner(deployer)
+          .tx.mint(toDec18(100_000), { gasLimit })
+        await usdt.token
+          .withSigner(deployer)
+          .tx.mint(user.address, toDec6(300_000))
+        await usdt.token
+          .withSigner(user)
+          .tx.approve(usdt.pool.address, toDec6(300_000))
+        await usdt.pool.withSigner(user).tx.mint(toDec6(300_000), { gasLimit })
+        await dai.pool.withSigner(user).tx.borrow(toDec18(50_000), { gasLimit })
+
+        const collateral = new BN(300_000 * 0.9).mul(mantissa())
+        const expectLiquidity = async (borrowBalance: BN) => {
+          const [liquidity, shortfall] = (
+            await controller.query.getAccountLiquidity(user.address)
+          ).value.ok.ok
+          expect(liquidity.toString()).toEqual(
+            collateral.sub(borrowBalance).toString(),
+          )
+          expect(shortfall.toNumber()).toEqual(0)
+        }
+
+        // time passes without any action on the dai pool
+        await sleep(2000)
+        await priceOracle.tx.setFixedPrice(usdt.token.address, ONE_ETHER)
+        const stored = new BN(
+          (
+            await dai.pool.query.borrowBalanceStored(user.address)
+          ).value.ok.toString(),
+        )
+        const projected = new BN(
+          (
+            await dai.pool.query.borrowBalanceAt(user.address, null)
+          ).value.ok.ok.toString(),
+        )
+        expect(projected.gt(stored)).toBe(true)
+        await expectLiquidity(projected)
+
+        // agree with the stored values once the pool has been accrued
+        await dai.pool.tx.accrueInterest({ gasLimit })
+        const accrued = new BN(
+          (
+            await dai.pool.query.borrowBalanceStored(user.address)
+          ).value.ok.toString(),
+        )
+        expect(
+          (
+            await dai.pool.query.borrowBalanceAt(user.address, null)
+          ).value.ok.ok.toString(),
+        ).toEqual(accrued.toString())
+        await expectLiquidity(accrued)
+      })
     })
   })
 
@@ -1115,7 +1381,7 @@ describe('Controller spec', () => {
 
       expect(
         (await dai.pool.query.balanceOf(users[0].address)).value.ok.toNumber(),
-      ).toEqual(daiDeposited)
+      ).toEqual(daiDeposited - DEAD_SHARES)
 
       await shouldNotRevert(usdc.token, 'mint', [
         deployer.address,
@@ -1129,7 +1395,7 @@ describe('Controller spec', () => {
 
       expect(
         (await usdc.pool.query.balanceOf(deployer.address)).value.ok.toNumber(),
-      ).toEqual(usdcDeposited)
+      ).toEqual(usdcDeposited - DEAD_SHARES)
 
       await shouldNotRevert(dai.pool, 'borrow', [daiBorrowed, { gasLimit }])
 
@@ -1152,7 +1418,7 @@ describe('Controller spec', () => {
             deployerAccountData.totalCollateralInBaseCurrency.toString(),
           ).toString(),
         ).toString(),
-      ).toEqual(new BN(usdcDeposited).toString())
+      ).toEqual(new BN(usdcDeposited - DEAD_SHARES).toString())
 
       // Total Debt In Eth
       expect(
@@ -1170,6 +1436,34 @@ describe('Controller spec', () => {
     })
   })
 
+  it('.shutdown freezes only the prices within the heartbeat', async () => {
+    const {
+      controller,
+      priceOracle,
+      pools: { dai, usdc },
+    } = await setupWithPools()
+    const settlementPrice = async (pool: string) =>
+      (await controller.query.settlementPrice(pool)).value.ok
+
+    // the price of dai gets older than its heartbeat
+    await shouldNotRevert(priceOracle, 'setHeartbeat', [dai.token.address, 1])
+    await sleep(2000)
+    await shouldNotRevert(controller, 'shutdown', [])
+
+    expect(await settlementPrice(dai.pool.address)).toBe(null)
+    expect((await settlementPrice(usdc.pool.address)).toString()).toBe(
+      ONE_ETHER.toString(),
+    )
+
+    await shouldNotRevert(controller, 'setSettlementPrice', [
+      dai.pool.address,
+      ONE_ETHER,
+    ])
+    expect((await settlementPrice(dai.pool.address)).toString()).toBe(
+      ONE_ETHER.toString(),
+    )
+  })
+
   it('.support_market_same_underlying', async () => {
     /*
     reproduced in `tests/Controller.spec.ts`
@@ -19,7 +19,11 @@ import Lens from '../types/contracts/lens'
 import Pool from '../types/contracts/pool'
 import PriceOracle from '../types/contracts/price_oracle'
 import PSP22Token from '../types/contracts/psp22_token'
-import { shouldNotRevert, shouldNotRevertWithNetworkGas } from './testHelpers'
+import {
+  DEAD_SHARES,
+  shouldNotRevert,
+  shouldNotRevertWithNetworkGas,
+} from './testHelpers'
 
 const MAX_CALL_WEIGHT = new BN(100_000_000_000).isub(BN_ONE).mul(BN_TEN)
 const PROOFSIZE = new BN(2_000_000)
@@ -343,6 +347,8 @@ describe('Lens', () => {
   })
   describe('reflect pool values', () => {
     const balance = 1000
+    // supplied beforehand so that the signer does not mint the dead shares
+    const seeded = DEAD_SHARES * 2
     beforeEach(async () => {
       ;({
         lens,
@@ -350,6 +356,8 @@ describe('Lens', () => {
         pools,
         controller,
         faucet,
+        deployer,
+        gasLimit,
         users: [signer],
       } = await setup())
       await shouldNotRevert(faucet, 'mintUnderlyingAll', [
@@ -357,6 +365,9 @@ describe('Lens', () => {
         balance,
         signer.address,
       ])
+      await shouldNotRevert(tokens[0], 'mint', [deployer.address, seeded])
+      await shouldNotRevert(tokens[0], 'approve', [pools[0].address, seeded])
+      await shouldNotRevert(pools[0], 'mint', [seeded, { gasLimit }])
     })
     it('faucet', async () => {
       const {
@@ -389,8 +400,8 @@ describe('Lens', () => {
         },
       } = await lens.query.poolBalancesAll([pool.address], signer.address)
 
-      expect(metadata.totalSupply.toNumber()).toBe(depositAmount)
-      expect(metadata.totalCash.toNumber()).toBe(depositAmount)
+      expect(metadata.totalSupply.toNumber()).toBe(seeded + depositAmount)
+      expect(metadata.totalCash.toNumber()).toBe(seeded + depositAmount)
 
       expect(balances.balanceOf.toNumber()).toBe(depositAmount)
       expect(balances.tokenBalance.toNumber()).toBe(balance - depositAmount)
@@ -418,8 +429,12 @@ describe('Lens', () => {
         },
       } = await lens.query.poolBalancesAll([pool.address], signer.address)
 
-      expect(metadata.totalSupply.toNumber()).toBe(depositAmount - redeemAmount)
-      expect(metadata.totalCash.toNumber()).toBe(depositAmount - redeemAmount)
+      expect(metadata.totalSupply.toNumber()).toBe(
+        seeded + depositAmount - redeemAmount,
+      )
+      expect(metadata.totalCash.toNumber()).toBe(
+        seeded + depositAmount - redeemAmount,
+      )
 
       expect(balances.balanceOf.toNumber()).toBe(depositAmount - redeemAmount)
       expect(balances.tokenBalance.toNumber()).toBe(
@@ -446,8 +461,10 @@ describe('Lens', () => {
         },
       } = await lens.query.poolBalancesAll([pool.address], signer.address)
 
-      expect(metadata.totalSupply.toNumber()).toBe(depositAmount)
-      expect(metadata.totalCash.toNumber()).toBe(depositAmount - borrowAmount)
+      expect(metadata.totalSupply.toNumber()).toBe(seeded + depositAmount)
+      expect(metadata.totalCash.toNumber()).toBe(
+        seeded + depositAmount - borrowAmount,
+      )
       expect(metadata.totalBorrows.toNumber()).toBe(borrowAmount)
 
       expect(balances.balanceOf.toNumber()).toBe(depositAmount)
@@ -481,9 +498,9 @@ describe('Lens', () => {
         },
       } = await lens.query.poolBalancesAll([pool.address], signer.address)
 
-      expect(metadata.totalSupply.toNumber()).toBe(depositAmount)
+      expect(metadata.totalSupply.toNumber()).toBe(seeded + depositAmount)
       expect(metadata.totalCash.toNumber()).toBe(
-        depositAmount - borrowAmount + repayAmount,
+        seeded + depositAmount - borrowAmount + repayAmount,
       )
       expect(metadata.totalBorrows.toNumber()).toBe(borrowAmount - repayAmount)
 
@@ -20,7 +20,11 @@ import {
   Pools,
   preparePoolsWithPreparedTokens,
 } from './testContractHelper'
-import { shouldNotRevert, shouldNotRevertWithNetworkGas } from './testHelpers'
+import {
+  DEAD_SHARES,
+  shouldNotRevert,
+  shouldNotRevertWithNetworkGas,
+} from './testHelpers'
 
 const MAX_CALL_WEIGHT = new BN(128_000_000_000).isub(BN_ONE).mul(BN_TEN)
 const PROOFSIZE = new BN(2_000_000)
@@ -144,6 +148,8 @@ describe('Leverager spec', () => {
 
     const depositedUser = (await dai.pool.query.balanceOf(deployer.address))
       .value.ok
-    expect(depositedUser.toNumber()).toEqual(borrowTotal + depositAmount)
+    expect(depositedUser.toNumber()).toEqual(
+      borrowTotal + depositAmount - DEAD_SHARES,
+    )
   })
 })
@@ -7,6 +7,7 @@ import {
   deployController,
   deployDefaultInterestRateModel,
   deployIncentivesController,
+  deployLiquidationHelper,
   deployPSP22Token,
   deployPoolFromAsset,
   deployPriceOracle,
@@ -22,8 +23,11 @@ import {
   ReserveUsedAsCollateralEnabled,
 } from '../types/event-types/pool'
 import { Transfer } from '../types/event-types/psp22_token'
+import { BorrowRateMode } from '../types/types-arguments/pool'
 import { Pools, preparePoolsWithPreparedTokens } from './testContractHelper'
 import {
+  DEAD_SHARES,
+  DEAD_SHARES_HOLDER,
   expectToEmit,
   mantissa,
   shouldNotRevert,
@@ -34,6 +38,7 @@ import {
 
 const MAX_CALL_WEIGHT = new BN(125_000_000_000).isub(BN_ONE).mul(BN_TEN)
 const PROOFSIZE = new BN(2_000_000)
+const MAX_U128 = new BN(2).pow(new BN(128)).isub(BN_ONE).toString()
 describe('Pool spec 1', () => {
   const setup = async (model?: DefaultInterestRateModel) => {
     const { api, alice: deployer, bob, charlie, django } = globalThis.setup
@@ -176,7 +181,8 @@ describe('Pool spec 1', () => {
 
     it('execute', async () => {
       const depositAmount = 3_000
-      const mintAmount = depositAmount * 2
+      // the first mint into the pool locks the dead shares
+      const mintAmount = depositAmount * 2 - DEAD_SHARES
       await shouldNotRevert(token, 'approve', [pool.address, depositAmount])
       const { events } = await shouldNotRevert(pool, 'mint', [depositAmount])
 
@@ -191,25 +197,33 @@ describe('Pool spec 1', () => {
         (
           await pool.query.balanceOfUnderlying(deployer.address)
         ).value.ok.toNumber(),
-      ).toBe(depositAmount) // NOTE: because balanceOf is converted to underlying value
+      ).toBe(mintAmount / 2) // NOTE: because balanceOf is converted to underlying value
       expect(
         (await pool.query.balanceOf(deployer.address)).value.ok.toNumber(),
       ).toBe(mintAmount)
+      expect(
+        (await pool.query.balanceOf(DEAD_SHARES_HOLDER)).value.ok.toNumber(),
+      ).toBe(DEAD_SHARES)
 
-      expect(events).toHaveLength(4)
+      expect(events).toHaveLength(5)
+      expectToEmit<Transfer>(events[1], 'Transfer', {
+        from: null,
+        to: DEAD_SHARES_HOLDER,
+        value: DEAD_SHARES,
+      })
       expectToEmit<ReserveUsedAsCollateralEnabled>(
-        events[1],
+        events[2],
         'ReserveUsedAsCollateralEnabled',
         {
           user: deployer.address,
         },
       )
-      expectToEmit<Transfer>(events[2], 'Transfer', {
+      expectToEmit<Transfer>(events[3], 'Transfer', {
         from: null,
         to: deployer.address,
         value: mintAmount,
       })
-      expectToEmit<Mint>(events[3], 'Mint', {
+      expectToEmit<Mint>(events[4], 'Mint', {
         minter: deployer.address,
         mintAmount: depositAmount,
         mintTokens: mintAmount,
@@ -217,6 +231,169 @@ describe('Pool spec 1', () => {
     })
   })
 
+  describe('.deposit / .withdraw (vault)', () => {
+    let deployer: KeyringPair
+    let users: KeyringPair[]
+    let token: PSP22Token
+    let pool: Pool
+    let gasLimit: WeightV2
+
+    beforeAll(async () => {
+      ;({
+        deployer,
+        users,
+        pools: {
+          dai: { token, pool },
+        },
+        gasLimit,
+      } = await setup())
+    })
+
+    const deposited = 10_000
+    it('views before deposit', async () => {
+      expect((await pool.query.asset()).value.ok).toEqual(token.address)
+      expect((await pool.query.totalAssets()).value.ok.toNumber()).toBe(0)
+      expect(
+        (await pool.query.previewDeposit(deposited)).value.ok.toNumber(),
+      ).toBe(deposited)
+      expect(
+        (await pool.query.maxDeposit(deployer.address)).value.ok.toString(),
+      ).toBe(MAX_U128)
+    })
+
+    it('deposit', async () => {
+      const [receiver] = users
+      await shouldNotRevert(token, 'mint', [deployer.address, deposited])
+      await shouldNotRevert(token, 'approve', [pool.address, deposited])
+      await shouldNotRevert(pool, 'deposit', [
+        deposited,
+        receiver.address,
+        { gasLimit },
+      ])
+      expect(
+        (await pool.query.balanceOf(receiver.address)).value.ok.toNumber(),
+      ).toBe(deposited - DEAD_SHARES)
+      expect((await pool.query.totalAssets()).value.ok.toNumber()).toBe(
+        deposited,
+      )
+      expect(
+        (await pool.query.maxWithdraw(receiver.address)).value.ok.toNumber(),
+      ).toBe(deposited - DEAD_SHARES)
+    })
+
+    it('withdraw by approved spender', async () => {
+      const [owner] = users
+      const withdrawAmount = 3_000
+      const { value } = await pool
+        .withSigner(deployer)
+        .query.withdraw(withdrawAmount, deployer.address, owner.address)
+      expect(value.ok.err).toStrictEqual({
+        psp22: { insufficientAllowance: null },
+      })
+
+      await shouldNotRevert(pool.withSigner(owner), 'approve', [
+        deployer.address,
+        withdrawAmount,
+      ])
+      await shouldNotRevert(pool.withSigner(deployer), 'withdraw', [
+        withdrawAmount,
+        deployer.address,
+        owner.address,
+        { gasLimit },
+      ])
+      expect(
+        (await token.query.balanceOf(deployer.address)).value.ok.toNumber(),
+      ).toBe(withdrawAmount)
+      expect(
+        (await pool.query.balanceOf(owner.address)).value.ok.toNumber(),
+      ).toBe(deposited - DEAD_SHARES - withdrawAmount)
+      expect(
+        (
+          await pool.query.allowance(owner.address, deployer.address)
+        ).value.ok.toNumber(),
+      ).toBe(0)
+    })
+  })
+
+  describe('.withdraw (vault) with an exchange rate other than 1', () => {
+    it('checks the liquidity by the burned shares', async () => {
+      const {
+        api,
+        deployer,
+        rateModel,
+        controller,
+        priceOracle,
+        incentivesController,
+        pools: { usdc },
+        users: [owner],
+        gasLimit,
+      } = await setup()
+
+      const token = await deployPSP22Token({
+        api,
+        signer: deployer,
+        args: [0, 'Sample', 'SAMPLE', 6],
+      })
+      const pool = await deployPoolFromAsset({
+        api,
+        signer: deployer,
+        args: [
+          incentivesController.address,
+          token.address,
+          controller.address,
+          rateModel.address,
+          deployer.address,
+          [ONE_ETHER.div(new BN(2)).toString()], // pool = underlying * 2
+          10000,
+        ],
+        token,
+      })
+      await priceOracle.tx.setFixedPrice(token.address, ONE_ETHER)
+      await controller.tx.supportMarketWithCollateralFactorMantissa(
+        pool.address,
+        token.address,
+        [ONE_ETHER.mul(new BN(90)).div(new BN(100))],
+      )
+
+      // the first mint locks the dead shares
+      await shouldNotRevert(token, 'mint', [deployer.address, DEAD_SHARES])
+      await shouldNotRevert(token, 'approve', [pool.address, DEAD_SHARES])
+      await shouldNotRevert(pool, 'mint', [DEAD_SHARES])
+      await shouldNotRevert(usdc.token, 'mint', [deployer.address, 10_000])
+      await shouldNotRevert(usdc.token, 'approve', [usdc.pool.address, 10_000])
+      await shouldNotRevert(usdc.pool, 'mint', [10_000])
+
+      // collateral: 10,000 * 90% = 9,000, borrow: 4,500
+      await shouldNotRevert(token, 'mint', [owner.address, 10_000])
+      await shouldNotRevert(token.withSigner(owner), 'approve', [
+        pool.address,
+        10_000,
+      ])
+      await shouldNotRevert(pool.withSigner(owner), 'mint', [10_000])
+      await shouldNotRevert(usdc.pool.withSigner(owner), 'borrow', [
+        4_500,
+        { gasLimit },
+      ])
+
+      // 5,000 * 90% = 4,500 is the most the remaining borrow power covers
+      const { value } = await pool
+        .withSigner(owner)
+        .query.withdraw(5_001, owner.address, owner.address)
+      expect(value.ok.err).toStrictEqual({
+        controller: 'InsufficientLiquidity',
+      })
+      await shouldNotRevert(pool.withSigner(owner), 'withdraw', [
+        5_000,
+        owner.address,
+        owner.address,
+        { gasLimit },
+      ])
+      expect(
+        (await pool.query.balanceOf(owner.address)).value.ok.toNumber(),
+      ).toBe(10_000)
+    })
+  })
+
   describe('.redeem_underlying', () => {
     let deployer: KeyringPair
     let token: PSP22Token
@@ -234,7 +411,7 @@ describe('Pool spec 1', () => {
     })
 
     const deposited = 10_000
-    const minted = deposited
+    const minted = deposited - DEAD_SHARES
     it('setup', async () => {
       await shouldNotRevert(token, 'mint', [deployer.address, deposited])
 
@@ -329,7 +506,7 @@ describe('Pool spec 1', () => {
       await shouldNotRevert(pool, 'mint', [toDec6(30_000), { gasLimit }])
       expect(
         (await pool.query.balanceOf(deployer.address)).value.ok.toNumber(),
-      ).toBe(toDec6(60_000).toNumber())
+      ).toBe(toDec6(60_000).toNumber() - DEAD_SHARES)
 
       await pool.withSigner(deployer).tx.redeemAll({ gasLimit })
       expect(
@@ -337,7 +514,56 @@ describe('Pool spec 1', () => {
       ).toBe(0)
       expect(
         (await token.query.balanceOf(deployer.address)).value.ok.toNumber(),
-      ).toBe(toDec6(60_000).toNumber())
+      ).toBe(toDec6(60_000).toNumber() - DEAD_SHARES)
+    })
+  })
+
+  describe('.skim', () => {
+    it('direct transfers do not move exchange rate and can be skimmed', async () => {
+      const {
+        deployer,
+        users,
+        pools: {
+          dai: { token, pool },
+        },
+        gasLimit,
+      } = await setup()
+      const [donor] = users
+
+      await shouldNotRevert(token, 'mint', [deployer.address, 10_000])
+      await shouldNotRevert(token, 'approve', [pool.address, 10_000])
+      await shouldNotRevert(pool, 'mint', [10_000, { gasLimit }])
+      const exchangeRate = (
+        await pool.query.exchangeRateStored()
+      ).value.ok.toString()
+
+      // donation
+      await shouldNotRevert(token, 'mint', [donor.address, 5_000])
+      await shouldNotRevert(token.withSigner(donor), 'transfer', [
+        pool.address,
+        5_000,
+        [],
+      ])
+      expect((await pool.query.getCashPrior()).value.ok.toNumber()).toBe(
+        10_000,
+      )
+      expect(
+        (await pool.query.exchangeRateStored()).value.ok.toString(),
+      ).toBe(exchangeRate)
+
+      const { value } = await pool.withSigner(donor).query.skim()
+      expect(value.ok.err).toStrictEqual({ callerIsNotManager: null })
+
+      await shouldNotRevert(pool, 'skim', [])
+      expect(
+        (await token.query.balanceOf(deployer.address)).value.ok.toNumber(),
+      ).toBe(5_000)
+      expect(
+        (await token.query.balanceOf(pool.address)).value.ok.toNumber(),
+      ).toBe(10_000)
+      expect((await pool.query.getCashPrior()).value.ok.toNumber()).toBe(
+        10_000,
+      )
     })
   })
 
@@ -372,7 +598,7 @@ describe('Pool spec 1', () => {
       )
       expect(
         (await pool.query.balanceOf(user1.address)).value.ok.toNumber(),
-      ).toEqual(5_000)
+      ).toEqual(5_000 - DEAD_SHARES)
       expect(
         (await pool.query.balanceOf(user2.address)).value.ok.toNumber(),
       ).toEqual(5_000)
@@ -420,6 +646,28 @@ describe('Pool spec 1', () => {
       expect(event2.args.accountBorrows.toNumber()).toEqual(2_500)
       expect(event2.args.totalBorrows.toNumber()).toEqual(5_500)
     })
+
+    it('projected views', async () => {
+      const [user1, user2] = users
+      expect(
+        (
+          await pool.query.borrowBalanceAt(user1.address, null)
+        ).value.ok.ok.toNumber(),
+      ).toEqual(3_000)
+      expect(
+        (
+          await pool.query.borrowBalanceAt(user2.address, null)
+        ).value.ok.ok.toNumber(),
+      ).toEqual(2_500)
+      expect(
+        (await pool.query.totalBorrowsAt(null)).value.ok.ok.toNumber(),
+      ).toEqual(5_500)
+      expect(
+        (await pool.query.exchangeRateAt(null)).value.ok.ok.toString(),
+      ).toEqual((await pool.query.exchangeRateStored()).value.ok.toString())
+      const { value } = await pool.query.totalBorrowsAt(0)
+      expect(value.ok.err).toStrictEqual({ invalidParameter: null })
+    })
   })
 
   describe('.borrow_for', () => {
@@ -450,7 +698,7 @@ describe('Pool spec 1', () => {
       )
       expect(
         (await pool.query.balanceOf(user1.address)).value.ok.toNumber(),
-      ).toEqual(5_000)
+      ).toEqual(5_000 - DEAD_SHARES)
     })
 
     it('execute', async () => {
@@ -491,6 +739,95 @@ describe('Pool spec 1', () => {
     })
   })
 
+  describe('.borrow_with_rate_mode (stable)', () => {
+    let deployer: KeyringPair
+    let token: PSP22Token
+    let pool: Pool
+    let users: KeyringPair[]
+    let gasLimit: WeightV2
+
+    beforeAll(async () => {
+      ;({
+        deployer,
+        users,
+        pools: {
+          dai: { token, pool },
+        },
+        gasLimit,
+      } = await setup())
+    })
+
+    it('preparations', async () => {
+      const [user1] = users
+      await token.withSigner(deployer).tx.mint(user1.address, 5_000)
+      await token.withSigner(user1).tx.approve(pool.address, 5_000)
+      await pool.withSigner(user1).tx.mint(5_000, { gasLimit })
+      await token.withSigner(deployer).tx.mint(deployer.address, 10_000)
+      await token.withSigner(deployer).tx.approve(pool.address, 10_000)
+      await pool.withSigner(deployer).tx.mint(10_000, { gasLimit })
+    })
+
+    it('rejects a borrow over the max size', async () => {
+      const [user1] = users
+      // 25% of the available liquidity: 15,000 * 25% = 3,750
+      const { value } = await pool
+        .withSigner(user1)
+        .query.borrowWithRateMode(3_751, BorrowRateMode.stable)
+      expect(value.ok.err).toStrictEqual({ maxStableBorrowSizeExceeded: null })
+    })
+
+    it('execute', async () => {
+      const [user1] = users
+      const { events } = await pool
+        .withSigner(user1)
+        .tx.borrowWithRateMode(3_000, BorrowRateMode.stable, { gasLimit })
+
+      expect(
+        (await token.query.balanceOf(user1.address)).value.ok.toNumber(),
+      ).toEqual(3_000)
+      expect(events[1].name).toEqual('Borrow')
+      expect((await pool.query.totalBorrows()).value.ok.toNumber()).toEqual(
+        3_000,
+      )
+      expect(
+        (await pool.query.totalStableBorrows()).value.ok.toNumber(),
+      ).toEqual(3_000)
+      expect(
+        (
+          await pool.query.stableBorrowBalanceStored(user1.address)
+        ).value.ok.toNumber(),
+      ).toEqual(3_000)
+      expect(
+        (
+          await pool.query.borrowBalanceStored(user1.address)
+        ).value.ok.toNumber(),
+      ).toEqual(3_000)
+    })
+
+    it('swap to variable', async () => {
+      const [user1] = users
+      const { value } = await pool
+        .withSigner(user1)
+        .query.swapBorrowRateMode(BorrowRateMode.variable)
+      expect(value.ok.err).toStrictEqual({ noDebtOfSelectedType: null })
+
+      const { events } = await shouldNotRevert(
+        pool.withSigner(user1),
+        'swapBorrowRateMode',
+        [BorrowRateMode.stable],
+      )
+      expect(events[events.length - 1].name).toEqual('SwapBorrowRateMode')
+      expect(
+        (await pool.query.totalStableBorrows()).value.ok.toNumber(),
+      ).toEqual(0)
+      expect(
+        (
+          await pool.query.borrowBalanceStored(user1.address)
+        ).value.ok.toNumber(),
+      ).toEqual(3_000)
+    })
+  })
+
   describe('.borrow (fail case)', () => {
     it('when no cash in pool', async () => {
       const {
@@ -538,7 +875,7 @@ describe('Pool spec 1', () => {
             await usdc.pool.query.balanceOf(deployer.address)
           ).value.ok.toString(),
         ).toString(),
-      ).toEqual(toDec6(10_000).toString())
+      ).toEqual(toDec6(10_000).subn(DEAD_SHARES).toString())
 
       // mint to dai pool for collateral
       const [user1] = users
@@ -554,7 +891,7 @@ describe('Pool spec 1', () => {
         BigInt(
           (await dai.pool.query.balanceOf(user1.address)).value.ok.toString(),
         ).toString(),
-      ).toEqual(toDec18(20_000).toString())
+      ).toEqual(toDec18(20_000).subn(DEAD_SHARES).toString())
 
       // borrow usdc
       await shouldNotRevertWithNetworkGas(
@@ -631,7 +968,7 @@ describe('Pool spec 1', () => {
             await usdc.pool.query.balanceOf(deployer.address)
           ).value.ok.toString(),
         ).toString(),
-      ).toEqual(toDec6(10_000).toString())
+      ).toEqual(toDec6(10_000).subn(DEAD_SHARES).toString())
 
       // mint to dai pool for collateral
       const [user1] = users
@@ -644,7 +981,7 @@ describe('Pool spec 1', () => {
         BigInt(
           (await dai.pool.query.balanceOf(user1.address)).value.ok.toString(),
         ).toString(),
-      ).toEqual(toDec18(20_000).toString())
+      ).toEqual(toDec18(20_000).subn(DEAD_SHARES).toString())
 
       // borrow usdc
       await shouldNotRevertWithNetworkGas(
@@ -775,7 +1112,7 @@ describe('Pool spec 1', () => {
       await usdc.pool.tx.mint(toDec6(10_000))
       expect(
         (await usdc.pool.query.balanceOf(deployer.address)).value.ok.toNumber(),
-      ).toEqual(toDec6(10_000).toNumber())
+      ).toEqual(toDec6(10_000).subn(DEAD_SHARES).toNumber())
 
       // mint to dai pool for collateral
       const [borrower] = users
@@ -790,7 +1127,7 @@ describe('Pool spec 1', () => {
             await dai.pool.query.balanceOf(borrower.address)
           ).value.ok.toString(),
         ).toString(),
-      ).toEqual(toDec18(20_000).toString())
+      ).toEqual(toDec18(20_000).subn(DEAD_SHARES).toString())
 
       // borrow usdc
       await shouldNotRevertWithNetworkGas(
@@ -823,7 +1160,7 @@ describe('Pool spec 1', () => {
         BigInt(9999) * BigInt(10) ** BigInt(18),
       )
 
-      return { deployer, controller, pools, users }
+      return { api, deployer, controller, pools, users }
     }
 
     it('execute', async () => {
@@ -850,6 +1187,7 @@ describe('Pool spec 1', () => {
           borrower.address,
           toDec6(5_000),
           collateral.pool.address,
+          false,
         )
 
       expect(
@@ -921,6 +1259,137 @@ describe('Pool spec 1', () => {
       // expect(totalReserves).toBeLessThanOrEqual(152n * dec18)
     })
 
+    it('execute with receiving underlying', async () => {
+      const {
+        controller,
+        pools: { dai: collateral, usdc: borrowing },
+        users,
+      } = await setupForShortage()
+      const [borrower, liquidator] = users
+      await borrowing.token.tx.mint(liquidator.address, toDec6(5_000))
+      await borrowing.token
+        .withSigner(liquidator)
+        .tx.approve(borrowing.pool.address, toDec6(5_000))
+
+      const liquidationIncentiveMantissa = mantissa()
+        .mul(new BN(108))
+        .div(new BN(100)) // 1.08
+      await controller.tx.setLiquidationIncentiveMantissa([
+        liquidationIncentiveMantissa,
+      ])
+      await borrowing.pool
+        .withSigner(liquidator)
+        .tx.liquidateBorrow(
+          borrower.address,
+          toDec6(5_000),
+          collateral.pool.address,
+          true,
+        )
+
+      expect(
+        (
+          await collateral.pool.query.balanceOf(liquidator.address)
+        ).value.ok.toNumber(),
+      ).toEqual(0)
+      // seized tokens without the protocol seize share (2.8%) are redeemed
+      const dec17 = BigInt(10) ** BigInt(17)
+      expect(
+        BigInt(
+          (
+            await collateral.token.query.balanceOf(liquidator.address)
+          ).value.ok.toString(),
+        ),
+      ).toBe(BigInt(52488) * dec17)
+    })
+
+    it('execute in batch', async () => {
+      const {
+        api,
+        deployer,
+        controller,
+        pools: { dai: collateral, usdc: borrowing },
+        users,
+      } = await setupForShortage()
+      const [borrower, liquidator] = users
+      const helper = await deployLiquidationHelper({
+        api,
+        signer: deployer,
+        args: [controller.address],
+      })
+      await borrowing.token.tx.mint(liquidator.address, toDec6(5_000))
+      await borrowing.token
+        .withSigner(liquidator)
+        .tx.approve(helper.address, toDec6(5_000))
+
+      const liquidations = [
+        {
+          borrower: borrower.address,
+          repayAmount: toDec6(2_000),
+          collateral: collateral.pool.address,
+        },
+        {
+          borrower: liquidator.address,
+          repayAmount: toDec6(1_000),
+          collateral: collateral.pool.address,
+        },
+        {
+          borrower: borrower.address,
+          repayAmount: toDec6(2_000),
+          collateral: collateral.pool.address,
+        },
+      ]
+      const { value: aborted } = await helper
+        .withSigner(liquidator)
+        .query.liquidateBorrows(
+          borrowing.pool.address,
+          liquidations,
+          false,
+          false,
+        )
+      expect(aborted.ok.err).toStrictEqual({ liquidatorIsBorrower: null })
+
+      const { value } = await helper
+        .withSigner(liquidator)
+        .query.liquidateBorrows(
+          borrowing.pool.address,
+          liquidations,
+          false,
+          true,
+        )
+      expect(value.ok.ok).toStrictEqual([
+        { ok: null },
+        { err: { liquidatorIsBorrower: null } },
+        { ok: null },
+      ])
+
+      await helper
+        .withSigner(liquidator)
+        .tx.liquidateBorrows(
+          borrowing.pool.address,
+          liquidations,
+          false,
+          true,
+        )
+      expect(
+        (
+          await borrowing.pool.query.borrowBalanceStored(borrower.address)
+        ).value.ok.toNumber(),
+      ).toEqual(toDec6(6_000).toNumber())
+      // the repay amount of the failed entry is returned
+      expect(
+        (
+          await borrowing.token.query.balanceOf(liquidator.address)
+        ).value.ok.toNumber(),
+      ).toEqual(toDec6(1_000).toNumber())
+      expect(
+        BigInt(
+          (
+            await collateral.pool.query.balanceOf(liquidator.address)
+          ).value.ok.toString(),
+        ),
+      ).toBeGreaterThan(BigInt(0))
+    })
+
     it('fail when liquidator is equal to borrower', async () => {
       const {
         pools: { dai: collateral, usdc: borrowing },
@@ -929,7 +1398,12 @@ describe('Pool spec 1', () => {
       const [borrower] = users
       const { value } = await borrowing.pool
         .withSigner(borrower)
-        .query.liquidateBorrow(borrower.address, 0, collateral.pool.address)
+        .query.liquidateBorrow(
+          borrower.address,
+          0,
+          collateral.pool.address,
+          false,
+        )
       expect(value.ok.err).toStrictEqual({
         liquidateLiquidatorIsBorrower: null,
       })
@@ -942,7 +1416,12 @@ describe('Pool spec 1', () => {
       const [borrower, liquidator] = users
       const { value } = await borrowing.pool
         .withSigner(liquidator)
-        .query.liquidateBorrow(borrower.address, 0, collateral.pool.address)
+        .query.liquidateBorrow(
+          borrower.address,
+          0,
+          collateral.pool.address,
+          false,
+        )
       expect(value.ok.err).toStrictEqual({
         liquidateCloseAmountIsZero: null,
       })
@@ -982,6 +1461,7 @@ describe('Pool spec 1', () => {
       deployer.address,
       users[0].address,
       toDec18(100),
+      false,
     )
     expect(val1.ok.err).toEqual({ reserveIsNotEnabledAsCollateral: null })
     // call usdc pool from user
@@ -989,6 +1469,7 @@ describe('Pool spec 1', () => {
       deployer.address,
       users[0].address,
       toDec6(100),
+      false,
     )
     expect(val2.ok.err).toEqual({ reserveIsNotEnabledAsCollateral: null })
   })
@@ -1017,7 +1498,7 @@ describe('Pool spec 1', () => {
             await usdc.pool.query.balanceOf(deployer.address)
           ).value.ok.toString(),
         ).toString(),
-      ).toEqual(toDec6(100_000).toString())
+      ).toEqual(toDec6(100_000).subn(DEAD_SHARES).toString())
       // mint to dai pool for collateral
       const [user1, user2] = users
       await dai.token.tx.mint(user1.address, toDec18(20_000))
@@ -1030,7 +1511,7 @@ describe('Pool spec 1', () => {
         BigInt(
           (await dai.pool.query.balanceOf(user1.address)).value.ok.toString(),
         ).toString(),
-      ).toEqual(toDec18(20_000).toString())
+      ).toEqual(toDec18(20_000).subn(DEAD_SHARES).toString())
       await usdc.token.tx.mint(user2.address, toDec6(20_000))
       // borrow usdc
       await shouldNotRevertWithNetworkGas(
@@ -0,0 +1,105 @@
+import { ONE_ETHER } from '../scripts/helper/constants'
+import {
+  deployDexPair,
+  deployPSP22Token,
+  deployPriceOracle,
+  deployTwapOracle,
+} from '../scripts/helper/deploy_helper'
+import { shouldNotRevert, sleep, toDec18, toDec6 } from './testHelpers'
+
+describe('TwapOracle spec', () => {
+  const setup = async () => {
+    const { api, alice: deployer } = globalThis.setup
+
+    const usdc = await deployPSP22Token({
+      api,
+      signer: deployer,
+      args: [0, 'USD Coin', 'USDC', 6],
+    })
+    const weth = await deployPSP22Token({
+      api,
+      signer: deployer,
+      args: [0, 'Wrapped Ether', 'WETH', 18],
+    })
+    const pair = await deployDexPair({
+      api,
+      signer: deployer,
+      args: [usdc.address, weth.address],
+    })
+    const twapOracle = await deployTwapOracle({
+      api,
+      signer: deployer,
+      args: [],
+    })
+    const priceOracle = await deployPriceOracle({
+      api,
+      signer: deployer,
+      args: [],
+    })
+
+    // 1,000 USDC against 2,000 WETH: 1 USDC is worth 2 WETH
+    await shouldNotRevert(pair, 'setReserves', [toDec6(1_000), toDec18(2_000)])
+
+    return { usdc, weth, pair, twapOracle, priceOracle }
+  }
+
+  const twapConfig = (pair: string, quoteOracle: string | null) => ({
+    pair,
+    window: 60_000,
+    period: 1_000,
+    minObservations: 2,
+    assetDecimals: 6,
+    quoteDecimals: 18,
+    quoteOracle,
+  })
+
+  const observe = async (
+    twapOracle: Awaited<ReturnType<typeof setup>>['twapOracle'],
+    asset: string,
+  ) => {
+    for (let i = 0; i < 2; i++) {
+      await shouldNotRevert(twapOracle, 'update', [asset])
+      await sleep(2000)
+    }
+  }
+
+  it('normalises the price by the decimals of the tokens', async () => {
+    const { usdc, pair, twapOracle } = await setup()
+    await shouldNotRevert(twapOracle, 'setTwapConfig', [
+      usdc.address,
+      twapConfig(pair.address, null),
+    ])
+    await observe(twapOracle, usdc.address)
+
+    const price = (await twapOracle.query.consult(usdc.address)).value.ok.ok
+    expect(price.toString()).toBe(ONE_ETHER.muln(2).toString())
+  })
+
+  it('converts the price into the base currency by the quote oracle', async () => {
+    const { usdc, weth, pair, twapOracle, priceOracle } = await setup()
+    await shouldNotRevert(priceOracle, 'setFixedPrice', [
+      weth.address,
+      ONE_ETHER.divn(2),
+    ])
+    await shouldNotRevert(twapOracle, 'setTwapConfig', [
+      usdc.address,
+      twapConfig(pair.address, priceOracle.address),
+    ])
+    await observe(twapOracle, usdc.address)
+
+    const price = (await twapOracle.query.consult(usdc.address)).value.ok.ok
+    expect(price.toString()).toBe(ONE_ETHER.toString())
+  })
+
+  it('fails without the price of the quote token', async () => {
+    const { usdc, pair, twapOracle, priceOracle } = await setup()
+    await shouldNotRevert(twapOracle, 'setTwapConfig', [
+      usdc.address,
+      twapConfig(pair.address, priceOracle.address),
+    ])
+    await observe(twapOracle, usdc.address)
+
+    const res = (await twapOracle.query.consult(usdc.address)).value.ok
+    expect(res.err).toStrictEqual({ quotePriceIsNotAvailable: null })
+  })
+})
@@ -16,7 +16,7 @@ import WETH from '../types/contracts/weth'
 import WETHGateway from '../types/contracts/weth_gateway'
 
 import { Pools, preparePoolsWithPreparedTokens } from './testContractHelper'
-import { shouldNotRevert } from './testHelpers'
+import { DEAD_SHARES, shouldNotRevert } from './testHelpers'
 
 const MAX_CALL_WEIGHT = new BN(100_000_000_000).isub(BN_ONE).mul(BN_TEN)
 const PROOFSIZE = new BN(2_000_000)
@@ -138,7 +138,7 @@ describe('WETHGateway spec', () => {
     expect((await weth.query.tokenDecimals()).value.ok).toEqual(18)
   })
 
-  const depositAmount = 3000
+  const depositAmount = 4000
   it('Deposit WETH', async () => {
     const { pool } = pools.weth
     const {
@@ -163,15 +163,16 @@ describe('WETHGateway spec', () => {
       afterWethContractBalance.sub(beforeWethContractBalance).toNumber(),
     ).toEqual(depositAmount)
 
+    // the first deposit into the pool locks the dead shares
     expect(
       (await pool.query.balanceOf(deployer.address)).value.ok.toString(),
-    ).toEqual(depositAmount.toString())
+    ).toEqual((depositAmount - DEAD_SHARES).toString())
 
     expect(
       (
         await pool.query.balanceOfUnderlying(deployer.address)
       ).value.ok.toString(),
-    ).toEqual(depositAmount.toString())
+    ).toEqual((depositAmount - DEAD_SHARES).toString())
   })
 
   const borrowAmount = 2000
@@ -1,5 +1,6 @@
 import type { ApiPromise } from '@polkadot/api'
 import type { KeyringPair } from '@polkadot/keyring/types'
+import { BN } from '@polkadot/util'
 import { ONE_ETHER } from '../scripts/helper/constants'
 import {
   deployPoolFromAsset,
@@ -11,6 +12,7 @@ import IncentivesController from '../types/contracts/incentives_controller'
 import Pool from '../types/contracts/pool'
 import PSP22Token from '../types/contracts/psp22_token'
 import WETH from '../types/contracts/weth'
+import { DEAD_SHARES } from './testHelpers'
 
 export type Metadata = {
   name: string
@@ -206,3 +208,15 @@ export const preparePoolsWithPreparedTokens = async ({
   })
   return { dai, usdc, usdt, weth }
 }
+
+// Supplies a small amount to the pool so that the dead shares locked by the
+// first mint do not affect the balances of the accounts under test
+export const seedPool = async (
+  { token, pool }: PoolContracts,
+  signer: KeyringPair,
+  amount: BN = new BN(DEAD_SHARES * 2),
+) => {
+  await token.withSigner(signer).tx.mint(signer.address, amount)
+  await token.withSigner(signer).tx.approve(pool.address, amount)
+  await pool.withSigner(signer).tx.mint(amount)
+}
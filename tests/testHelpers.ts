@@ -1,10 +1,14 @@
 import { ReturnNumber } from '@727-ventures/typechain-types'
+import { encodeAddress } from '@polkadot/keyring'
 import { WeightV2 } from '@polkadot/types/interfaces'
 import { BN, BN_TEN, BN_TWO } from '@polkadot/util'
 import { ReplacedType } from '../scripts/helper/utilityTypes'
 import { waitForTx } from '../scripts/helper/utils'
 
 export const mantissa = () => pow10(18)
+// pool tokens locked by the first mint into a pool
+export const DEAD_SHARES = 1_000
+export const DEAD_SHARES_HOLDER = encodeAddress(new Uint8Array(32).fill(0xff))
 export const toDec6 = (value: number): BN => toDec(value, 6)
 export const toDec18 = (value: number): BN => toDec(value, 18)
 